@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+
+/// Edition families an item can be limited to. The N, single language and
+/// workstation variants count as their base edition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Edition {
+    Home,
+    Pro,
+    Enterprise,
+    Education,
+}
+
+impl Edition {
+    /// Maps the `EditionID` registry value to its family, `None` for editions
+    /// the catalog has no use for (Server, IoT, Team, ...).
+    pub fn from_edition_id(edition_id: &str) -> Option<Edition> {
+        let id = edition_id.trim().to_lowercase();
+        // Pro Education is licensed and managed like Education.
+        if id.starts_with("education") || id.starts_with("professionaleducation") {
+            Some(Edition::Education)
+        } else if id.starts_with("core") {
+            Some(Edition::Home)
+        } else if id.starts_with("professional") {
+            Some(Edition::Pro)
+        } else if id.starts_with("enterprise") || id == "serverrdsh" {
+            // ServerRdsh is Enterprise multi-session.
+            Some(Edition::Enterprise)
+        } else {
+            None
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Edition::Home => "Home",
+            Edition::Pro => "Pro",
+            Edition::Enterprise => "Enterprise",
+            Edition::Education => "Education",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Architecture {
+    X64,
+    Arm64,
+    X86,
+}
+
+impl Architecture {
+    /// Parses `RuntimeInformation.OSArchitecture` or `PROCESSOR_ARCHITECTURE`.
+    pub fn parse(value: &str) -> Option<Architecture> {
+        match value.trim().to_lowercase().as_str() {
+            "x64" | "amd64" => Some(Architecture::X64),
+            "arm64" => Some(Architecture::Arm64),
+            "x86" => Some(Architecture::X86),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Architecture::X64 => "x64",
+            Architecture::Arm64 => "ARM64",
+            Architecture::X86 => "x86",
+        }
+    }
+}
+
+/// What a catalog item requires of the running system. Every field is
+/// optional; an empty list means any edition or architecture.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Requirements {
+    #[serde(default)]
+    pub min_build: Option<u32>,
+    #[serde(default)]
+    pub max_build: Option<u32>,
+    #[serde(default)]
+    pub editions: Vec<Edition>,
+    #[serde(default)]
+    pub architectures: Vec<Architecture>,
+}
+
+/// The running system as far as applicability is concerned. Parts that could
+/// not be read are `None` and never make an item inapplicable, so a failed
+/// query cannot hide the whole catalog.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Platform {
+    pub build: Option<u32>,
+    pub edition: Option<Edition>,
+    pub architecture: Option<Architecture>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Applicability {
+    pub applicable: bool,
+    /// Why the item does not apply, one entry per unmet requirement.
+    pub reasons: Vec<String>,
+}
+
+impl Default for Applicability {
+    fn default() -> Self {
+        Applicability {
+            applicable: true,
+            reasons: Vec::new(),
+        }
+    }
+}
+
+fn labels<T: Copy>(values: &[T], label: fn(T) -> &'static str) -> String {
+    values.iter().map(|value| label(*value)).collect::<Vec<_>>().join(", ")
+}
+
+pub fn evaluate(requirements: &Requirements, platform: &Platform) -> Applicability {
+    let mut reasons = Vec::new();
+
+    if let Some(build) = platform.build {
+        if let Some(min_build) = requirements.min_build.filter(|min_build| build < *min_build) {
+            reasons.push(format!("Requires Windows build {} or later (this is {})", min_build, build));
+        }
+        if let Some(max_build) = requirements.max_build.filter(|max_build| build > *max_build) {
+            reasons.push(format!("Only applies up to Windows build {} (this is {})", max_build, build));
+        }
+    }
+
+    if let Some(edition) = platform.edition {
+        if !requirements.editions.is_empty() && !requirements.editions.contains(&edition) {
+            reasons.push(format!(
+                "Only applies to {} editions (this is {})",
+                labels(&requirements.editions, Edition::label),
+                edition.label()
+            ));
+        }
+    }
+
+    if let Some(architecture) = platform.architecture {
+        if !requirements.architectures.is_empty() && !requirements.architectures.contains(&architecture) {
+            reasons.push(format!(
+                "Only applies to {} (this is {})",
+                labels(&requirements.architectures, Architecture::label),
+                architecture.label()
+            ));
+        }
+    }
+
+    Applicability {
+        applicable: reasons.is_empty(),
+        reasons,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(build: u32, edition: Edition, architecture: Architecture) -> Platform {
+        Platform {
+            build: Some(build),
+            edition: Some(edition),
+            architecture: Some(architecture),
+        }
+    }
+
+    #[test]
+    fn no_requirements_always_apply() {
+        let result = evaluate(&Requirements::default(), &platform(19045, Edition::Home, Architecture::X86));
+
+        assert_eq!(result, Applicability::default());
+    }
+
+    #[test]
+    fn build_range_is_inclusive() {
+        let requirements = Requirements {
+            min_build: Some(22621),
+            max_build: Some(22631),
+            ..Requirements::default()
+        };
+
+        assert!(evaluate(&requirements, &platform(22621, Edition::Pro, Architecture::X64)).applicable);
+        assert!(evaluate(&requirements, &platform(22631, Edition::Pro, Architecture::X64)).applicable);
+        assert!(!evaluate(&requirements, &platform(22000, Edition::Pro, Architecture::X64)).applicable);
+        assert!(!evaluate(&requirements, &platform(26100, Edition::Pro, Architecture::X64)).applicable);
+    }
+
+    #[test]
+    fn min_and_max_build_apply_on_their_own() {
+        let min_only = Requirements {
+            min_build: Some(26100),
+            ..Requirements::default()
+        };
+        let max_only = Requirements {
+            max_build: Some(22631),
+            ..Requirements::default()
+        };
+
+        assert!(evaluate(&min_only, &platform(26100, Edition::Home, Architecture::X64)).applicable);
+        assert!(evaluate(&min_only, &platform(26200, Edition::Home, Architecture::X64)).applicable);
+        assert_eq!(
+            evaluate(&min_only, &platform(26099, Edition::Home, Architecture::X64)).reasons,
+            vec!["Requires Windows build 26100 or later (this is 26099)"]
+        );
+
+        assert!(evaluate(&max_only, &platform(22631, Edition::Home, Architecture::X64)).applicable);
+        assert!(evaluate(&max_only, &platform(19045, Edition::Home, Architecture::X64)).applicable);
+        assert_eq!(
+            evaluate(&max_only, &platform(22632, Edition::Home, Architecture::X64)).reasons,
+            vec!["Only applies up to Windows build 22631 (this is 22632)"]
+        );
+    }
+
+    #[test]
+    fn unknown_build_keeps_build_limited_items() {
+        let requirements = Requirements {
+            min_build: Some(26100),
+            max_build: Some(26100),
+            editions: vec![Edition::Pro],
+            ..Requirements::default()
+        };
+        let platform = Platform {
+            build: None,
+            ..platform(0, Edition::Home, Architecture::X64)
+        };
+
+        // Only the known edition counts against the item.
+        assert_eq!(
+            evaluate(&requirements, &platform).reasons,
+            vec!["Only applies to Pro editions (this is Home)"]
+        );
+    }
+
+    #[test]
+    fn reports_every_unmet_requirement() {
+        let requirements = Requirements {
+            min_build: Some(26100),
+            editions: vec![Edition::Enterprise, Edition::Education],
+            architectures: vec![Architecture::Arm64],
+            ..Requirements::default()
+        };
+        let result = evaluate(&requirements, &platform(22631, Edition::Home, Architecture::X64));
+
+        assert!(!result.applicable);
+        assert_eq!(
+            result.reasons,
+            vec![
+                "Requires Windows build 26100 or later (this is 22631)",
+                "Only applies to Enterprise, Education editions (this is Home)",
+                "Only applies to ARM64 (this is x64)",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_platform_keeps_items() {
+        let requirements = Requirements {
+            min_build: Some(26100),
+            max_build: Some(26100),
+            editions: vec![Edition::Pro],
+            architectures: vec![Architecture::X64],
+        };
+
+        assert!(evaluate(&requirements, &Platform::default()).applicable);
+    }
+
+    #[test]
+    fn maps_edition_ids_to_families() {
+        assert_eq!(Edition::from_edition_id("Core"), Some(Edition::Home));
+        assert_eq!(Edition::from_edition_id("CoreSingleLanguage"), Some(Edition::Home));
+        assert_eq!(Edition::from_edition_id("Professional"), Some(Edition::Pro));
+        assert_eq!(Edition::from_edition_id("ProfessionalWorkstation"), Some(Edition::Pro));
+        assert_eq!(Edition::from_edition_id("ProfessionalEducation"), Some(Edition::Education));
+        assert_eq!(Edition::from_edition_id("EducationN"), Some(Edition::Education));
+        assert_eq!(Edition::from_edition_id("EnterpriseS"), Some(Edition::Enterprise));
+        assert_eq!(Edition::from_edition_id("ServerRdsh"), Some(Edition::Enterprise));
+        assert_eq!(Edition::from_edition_id("ServerStandard"), None);
+    }
+
+    #[test]
+    fn parses_architectures() {
+        assert_eq!(Architecture::parse("X64"), Some(Architecture::X64));
+        assert_eq!(Architecture::parse("AMD64"), Some(Architecture::X64));
+        assert_eq!(Architecture::parse("Arm64\r\n"), Some(Architecture::Arm64));
+        assert_eq!(Architecture::parse("Arm"), None);
+    }
+
+    #[test]
+    fn reads_requirements_from_catalog_json() {
+        let requirements: Requirements = serde_json::from_str(
+            r#"{"minBuild": 22621, "editions": ["pro", "enterprise"], "architectures": ["x64", "arm64"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(requirements.min_build, Some(22621));
+        assert_eq!(requirements.max_build, None);
+        assert_eq!(requirements.editions, vec![Edition::Pro, Edition::Enterprise]);
+        assert_eq!(requirements.architectures, vec![Architecture::X64, Architecture::Arm64]);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::risk;
+
+/// How long a batch stays open after it passed its checks. Long enough for
+/// a full preset of slow package removals.
+pub const BATCH_TTL: Duration = Duration::from_secs(60 * 60);
+
+struct OpenBatch {
+    /// Ids that have not run yet.
+    ids: BTreeSet<String>,
+    opened: Instant,
+}
+
+/// Batches that passed the checks of `begin_apply`, kept in Tauri's managed
+/// state. Each apply command presents the ticket of its batch, which admits
+/// every id of the batch exactly once, so nothing is applied without going
+/// through the checks first.
+#[derive(Default)]
+pub struct Tickets(Mutex<BTreeMap<String, OpenBatch>>);
+
+impl Tickets {
+    pub fn issue(&self, ids: BTreeSet<String>) -> String {
+        self.issue_at(ids, Instant::now())
+    }
+
+    fn issue_at(&self, ids: BTreeSet<String>, now: Instant) -> String {
+        let mut batches = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        batches.retain(|_, batch| now.duration_since(batch.opened) < BATCH_TTL);
+
+        let ticket = risk::new_token();
+        batches.insert(ticket.clone(), OpenBatch { ids, opened: now });
+        ticket
+    }
+
+    /// Lets `id` run if `ticket` belongs to an open batch that contains it and
+    /// it has not run yet.
+    pub fn admit(&self, ticket: Option<&str>, id: &str) -> Result<(), String> {
+        self.admit_at(ticket, id, Instant::now())
+    }
+
+    fn admit_at(&self, ticket: Option<&str>, id: &str, now: Instant) -> Result<(), String> {
+        let unchecked = || format!("{} has not been checked before applying; apply it again", id);
+        let ticket = ticket.ok_or_else(unchecked)?;
+
+        let mut batches = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let batch = batches.get_mut(ticket).ok_or_else(unchecked)?;
+        if now.duration_since(batch.opened) >= BATCH_TTL {
+            batches.remove(ticket);
+            return Err("The batch has expired; apply it again".to_string());
+        }
+        if !batch.ids.remove(id) {
+            return Err(unchecked());
+        }
+        if batch.ids.is_empty() {
+            batches.remove(ticket);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn admits_each_id_of_the_batch_once() {
+        let tickets = Tickets::default();
+        let ticket = tickets.issue(ids(&["a", "b"]));
+
+        assert!(tickets.admit(Some(&ticket), "a").is_ok());
+        assert_eq!(
+            tickets.admit(Some(&ticket), "a").unwrap_err(),
+            "a has not been checked before applying; apply it again"
+        );
+        assert!(tickets.admit(Some(&ticket), "b").is_ok());
+    }
+
+    #[test]
+    fn refuses_ids_outside_the_batch() {
+        let tickets = Tickets::default();
+        let ticket = tickets.issue(ids(&["a"]));
+
+        assert!(tickets.admit(Some(&ticket), "b").is_err());
+        assert!(tickets.admit(None, "a").is_err());
+        assert!(tickets.admit(Some("made-up"), "a").is_err());
+        assert!(tickets.admit(Some(&ticket), "a").is_ok());
+    }
+
+    #[test]
+    fn batches_expire() {
+        let tickets = Tickets::default();
+        let opened = Instant::now();
+        let ticket = tickets.issue_at(ids(&["a"]), opened);
+
+        assert_eq!(
+            tickets.admit_at(Some(&ticket), "a", opened + BATCH_TTL).unwrap_err(),
+            "The batch has expired; apply it again"
+        );
+    }
+
+    #[test]
+    fn finished_batches_are_closed() {
+        let tickets = Tickets::default();
+        let ticket = tickets.issue(ids(&["a"]));
+        tickets.admit(Some(&ticket), "a").unwrap();
+
+        assert!(tickets.0.lock().unwrap().is_empty());
+    }
+}
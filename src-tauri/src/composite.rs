@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+/// One step of a composite catalog item. Steps run in order; a failing step
+/// stops the item unless it is marked `continueOnError`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Step {
+    pub id: String,
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub rollback_command: Option<String>,
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StepResult {
+    pub id: String,
+    pub name: String,
+    pub success: bool,
+    pub skipped: bool,
+    pub output: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompositeResult {
+    pub success: bool,
+    pub steps: Vec<StepResult>,
+}
+
+/// The commands to run for `steps`, in execution order. Rolling back walks the
+/// steps in reverse and only includes those that declare a rollback.
+pub fn plan(steps: &[Step], is_rollback: bool) -> Vec<(&Step, &str)> {
+    if is_rollback {
+        steps
+            .iter()
+            .rev()
+            .filter_map(|step| step.rollback_command.as_deref().map(|command| (step, command)))
+            .collect()
+    } else {
+        steps.iter().map(|step| (step, step.command.as_str())).collect()
+    }
+}
+
+/// Whether the remaining steps are skipped after `step` ran. Rollback is best
+/// effort and never stops: undo as much as possible.
+pub fn stops_after(step: &Step, succeeded: bool, is_rollback: bool) -> bool {
+    !succeeded && !step.continue_on_error && !is_rollback
+}
+
+pub fn skipped(step: &Step) -> StepResult {
+    StepResult {
+        id: step.id.clone(),
+        name: step.name.clone(),
+        success: false,
+        skipped: true,
+        output: String::new(),
+        error: None,
+    }
+}
+
+/// Overall outcome: every step that ran succeeded or was allowed to fail, and
+/// nothing was skipped because of an earlier failure.
+pub fn summarize(steps: &[Step], results: Vec<StepResult>) -> CompositeResult {
+    let success = results.iter().all(|result| {
+        result.success
+            || (!result.skipped
+                && steps
+                    .iter()
+                    .any(|step| step.id == result.id && step.continue_on_error))
+    });
+
+    CompositeResult { success, steps: results }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &str, rollback: bool, continue_on_error: bool) -> Step {
+        Step {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            command: format!("apply-{}", id),
+            rollback_command: rollback.then(|| format!("undo-{}", id)),
+            continue_on_error,
+        }
+    }
+
+    /// Runs the plan like execute_composite does, failing the commands in `failing`.
+    fn run(steps: &[Step], is_rollback: bool, failing: &[&str]) -> (Vec<String>, CompositeResult) {
+        let mut executed = Vec::new();
+        let mut results = Vec::new();
+        let mut stopped = false;
+
+        for (step, command) in plan(steps, is_rollback) {
+            if stopped {
+                results.push(skipped(step));
+                continue;
+            }
+
+            executed.push(command.to_string());
+            let succeeded = !failing.contains(&command);
+            stopped = stops_after(step, succeeded, is_rollback);
+            results.push(StepResult {
+                id: step.id.clone(),
+                name: step.name.clone(),
+                success: succeeded,
+                skipped: false,
+                output: String::new(),
+                error: (!succeeded).then(|| "failed".to_string()),
+            });
+        }
+
+        (executed, summarize(steps, results))
+    }
+
+    fn steps() -> Vec<Step> {
+        vec![
+            step("stop", true, false),
+            step("uninstall", true, false),
+            step("cleanup", false, true),
+            step("policy", true, false),
+        ]
+    }
+
+    #[test]
+    fn runs_every_step_in_order() {
+        let (executed, result) = run(&steps(), false, &[]);
+
+        assert_eq!(executed, ["apply-stop", "apply-uninstall", "apply-cleanup", "apply-policy"]);
+        assert!(result.success);
+        assert!(result.steps.iter().all(|step| step.success && !step.skipped));
+    }
+
+    #[test]
+    fn skips_the_rest_after_a_failure() {
+        let (executed, result) = run(&steps(), false, &["apply-uninstall"]);
+
+        assert_eq!(executed, ["apply-stop", "apply-uninstall"]);
+        assert!(!result.success);
+        let skipped: Vec<&str> = result
+            .steps
+            .iter()
+            .filter(|step| step.skipped)
+            .map(|step| step.id.as_str())
+            .collect();
+        assert_eq!(skipped, ["cleanup", "policy"]);
+    }
+
+    #[test]
+    fn continue_on_error_steps_do_not_stop_or_fail_the_item() {
+        let (executed, result) = run(&steps(), false, &["apply-cleanup"]);
+
+        assert_eq!(executed, ["apply-stop", "apply-uninstall", "apply-cleanup", "apply-policy"]);
+        assert!(result.success);
+        assert!(!result.steps[2].success);
+    }
+
+    #[test]
+    fn rolls_back_in_reverse_skipping_steps_without_rollback() {
+        let (executed, result) = run(&steps(), true, &[]);
+
+        assert_eq!(executed, ["undo-policy", "undo-uninstall", "undo-stop"]);
+        assert!(result.success);
+    }
+
+    #[test]
+    fn rollback_continues_past_failures() {
+        let (executed, result) = run(&steps(), true, &["undo-uninstall"]);
+
+        assert_eq!(executed, ["undo-policy", "undo-uninstall", "undo-stop"]);
+        assert!(!result.success);
+        assert!(result.steps.iter().all(|step| !step.skipped));
+    }
+}
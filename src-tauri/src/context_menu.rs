@@ -0,0 +1,518 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::powershell;
+use crate::status::ItemState;
+
+const USER_BLOCKED_KEY: &str = "HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Shell Extensions\\Blocked";
+const MACHINE_BLOCKED_KEY: &str = "HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Shell Extensions\\Blocked";
+
+/// Registering an empty InprocServer32 for this CLSID makes Windows 11 fall
+/// back to the classic (full) context menu.
+const CLASSIC_MENU_KEY: &str = "HKCU:\\Software\\Classes\\CLSID\\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}";
+
+/// Reads the handler registrations, both Blocked lists and the classic menu
+/// switch in the shape [`parse_state`] expects.
+pub const SHELL_STATE_SCRIPT: &str = "$locations = @('*', 'AllFilesystemObjects', 'Directory', 'Directory\\Background', 'Folder', 'Drive'); \
+$handlers = @(foreach ($location in $locations) { \
+    Get-ChildItem -LiteralPath \"Registry::HKEY_CLASSES_ROOT\\$location\\shellex\\ContextMenuHandlers\" -ErrorAction SilentlyContinue | ForEach-Object { \
+        $default = $_.GetValue(''); \
+        $clsid = if ($default -match '^\\{[0-9A-Fa-f-]{36}\\}$') { $default } elseif ($_.PSChildName -match '^\\{[0-9A-Fa-f-]{36}\\}$') { $_.PSChildName } else { $null }; \
+        if ($clsid) { \
+            $class = Get-Item -LiteralPath \"Registry::HKEY_CLASSES_ROOT\\CLSID\\$clsid\" -ErrorAction SilentlyContinue; \
+            [pscustomobject]@{ Location = $location; Name = $_.PSChildName; Clsid = $clsid; DisplayName = if ($class) { [string]$class.GetValue('') } else { $null } } \
+        } \
+    } \
+}); \
+$blocked = @(foreach ($hive in @(@{ Name = 'user'; Path = 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Shell Extensions\\Blocked' }, @{ Name = 'machine'; Path = 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Shell Extensions\\Blocked' })) { \
+    $key = Get-Item -LiteralPath $hive.Path -ErrorAction SilentlyContinue; \
+    if ($key) { foreach ($name in $key.GetValueNames()) { if ($name) { [pscustomobject]@{ Scope = $hive.Name; Clsid = $name; Data = [string]$key.GetValue($name) } } } } \
+}); \
+[pscustomobject]@{ \
+    Handlers = $handlers; \
+    Blocked = $blocked; \
+    ClassicMenu = Test-Path -LiteralPath 'HKCU:\\Software\\Classes\\CLSID\\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}\\InprocServer32' \
+} | ConvertTo-Json -Depth 4 -Compress";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockScope {
+    User,
+    Machine,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct HandlerRegistration {
+    pub location: String,
+    pub name: String,
+    pub clsid: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BlockedEntry {
+    pub scope: BlockScope,
+    pub clsid: String,
+    #[serde(default)]
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShellState {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub handlers: Vec<HandlerRegistration>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub blocked: Vec<BlockedEntry>,
+    #[serde(default)]
+    pub classic_menu: bool,
+}
+
+/// Accepts `null` or a bare object where a list is expected, as
+/// `ConvertTo-Json` produces for empty and single-element collections.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+        None(()),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(items) => items,
+        OneOrMany::One(item) => vec![item],
+        OneOrMany::None(()) => Vec::new(),
+    })
+}
+
+pub fn parse_state(json: &str) -> Result<ShellState, String> {
+    serde_json::from_str(json.trim()).map_err(|e| format!("Failed to parse shell extension state: {}", e))
+}
+
+pub fn is_clsid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 38
+        && bytes[0] == b'{'
+        && bytes[37] == b'}'
+        && value[1..37].char_indices().all(|(i, c)| {
+            if matches!(i, 8 | 13 | 18 | 23) {
+                c == '-'
+            } else {
+                c.is_ascii_hexdigit()
+            }
+        })
+}
+
+impl ShellState {
+    pub fn blocked_in(&self, clsid: &str, scope: BlockScope) -> Option<&BlockedEntry> {
+        self.blocked
+            .iter()
+            .find(|entry| entry.scope == scope && entry.clsid.eq_ignore_ascii_case(clsid))
+    }
+
+    pub fn is_blocked(&self, clsid: &str) -> bool {
+        self.blocked.iter().any(|entry| entry.clsid.eq_ignore_ascii_case(clsid))
+    }
+}
+
+/// A shell extension and where it is registered as a context menu handler.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShellExtension {
+    pub clsid: String,
+    pub name: String,
+    /// HKCR classes it is registered under, such as `*` or `Directory`.
+    pub locations: Vec<String>,
+    pub blocked: bool,
+    /// Blocked for every user in HKLM; unblocking needs elevation.
+    pub blocked_for_all_users: bool,
+}
+
+/// Groups handler registrations by CLSID. Blocked CLSIDs with no registration
+/// left are listed too so they can still be unblocked.
+pub fn extensions(state: &ShellState) -> Vec<ShellExtension> {
+    let mut by_clsid: BTreeMap<String, ShellExtension> = BTreeMap::new();
+
+    for handler in &state.handlers {
+        let extension = by_clsid
+            .entry(handler.clsid.to_uppercase())
+            .or_insert_with(|| ShellExtension {
+                clsid: handler.clsid.clone(),
+                name: handler
+                    .display_name
+                    .clone()
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| handler.name.clone()),
+                locations: Vec::new(),
+                blocked: state.is_blocked(&handler.clsid),
+                blocked_for_all_users: state.blocked_in(&handler.clsid, BlockScope::Machine).is_some(),
+            });
+        if !extension.locations.contains(&handler.location) {
+            extension.locations.push(handler.location.clone());
+        }
+    }
+
+    for entry in state.blocked.iter().filter(|entry| is_clsid(&entry.clsid)) {
+        by_clsid
+            .entry(entry.clsid.to_uppercase())
+            .or_insert_with(|| ShellExtension {
+                clsid: entry.clsid.clone(),
+                name: if entry.data.trim().is_empty() { entry.clsid.clone() } else { entry.data.clone() },
+                locations: Vec::new(),
+                blocked: true,
+                blocked_for_all_users: state.blocked_in(&entry.clsid, BlockScope::Machine).is_some(),
+            });
+    }
+
+    let mut extensions: Vec<ShellExtension> = by_clsid.into_values().collect();
+    extensions.sort_by_key(|extension| extension.name.to_lowercase());
+    extensions
+}
+
+fn ensure_key(key: &str) -> String {
+    let key = powershell::quote(key);
+    format!("if (-not (Test-Path -LiteralPath {key})) {{ New-Item -Path {key} -Force | Out-Null }}")
+}
+
+/// Adds `clsid` to the current user's Blocked list with `data` as its note.
+pub fn block_script(clsid: &str, data: &str) -> String {
+    format!(
+        "{}; New-ItemProperty -LiteralPath {} -Name {} -PropertyType String -Value {} -Force | Out-Null",
+        ensure_key(USER_BLOCKED_KEY),
+        powershell::quote(USER_BLOCKED_KEY),
+        powershell::quote(clsid),
+        powershell::quote(data)
+    )
+}
+
+/// Removes `clsid` from the Blocked list of `scope`.
+pub fn unblock_script(clsid: &str, scope: BlockScope) -> String {
+    let key = match scope {
+        BlockScope::User => USER_BLOCKED_KEY,
+        BlockScope::Machine => MACHINE_BLOCKED_KEY,
+    };
+    format!(
+        "Remove-ItemProperty -LiteralPath {} -Name {} -ErrorAction SilentlyContinue",
+        powershell::quote(key),
+        powershell::quote(&powershell::escape_wildcards(clsid))
+    )
+}
+
+pub fn classic_menu_script(enabled: bool) -> String {
+    if enabled {
+        let server = format!("{}\\InprocServer32", CLASSIC_MENU_KEY);
+        format!(
+            "{}; Set-ItemProperty -LiteralPath {} -Name '(default)' -Value ''",
+            ensure_key(&server),
+            powershell::quote(&server)
+        )
+    } else {
+        format!(
+            "Remove-Item -LiteralPath {} -Recurse -Force -ErrorAction SilentlyContinue",
+            powershell::quote(CLASSIC_MENU_KEY)
+        )
+    }
+}
+
+/// A handler the catalog blocks, with the note written into the Blocked list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedHandler {
+    pub clsid: String,
+    pub name: String,
+}
+
+/// What a context menu catalog item changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ContextMenuChange {
+    BlockHandlers { handlers: Vec<BlockedHandler> },
+    ClassicMenu,
+}
+
+impl ContextMenuChange {
+    pub fn validate(&self) -> Result<(), String> {
+        if let ContextMenuChange::BlockHandlers { handlers } = self {
+            if let Some(handler) = handlers.iter().find(|handler| !is_clsid(&handler.clsid)) {
+                return Err(format!("Invalid CLSID for {}: {}", handler.name, handler.clsid));
+            }
+        }
+        Ok(())
+    }
+
+    /// The script shown for the item. Applying also records the previous
+    /// state first, see [`record`].
+    pub fn apply_script(&self) -> String {
+        match self {
+            ContextMenuChange::BlockHandlers { handlers } => handlers
+                .iter()
+                .map(|handler| block_script(&handler.clsid, &handler.name))
+                .collect::<Vec<_>>()
+                .join("; "),
+            ContextMenuChange::ClassicMenu => classic_menu_script(true),
+        }
+    }
+
+    pub fn state(&self, state: &ShellState) -> ItemState {
+        match self {
+            ContextMenuChange::BlockHandlers { handlers } => {
+                let blocked = handlers.iter().filter(|handler| state.is_blocked(&handler.clsid)).count();
+                ItemState::from_counts(blocked, handlers.len())
+            }
+            ContextMenuChange::ClassicMenu if state.classic_menu => ItemState::Applied,
+            ContextMenuChange::ClassicMenu => ItemState::NotApplied,
+        }
+    }
+}
+
+/// The part of the shell state a context menu item changed, as it was before.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ContextMenuRecord {
+    /// Previous data of each CLSID in the user's Blocked list, `None` when it
+    /// was not listed.
+    BlockHandlers { previous: BTreeMap<String, Option<String>> },
+    ClassicMenu { was_enabled: bool },
+}
+
+/// Captures what applying `change` is about to overwrite. An existing record is kept
+/// so applying twice never records the item's own changes as the original.
+pub fn record(change: &ContextMenuChange, state: &ShellState, existing: Option<ContextMenuRecord>) -> ContextMenuRecord {
+    match (change, existing) {
+        (ContextMenuChange::BlockHandlers { handlers }, Some(ContextMenuRecord::BlockHandlers { mut previous })) => {
+            for handler in handlers {
+                previous
+                    .entry(handler.clsid.clone())
+                    .or_insert_with(|| state.blocked_in(&handler.clsid, BlockScope::User).map(|entry| entry.data.clone()));
+            }
+            ContextMenuRecord::BlockHandlers { previous }
+        }
+        (ContextMenuChange::BlockHandlers { handlers }, _) => ContextMenuRecord::BlockHandlers {
+            previous: handlers
+                .iter()
+                .map(|handler| {
+                    (
+                        handler.clsid.clone(),
+                        state.blocked_in(&handler.clsid, BlockScope::User).map(|entry| entry.data.clone()),
+                    )
+                })
+                .collect(),
+        },
+        (ContextMenuChange::ClassicMenu, Some(record @ ContextMenuRecord::ClassicMenu { .. })) => record,
+        (ContextMenuChange::ClassicMenu, _) => ContextMenuRecord::ClassicMenu { was_enabled: state.classic_menu },
+    }
+}
+
+/// Restores exactly what a record captured.
+pub fn restore_script(record: &ContextMenuRecord) -> String {
+    match record {
+        ContextMenuRecord::BlockHandlers { previous } => previous
+            .iter()
+            .map(|(clsid, data)| match data {
+                Some(data) => block_script(clsid, data),
+                None => unblock_script(clsid, BlockScope::User),
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        ContextMenuRecord::ClassicMenu { was_enabled } => classic_menu_script(*was_enabled),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARING: &str = "{F81E9010-6EA4-11CE-A7FF-00AA003CA9F6}";
+    const DEFENDER: &str = "{09A47860-11B0-4DA5-AFA5-26D86198A780}";
+
+    fn state() -> ShellState {
+        parse_state(
+            r#"{
+                "Handlers": [
+                    {"Location":"*","Name":"EPP","Clsid":"{09A47860-11B0-4DA5-AFA5-26D86198A780}","DisplayName":"Microsoft Defender"},
+                    {"Location":"Directory","Name":"EPP","Clsid":"{09A47860-11B0-4DA5-AFA5-26D86198A780}","DisplayName":"Microsoft Defender"},
+                    {"Location":"Directory\\Background","Name":"Sharing","Clsid":"{f81e9010-6ea4-11ce-a7ff-00aa003ca9f6}","DisplayName":""}
+                ],
+                "Blocked": {"Scope":"machine","Clsid":"{F81E9010-6EA4-11CE-A7FF-00AA003CA9F6}","Data":"Sharing"},
+                "ClassicMenu": false
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn block(clsids: &[&str]) -> ContextMenuChange {
+        ContextMenuChange::BlockHandlers {
+            handlers: clsids
+                .iter()
+                .map(|clsid| BlockedHandler {
+                    clsid: clsid.to_string(),
+                    name: "Catalog".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_single_and_missing_collections() {
+        let state = state();
+        assert_eq!(state.handlers.len(), 3);
+        assert_eq!(state.blocked.len(), 1);
+        assert_eq!(state.blocked[0].scope, BlockScope::Machine);
+
+        let empty = parse_state(r#"{"Handlers":null,"Blocked":null,"ClassicMenu":true}"#).unwrap();
+        assert!(empty.handlers.is_empty());
+        assert!(empty.blocked.is_empty());
+        assert!(empty.classic_menu);
+
+        assert!(parse_state("").is_err());
+    }
+
+    #[test]
+    fn recognizes_clsids() {
+        assert!(is_clsid(SHARING));
+        assert!(is_clsid("{f81e9010-6ea4-11ce-a7ff-00aa003ca9f6}"));
+        assert!(!is_clsid("F81E9010-6EA4-11CE-A7FF-00AA003CA9F6"));
+        assert!(!is_clsid("{F81E9010-6EA4-11CE-A7FF-00AA003CA9FG}"));
+        assert!(!is_clsid("{F81E90106-EA4-11CE-A7FF-00AA003CA9F6}"));
+    }
+
+    #[test]
+    fn groups_registrations_by_clsid() {
+        let extensions = extensions(&state());
+
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions[0].name, "Microsoft Defender");
+        assert_eq!(extensions[0].locations, ["*", "Directory"]);
+        assert!(!extensions[0].blocked);
+        // No display name: falls back to the handler key name.
+        assert_eq!(extensions[1].name, "Sharing");
+        assert_eq!(extensions[1].locations, ["Directory\\Background"]);
+        assert!(extensions[1].blocked);
+        assert!(extensions[1].blocked_for_all_users);
+    }
+
+    #[test]
+    fn lists_blocked_clsids_without_registrations() {
+        let state = parse_state(
+            r#"{"Handlers":[],"Blocked":[
+                {"Scope":"user","Clsid":"{09A47860-11B0-4DA5-AFA5-26D86198A780}","Data":""},
+                {"Scope":"user","Clsid":"not a clsid","Data":"ignored"}
+            ],"ClassicMenu":false}"#,
+        )
+        .unwrap();
+
+        let extensions = extensions(&state);
+
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(extensions[0].name, DEFENDER);
+        assert!(extensions[0].locations.is_empty());
+        assert!(extensions[0].blocked);
+        assert!(!extensions[0].blocked_for_all_users);
+    }
+
+    #[test]
+    fn builds_block_and_classic_menu_scripts() {
+        assert_eq!(
+            block_script(SHARING, "Give access to"),
+            r#"if (-not (Test-Path -LiteralPath 'HKCU:\SOFTWARE\Microsoft\Windows\CurrentVersion\Shell Extensions\Blocked')) { New-Item -Path 'HKCU:\SOFTWARE\Microsoft\Windows\CurrentVersion\Shell Extensions\Blocked' -Force | Out-Null }; New-ItemProperty -LiteralPath 'HKCU:\SOFTWARE\Microsoft\Windows\CurrentVersion\Shell Extensions\Blocked' -Name '{F81E9010-6EA4-11CE-A7FF-00AA003CA9F6}' -PropertyType String -Value 'Give access to' -Force | Out-Null"#
+        );
+        assert_eq!(
+            unblock_script(SHARING, BlockScope::Machine),
+            r#"Remove-ItemProperty -LiteralPath 'HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\Shell Extensions\Blocked' -Name '{F81E9010-6EA4-11CE-A7FF-00AA003CA9F6}' -ErrorAction SilentlyContinue"#
+        );
+        assert_eq!(
+            classic_menu_script(true),
+            r#"if (-not (Test-Path -LiteralPath 'HKCU:\Software\Classes\CLSID\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}\InprocServer32')) { New-Item -Path 'HKCU:\Software\Classes\CLSID\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}\InprocServer32' -Force | Out-Null }; Set-ItemProperty -LiteralPath 'HKCU:\Software\Classes\CLSID\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}\InprocServer32' -Name '(default)' -Value ''"#
+        );
+        assert_eq!(
+            classic_menu_script(false),
+            r#"Remove-Item -LiteralPath 'HKCU:\Software\Classes\CLSID\{86ca1aa0-34aa-4e8b-a509-50c905bae2a2}' -Recurse -Force -ErrorAction SilentlyContinue"#
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_clsids() {
+        assert!(block(&[SHARING, DEFENDER]).validate().is_ok());
+        assert_eq!(
+            block(&[SHARING, "{nope}"]).validate(),
+            Err("Invalid CLSID for Catalog: {nope}".to_string())
+        );
+        assert!(ContextMenuChange::ClassicMenu.validate().is_ok());
+    }
+
+    #[test]
+    fn state_counts_blocked_handlers_in_any_scope() {
+        let state = state();
+
+        assert_eq!(block(&[SHARING]).state(&state), ItemState::Applied);
+        assert_eq!(block(&[SHARING, DEFENDER]).state(&state), ItemState::Partial);
+        assert_eq!(block(&[DEFENDER]).state(&state), ItemState::NotApplied);
+        assert_eq!(ContextMenuChange::ClassicMenu.state(&state), ItemState::NotApplied);
+    }
+
+    #[test]
+    fn records_only_the_user_blocked_list() {
+        let mut state = state();
+        state.blocked.push(BlockedEntry {
+            scope: BlockScope::User,
+            clsid: DEFENDER.to_lowercase(),
+            data: "Scan with Defender".to_string(),
+        });
+
+        let record = record(&block(&[SHARING, DEFENDER]), &state, None);
+
+        assert_eq!(
+            record,
+            ContextMenuRecord::BlockHandlers {
+                previous: BTreeMap::from([
+                    (DEFENDER.to_string(), Some("Scan with Defender".to_string())),
+                    (SHARING.to_string(), None),
+                ]),
+            }
+        );
+        assert_eq!(
+            restore_script(&record),
+            format!(
+                "{}; {}",
+                block_script(DEFENDER, "Scan with Defender"),
+                unblock_script(SHARING, BlockScope::User)
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_record_when_applied_again() {
+        let existing = ContextMenuRecord::BlockHandlers {
+            previous: BTreeMap::from([(SHARING.to_string(), None)]),
+        };
+        let mut blocked = state();
+        blocked.blocked.push(BlockedEntry {
+            scope: BlockScope::User,
+            clsid: SHARING.to_string(),
+            data: "Catalog".to_string(),
+        });
+
+        let record = record(&block(&[SHARING, DEFENDER]), &blocked, Some(existing));
+
+        assert_eq!(
+            record,
+            ContextMenuRecord::BlockHandlers {
+                previous: BTreeMap::from([(DEFENDER.to_string(), None), (SHARING.to_string(), None)]),
+            }
+        );
+
+        let classic = super::record(
+            &ContextMenuChange::ClassicMenu,
+            &ShellState { classic_menu: true, ..state() },
+            Some(ContextMenuRecord::ClassicMenu { was_enabled: false }),
+        );
+        assert_eq!(classic, ContextMenuRecord::ClassicMenu { was_enabled: false });
+        assert_eq!(restore_script(&classic), classic_menu_script(false));
+    }
+}
@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::PackageInfo;
+use crate::risk::Risk;
+
+/// Package name prefixes (publisher ids or vendor names) of OEM and trialware
+/// vendors that ship Store apps on new machines.
+const OEM_PREFIXES: &[(&str, &str)] = &[
+    ("AD2F1837.", "HP"),
+    ("HPInc.", "HP"),
+    ("DellInc.", "Dell"),
+    ("E046963F.", "Lenovo"),
+    ("LenovoCorporation.", "Lenovo"),
+    ("B9ECED6F.", "ASUS"),
+    ("AcerIncorporated.", "Acer"),
+    ("SAMSUNGELECTRONICSCO.LTD.", "Samsung"),
+    ("SAMSUNGELECTRONICSCoLtd.", "Samsung"),
+    ("MSICO.LTD.", "MSI"),
+    ("5A894077.", "McAfee"),
+    ("McAfee", "McAfee"),
+    ("NortonLifeLock", "Norton"),
+];
+
+/// Package name prefixes of third-party vendors whose apps are not made by
+/// the OEM but come bundled with new machines as trials or promotions.
+const TRIALWARE_PREFIXES: &[(&str, &str)] = &[
+    ("ExpressVPN", "ExpressVPN"),
+    ("Dropbox", "Dropbox"),
+];
+
+/// Publisher subject words of the OEM vendors, for packages whose name does
+/// not carry a recognisable prefix. Matched as whole words, so "dell" does not
+/// flag "Odell Software".
+const OEM_PUBLISHERS: &[(&str, &str)] = &[
+    ("hp inc", "HP"),
+    ("hewlett-packard", "HP"),
+    ("dell", "Dell"),
+    ("lenovo", "Lenovo"),
+    ("asustek", "ASUS"),
+    ("acer", "Acer"),
+    ("samsung", "Samsung"),
+    ("micro-star", "MSI"),
+    ("mcafee", "McAfee"),
+    ("nortonlifelock", "Norton"),
+];
+
+const TRIAL_MARKERS: &[&str] = &["trial", "offer", "promo", "jumpstart", "welcome", "registration"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Likely,
+    Possible,
+    Unlikely,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Classification {
+    pub verdict: Verdict,
+    pub vendor: Option<String>,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredPackage {
+    pub package: PackageInfo,
+    pub classification: Classification,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn prefixed_vendor(package: &PackageInfo, prefixes: &[(&str, &'static str)]) -> Option<&'static str> {
+    let name = package.name.to_lowercase();
+    prefixes
+        .iter()
+        .find(|(prefix, _)| name.starts_with(&prefix.to_lowercase()))
+        .map(|(_, vendor)| *vendor)
+}
+
+fn oem_vendor(package: &PackageInfo) -> Option<&'static str> {
+    let publisher = words(&package.publisher);
+
+    prefixed_vendor(package, OEM_PREFIXES).or_else(|| {
+        OEM_PUBLISHERS
+            .iter()
+            .find(|(fragment, _)| {
+                let fragment = words(fragment);
+                publisher.windows(fragment.len()).any(|window| window == fragment.as_slice())
+            })
+            .map(|(_, vendor)| *vendor)
+    })
+}
+
+fn is_microsoft(package: &PackageInfo) -> bool {
+    package.publisher.to_lowercase().contains("o=microsoft corporation")
+}
+
+/// Guesses whether an uncatalogued package is bloatware from its publisher,
+/// signature and name. Never removes anything; the verdict only orders the
+/// discovery list and seeds the risk level of promoted items.
+pub fn classify(package: &PackageInfo) -> Classification {
+    let mut reasons = Vec::new();
+
+    if package.non_removable || package.signature_kind.eq_ignore_ascii_case("system") {
+        reasons.push("Part of Windows and cannot be removed".to_string());
+        return Classification { verdict: Verdict::Unlikely, vendor: None, reasons };
+    }
+    if package.is_framework {
+        reasons.push("Framework package used by other apps".to_string());
+        return Classification { verdict: Verdict::Unlikely, vendor: None, reasons };
+    }
+    if !package.dependents.is_empty() {
+        reasons.push(format!("{} installed package(s) depend on it", package.dependents.len()));
+    }
+
+    let oem = oem_vendor(package);
+    let trialware = prefixed_vendor(package, TRIALWARE_PREFIXES);
+    let microsoft = is_microsoft(package);
+    let name = package.name.to_lowercase();
+    let mut score = 0;
+
+    if let Some(vendor) = oem {
+        reasons.push(format!("Preinstalled {} software", vendor));
+        score += 2;
+    } else if let Some(vendor) = trialware {
+        reasons.push(format!("{} software often bundled as a trial or promotion", vendor));
+        score += 2;
+    } else if !microsoft {
+        reasons.push("Not published by Microsoft".to_string());
+        score += 1;
+    }
+    if TRIAL_MARKERS.iter().any(|marker| name.contains(marker)) {
+        reasons.push("Name suggests trial or promotional content".to_string());
+        score += 1;
+    }
+    if package.signature_kind.eq_ignore_ascii_case("developer") {
+        reasons.push("Sideloaded with a developer signature".to_string());
+    }
+    if microsoft {
+        reasons.push("Published by Microsoft".to_string());
+        score -= 1;
+    }
+
+    let verdict = if !package.dependents.is_empty() {
+        Verdict::Unlikely
+    } else if score >= 2 {
+        Verdict::Likely
+    } else if score >= 1 {
+        Verdict::Possible
+    } else {
+        Verdict::Unlikely
+    };
+
+    Classification {
+        verdict,
+        vendor: oem.or(trialware).map(str::to_string),
+        reasons,
+    }
+}
+
+/// Classifies the packages for which `is_catalogued` is false, most likely
+/// bloatware first and largest first within a verdict.
+pub fn discover<F>(inventory: Vec<PackageInfo>, is_catalogued: F) -> Vec<DiscoveredPackage>
+where
+    F: Fn(&PackageInfo) -> bool,
+{
+    let mut discovered: Vec<DiscoveredPackage> = inventory
+        .into_iter()
+        .filter(|package| !is_catalogued(package))
+        .map(|package| DiscoveredPackage {
+            classification: classify(&package),
+            package,
+        })
+        .collect();
+
+    discovered.sort_by(|a, b| {
+        a.classification
+            .verdict
+            .cmp(&b.classification.verdict)
+            .then_with(|| b.package.install_size.unwrap_or(0).cmp(&a.package.install_size.unwrap_or(0)))
+            .then_with(|| a.package.name.cmp(&b.package.name))
+    });
+
+    discovered
+}
+
+/// A discovered package the user promoted into the removable app list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomApp {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Apps promoted before risk levels were saved with a `safe` flag.
+    #[serde(alias = "safe", deserialize_with = "crate::risk::deserialize_legacy")]
+    pub risk: Risk,
+    pub package_pattern: String,
+}
+
+/// Id for a custom app promoted from `package_name`. Promoting the same
+/// package again keeps its id; package names that only differ in punctuation
+/// share a slug, so later ones get a numbered suffix instead of replacing the
+/// earlier app.
+pub fn custom_app_id(package_name: &str, existing: &[CustomApp]) -> String {
+    if let Some(app) = existing
+        .iter()
+        .find(|app| app.package_pattern.eq_ignore_ascii_case(package_name))
+    {
+        return app.id.clone();
+    }
+
+    let slug: String = package_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let base = format!("custom-{}", slug);
+    let taken = |id: &str| existing.iter().any(|app| app.id == id);
+
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while taken(&id) {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MICROSOFT: &str = "CN=Microsoft Corporation, O=Microsoft Corporation, L=Redmond, S=Washington, C=US";
+
+    fn package(name: &str, publisher: &str) -> PackageInfo {
+        PackageInfo {
+            name: name.to_string(),
+            family_name: format!("{}_abcdefghijklm", name),
+            full_name: format!("{}_1.0.0.0_x64__abcdefghijklm", name),
+            version: "1.0.0.0".to_string(),
+            publisher: publisher.to_string(),
+            architecture: "X64".to_string(),
+            install_location: None,
+            install_size: Some(1024),
+            is_framework: false,
+            non_removable: false,
+            signature_kind: "Store".to_string(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            removable: true,
+        }
+    }
+
+    #[test]
+    fn oem_packages_are_likely_bloatware() {
+        let by_prefix = classify(&package("AD2F1837.HPJumpStarts", "CN=ED346674-0FA1-4272-85CE-3187C9C86E26"));
+        assert_eq!(by_prefix.verdict, Verdict::Likely);
+        assert_eq!(by_prefix.vendor.as_deref(), Some("HP"));
+        assert!(by_prefix.reasons.contains(&"Name suggests trial or promotional content".to_string()));
+
+        let by_publisher = classify(&package("Vantage.Companion", "CN=Lenovo Group Ltd, O=Lenovo Group Ltd"));
+        assert_eq!(by_publisher.verdict, Verdict::Likely);
+        assert_eq!(by_publisher.vendor.as_deref(), Some("Lenovo"));
+    }
+
+    #[test]
+    fn third_party_trials_are_likely_bloatware() {
+        let classification = classify(&package("SomeGame.FreeTrial", "CN=Some Studio"));
+
+        assert_eq!(classification.verdict, Verdict::Likely);
+        assert_eq!(classification.vendor, None);
+    }
+
+    #[test]
+    fn unknown_third_party_apps_are_possible_bloatware() {
+        let mut sideloaded = package("Contoso.Notes", "CN=Contoso");
+        sideloaded.signature_kind = "Developer".to_string();
+
+        let classification = classify(&sideloaded);
+        assert_eq!(classification.verdict, Verdict::Possible);
+        assert_eq!(
+            classification.reasons,
+            ["Not published by Microsoft", "Sideloaded with a developer signature"]
+        );
+    }
+
+    #[test]
+    fn microsoft_apps_are_unlikely_bloatware() {
+        assert_eq!(classify(&package("Microsoft.Todos", MICROSOFT)).verdict, Verdict::Unlikely);
+        // A promotional name alone does not outweigh the Microsoft publisher.
+        assert_eq!(classify(&package("Microsoft.OfficeOffer", MICROSOFT)).verdict, Verdict::Unlikely);
+    }
+
+    #[test]
+    fn framework_and_system_packages_are_unlikely_bloatware() {
+        let mut framework = package("AD2F1837.HPRuntime", "CN=HP Inc.");
+        framework.is_framework = true;
+        let classification = classify(&framework);
+        assert_eq!(classification.verdict, Verdict::Unlikely);
+        assert_eq!(classification.reasons, ["Framework package used by other apps"]);
+
+        let mut non_removable = package("Contoso.Trial", "CN=Contoso");
+        non_removable.non_removable = true;
+        assert_eq!(classify(&non_removable).verdict, Verdict::Unlikely);
+
+        let mut system = package("Microsoft.Windows.Search", MICROSOFT);
+        system.signature_kind = "System".to_string();
+        assert_eq!(classify(&system).reasons, ["Part of Windows and cannot be removed"]);
+    }
+
+    #[test]
+    fn packages_with_dependents_are_unlikely_bloatware() {
+        let mut shared = package("DellInc.DellSupportAssist", "CN=Dell Inc");
+        shared.dependents = vec!["DellInc.DellDigitalDelivery_1.0_x64__htrsf667h5kn2".to_string()];
+
+        let classification = classify(&shared);
+        assert_eq!(classification.verdict, Verdict::Unlikely);
+        assert_eq!(classification.vendor.as_deref(), Some("Dell"));
+        assert_eq!(classification.reasons[0], "1 installed package(s) depend on it");
+    }
+
+    #[test]
+    fn discovers_uncatalogued_packages_most_likely_first() {
+        let mut large = package("Contoso.Notes", "CN=Contoso");
+        large.install_size = Some(4096);
+        let inventory = vec![
+            package("Microsoft.Todos", MICROSOFT),
+            large,
+            package("AD2F1837.HPJumpStarts", "CN=HP Inc."),
+            package("Microsoft.BingNews", MICROSOFT),
+        ];
+
+        let discovered = discover(inventory, |package| package.name == "Microsoft.BingNews");
+
+        let names: Vec<&str> = discovered.iter().map(|d| d.package.name.as_str()).collect();
+        assert_eq!(names, ["AD2F1837.HPJumpStarts", "Contoso.Notes", "Microsoft.Todos"]);
+    }
+
+    #[test]
+    fn bundled_third_party_apps_are_not_oem_software() {
+        let classification = classify(&package("ExpressVPN.ExpressVPN", "CN=ExpressVPN"));
+
+        assert_eq!(classification.verdict, Verdict::Likely);
+        assert_eq!(classification.vendor.as_deref(), Some("ExpressVPN"));
+        assert_eq!(classification.reasons, ["ExpressVPN software often bundled as a trial or promotion"]);
+    }
+
+    #[test]
+    fn publishers_match_whole_words() {
+        assert_eq!(oem_vendor(&package("Contoso.Support", "CN=Dell Inc, O=Dell Inc")), Some("Dell"));
+        assert_eq!(oem_vendor(&package("Contoso.Support", "CN=HP Inc., O=HP Inc.")), Some("HP"));
+        assert_eq!(oem_vendor(&package("Contoso.Notes", "CN=Odell Software")), None);
+        assert_eq!(oem_vendor(&package("Contoso.Notes", "CN=Chp Incubator")), None);
+    }
+
+    fn custom(id: &str, package_pattern: &str) -> CustomApp {
+        CustomApp {
+            id: id.to_string(),
+            name: package_pattern.to_string(),
+            description: String::new(),
+            risk: Risk::default(),
+            package_pattern: package_pattern.to_string(),
+        }
+    }
+
+    #[test]
+    fn custom_app_ids_are_slugs() {
+        assert_eq!(custom_app_id("AD2F1837.HPJumpStarts", &[]), "custom-ad2f1837-hpjumpstarts");
+    }
+
+    #[test]
+    fn colliding_custom_app_ids_get_a_suffix() {
+        let existing = [
+            custom("custom-contoso-notes", "Contoso.Notes"),
+            custom("custom-contoso-notes-2", "Contoso-Notes"),
+        ];
+
+        assert_eq!(custom_app_id("contoso.notes", &existing), "custom-contoso-notes");
+        assert_eq!(custom_app_id("Contoso-Notes", &existing), "custom-contoso-notes-2");
+        assert_eq!(custom_app_id("Contoso_Notes", &existing), "custom-contoso-notes-3");
+    }
+}
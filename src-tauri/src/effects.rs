@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What it takes for an applied item to take effect. Ordered weakest first,
+/// so the requirement of a batch is the maximum of its items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    #[default]
+    Immediate,
+    ExplorerRestart,
+    Signout,
+    Reboot,
+}
+
+/// When the parts of the session that settle an effect last started, in
+/// Unix milliseconds, in the shape [`SESSION_TIMES_SCRIPT`] prints. `None`
+/// when unknown, which settles nothing.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SessionTimes {
+    #[serde(default)]
+    pub boot: Option<u64>,
+    /// Start of the Shell Infrastructure Host, which runs once per sign-in
+    /// and, unlike Explorer, is not restarted with the shell.
+    #[serde(default)]
+    pub logon: Option<u64>,
+    #[serde(default)]
+    pub explorer: Option<u64>,
+}
+
+/// Reads [`SessionTimes`] for the session the app runs in. A shutdown with
+/// Fast Startup enabled resumes the kernel and leaves `LastBootUpTime` alone,
+/// which is right: only a full restart settles a reboot.
+pub const SESSION_TIMES_SCRIPT: &str = "$session = (Get-Process -Id $PID).SessionId; \
+function Started($name) { \
+    $p = Get-Process -Name $name -ErrorAction SilentlyContinue | Where-Object { $_.SessionId -eq $session -and $_.StartTime } | Sort-Object StartTime | Select-Object -First 1; \
+    if ($p) { ([DateTimeOffset]$p.StartTime).ToUnixTimeMilliseconds() } else { $null } \
+}; \
+[pscustomobject]@{ \
+    Boot = ([DateTimeOffset](Get-CimInstance Win32_OperatingSystem).LastBootUpTime).ToUnixTimeMilliseconds(); \
+    Logon = Started 'sihost'; \
+    Explorer = Started 'explorer' \
+} | ConvertTo-Json -Compress";
+
+/// Restarts the shell. Windows starts Explorer again on its own when it is
+/// stopped, unless `AutoRestartShell` is off.
+pub const RESTART_EXPLORER_SCRIPT: &str = "Stop-Process -Name explorer -Force -ErrorAction Stop; \
+Start-Sleep -Seconds 2; \
+if (-not (Get-Process -Name explorer -ErrorAction SilentlyContinue)) { Start-Process explorer.exe }";
+
+pub fn parse_session_times(json: &str) -> Result<SessionTimes, String> {
+    serde_json::from_str(json.trim()).map_err(|e| format!("Failed to parse session times: {}", e))
+}
+
+/// An applied item still waiting for its effect, keyed by item id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRecord {
+    pub name: String,
+    pub effect: Effect,
+    /// Unix milliseconds.
+    pub applied_at: u64,
+}
+
+impl PendingRecord {
+    fn is_settled(&self, times: &SessionTimes) -> bool {
+        let since = |time: Option<u64>| time.is_some_and(|time| time > self.applied_at);
+        match self.effect {
+            Effect::Immediate => true,
+            Effect::ExplorerRestart => since(times.boot) || since(times.logon) || since(times.explorer),
+            Effect::Signout => since(times.boot) || since(times.logon),
+            Effect::Reboot => since(times.boot),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingItem {
+    pub id: String,
+    pub name: String,
+    pub effect: Effect,
+}
+
+/// Items waiting for an effect and the strongest one among them, which is
+/// `Immediate` when nothing is waiting.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingEffects {
+    pub effect: Effect,
+    pub items: Vec<PendingItem>,
+}
+
+impl PendingEffects {
+    pub fn new(items: Vec<PendingItem>) -> Self {
+        PendingEffects {
+            effect: items.iter().map(|item| item.effect).max().unwrap_or_default(),
+            items,
+        }
+    }
+}
+
+/// Records the applied items that do not take effect immediately. An item
+/// applied again replaces its earlier record.
+pub fn record(records: &mut BTreeMap<String, PendingRecord>, applied: &[PendingItem], now: u64) {
+    for item in applied.iter().filter(|item| item.effect != Effect::Immediate) {
+        records.insert(
+            item.id.clone(),
+            PendingRecord {
+                name: item.name.clone(),
+                effect: item.effect,
+                applied_at: now,
+            },
+        );
+    }
+}
+
+/// Records rolled-back items. An item still waiting for its effect is back
+/// where it started and its record is dropped; rolling back one that already
+/// took effect needs the same restart, so it is recorded like an apply.
+/// Returns the items that now wait.
+pub fn record_rollback(records: &mut BTreeMap<String, PendingRecord>, rolled_back: &[PendingItem], now: u64) -> Vec<PendingItem> {
+    let mut waiting = Vec::new();
+    for item in rolled_back.iter().filter(|item| item.effect != Effect::Immediate) {
+        if records.remove(&item.id).is_none() {
+            waiting.push(item.clone());
+        }
+    }
+    record(records, &waiting, now);
+    waiting
+}
+
+/// Drops the records that took effect since they were made. Returns whether
+/// any were dropped.
+pub fn settle(records: &mut BTreeMap<String, PendingRecord>, times: &SessionTimes) -> bool {
+    let before = records.len();
+    records.retain(|_, record| !record.is_settled(times));
+    records.len() != before
+}
+
+pub fn pending(records: &BTreeMap<String, PendingRecord>) -> PendingEffects {
+    PendingEffects::new(
+        records
+            .iter()
+            .map(|(id, record)| PendingItem {
+                id: id.clone(),
+                name: record.name.clone(),
+                effect: record.effect,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, effect: Effect) -> PendingItem {
+        PendingItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            effect,
+        }
+    }
+
+    fn records() -> BTreeMap<String, PendingRecord> {
+        let mut records = BTreeMap::new();
+        record(
+            &mut records,
+            &[
+                item("explorer", Effect::ExplorerRestart),
+                item("signout", Effect::Signout),
+                item("reboot", Effect::Reboot),
+            ],
+            1_000,
+        );
+        records
+    }
+
+    fn remaining(records: &BTreeMap<String, PendingRecord>) -> Vec<&str> {
+        records.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn batch_takes_the_strongest_effect() {
+        let batch = PendingEffects::new(vec![
+            item("a", Effect::ExplorerRestart),
+            item("b", Effect::Reboot),
+            item("c", Effect::Signout),
+        ]);
+
+        assert_eq!(batch.effect, Effect::Reboot);
+        assert_eq!(PendingEffects::new(Vec::new()).effect, Effect::Immediate);
+    }
+
+    #[test]
+    fn immediate_items_are_not_recorded() {
+        let mut records = BTreeMap::new();
+        record(&mut records, &[item("a", Effect::Immediate), item("b", Effect::Signout)], 1_000);
+
+        assert_eq!(remaining(&records), vec!["b"]);
+    }
+
+    #[test]
+    fn rolling_back_a_pending_item_drops_its_record() {
+        let mut records = records();
+        let waiting = record_rollback(&mut records, &[item("reboot", Effect::Reboot)], 2_000);
+
+        assert!(waiting.is_empty());
+        assert_eq!(remaining(&records), vec!["explorer", "signout"]);
+    }
+
+    #[test]
+    fn rolling_back_a_settled_item_waits_again() {
+        let mut records = BTreeMap::new();
+        let waiting = record_rollback(&mut records, &[item("reboot", Effect::Reboot), item("a", Effect::Immediate)], 2_000);
+
+        assert_eq!(waiting, vec![item("reboot", Effect::Reboot)]);
+        assert_eq!(remaining(&records), vec!["reboot"]);
+        assert_eq!(records["reboot"].applied_at, 2_000);
+    }
+
+    #[test]
+    fn explorer_restart_settles_only_explorer_items() {
+        let mut records = records();
+        let times = SessionTimes {
+            boot: Some(100),
+            logon: Some(200),
+            explorer: Some(2_000),
+        };
+
+        assert!(settle(&mut records, &times));
+        assert_eq!(remaining(&records), vec!["reboot", "signout"]);
+    }
+
+    #[test]
+    fn signing_in_again_settles_signout_and_explorer_items() {
+        let mut records = records();
+        let times = SessionTimes {
+            boot: Some(100),
+            logon: Some(2_000),
+            explorer: Some(2_000),
+        };
+
+        settle(&mut records, &times);
+        assert_eq!(remaining(&records), vec!["reboot"]);
+    }
+
+    #[test]
+    fn reboot_settles_everything() {
+        let mut records = records();
+        let times = SessionTimes {
+            boot: Some(2_000),
+            logon: None,
+            explorer: None,
+        };
+
+        settle(&mut records, &times);
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn unknown_times_settle_nothing() {
+        let mut records = records();
+
+        assert!(!settle(&mut records, &SessionTimes::default()));
+        assert_eq!(records.len(), 3);
+        assert_eq!(pending(&records).effect, Effect::Reboot);
+    }
+
+    #[test]
+    fn parses_session_times() {
+        let times = parse_session_times("{\"Boot\":1700000000000,\"Logon\":1700000060000,\"Explorer\":null}\r\n").unwrap();
+
+        assert_eq!(times.boot, Some(1_700_000_000_000));
+        assert_eq!(times.logon, Some(1_700_000_060_000));
+        assert_eq!(times.explorer, None);
+    }
+}
@@ -0,0 +1,177 @@
+use serde::Serialize;
+use std::sync::OnceLock;
+
+#[cfg(target_os = "windows")]
+use crate::powershell;
+
+/// Command fragments that only succeed from an elevated process. Matching is
+/// case-insensitive because PowerShell cmdlets and registry drives are.
+const ADMIN_MARKERS: &[&str] = &[
+    "hklm:",
+    "hkey_local_machine",
+    "registry::hklm",
+    "set-service",
+    "stop-service",
+    "start-service",
+    "sc.exe",
+    "remove-appxprovisionedpackage",
+    "-allusers",
+    "set-mppreference",
+    "disable-mmagent",
+    "enable-mmagent",
+    "powercfg -h",
+    "bcdedit",
+    "disable-scheduledtask",
+    "enable-scheduledtask",
+    "windowsoptionalfeature",
+    "windowscapability",
+    "netfirewallrule",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PrivilegeStatus {
+    pub is_elevated: bool,
+    pub can_relaunch: bool,
+}
+
+/// Returns true when `command` touches machine-wide state (HKLM, services,
+/// provisioned packages, ...) and therefore needs an elevated process.
+pub fn requires_admin(command: &str) -> bool {
+    let command = command.to_lowercase();
+    ADMIN_MARKERS.iter().any(|marker| command.contains(marker))
+}
+
+/// Whether the current process runs with an elevated token. The answer cannot
+/// change for the lifetime of the process, so it is only queried once.
+pub fn is_elevated() -> bool {
+    static ELEVATED: OnceLock<bool> = OnceLock::new();
+    *ELEVATED.get_or_init(query_elevation)
+}
+
+/// Refuses to run `action` from an unelevated process with an error the UI can
+/// show as-is, instead of letting PowerShell fail with an access-denied dump.
+pub fn ensure_elevated(action: &str) -> Result<(), String> {
+    if is_elevated() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} requires administrator privileges. Restart Debloater as administrator and try again.",
+            action
+        ))
+    }
+}
+
+pub fn privilege_status() -> PrivilegeStatus {
+    let is_elevated = is_elevated();
+    PrivilegeStatus {
+        is_elevated,
+        can_relaunch: cfg!(target_os = "windows") && !is_elevated,
+    }
+}
+
+fn query_elevation() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        let ps_script = "([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)";
+
+        let output = powershell::run(ps_script);
+
+        match output {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).trim().eq_ignore_ascii_case("true"),
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        false
+    }
+}
+
+/// Starts a new elevated instance of the current executable through the UAC
+/// prompt. The caller is expected to exit once this returns `Ok`.
+pub fn relaunch_elevated() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let exe = std::env::current_exe()
+            .map_err(|e| format!("Failed to locate executable: {}", e))?;
+
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let mut script = format!(
+            "Start-Process -FilePath {} -Verb RunAs",
+            powershell::quote(&exe.to_string_lossy())
+        );
+        if !args.is_empty() {
+            script.push_str(&format!(" -ArgumentList {}", powershell::array(&args)));
+        }
+
+        let output = powershell::run(&script)
+            .map_err(|e| format!("Failed to relaunch as administrator: {}", e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            // Declining the UAC prompt lands here as well.
+            Err("Elevation was cancelled or failed".to_string())
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_registry_needs_admin() {
+        assert!(requires_admin(
+            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\DataCollection' -Name AllowTelemetry -Value 0"
+        ));
+        assert!(requires_admin("reg add HKEY_LOCAL_MACHINE\\SOFTWARE\\Test /v Value /d 1 /f"));
+        assert!(requires_admin("Get-Item 'Registry::HKLM\\SYSTEM\\CurrentControlSet'"));
+    }
+
+    #[test]
+    fn services_need_admin() {
+        assert!(requires_admin("Stop-Service -Name DiagTrack -Force; Set-Service -Name DiagTrack -StartupType Disabled"));
+        assert!(requires_admin("Start-Service WSearch"));
+        assert!(requires_admin("sc.exe config dmwappushservice start= disabled"));
+    }
+
+    #[test]
+    fn all_users_and_image_changes_need_admin() {
+        assert!(requires_admin("Get-AppxPackage -AllUsers *Clipchamp* | Remove-AppxPackage -AllUsers"));
+        assert!(requires_admin("Get-AppxProvisionedPackage -Online | Remove-AppxProvisionedPackage -Online"));
+        assert!(requires_admin("Disable-WindowsOptionalFeature -Online -FeatureName WorkFolders-Client -NoRestart"));
+        assert!(requires_admin("Remove-WindowsCapability -Online -Name 'MathRecognizer~~~~0.0.1.0'"));
+        assert!(requires_admin("powercfg -h off"));
+    }
+
+    #[test]
+    fn firewall_rules_need_admin() {
+        assert!(requires_admin(
+            "New-NetFirewallRule -DisplayName 'Block CompatTelRunner' -Direction Outbound -Action Block -Program '%SystemRoot%\\System32\\CompatTelRunner.exe'"
+        ));
+        assert!(requires_admin("Get-NetFirewallRule -DisplayName 'Block CompatTelRunner' | Remove-NetFirewallRule"));
+    }
+
+    #[test]
+    fn matching_ignores_case() {
+        assert!(requires_admin("set-itemproperty -path hklm:\\software\\test -name x -value 1"));
+        assert!(requires_admin("GET-APPXPACKAGE -ALLUSERS"));
+    }
+
+    #[test]
+    fn per_user_changes_do_not_need_admin() {
+        assert!(!requires_admin(
+            "Set-ItemProperty -Path 'HKCU:\\Software\\Microsoft\\Windows\\CurrentVersion\\AdvertisingInfo' -Name Enabled -Value 0"
+        ));
+        assert!(!requires_admin("Get-AppxPackage *Clipchamp* | Remove-AppxPackage"));
+        assert!(!requires_admin("Stop-Process -Name explorer -Force"));
+        assert!(!requires_admin(""));
+    }
+}
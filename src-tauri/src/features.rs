@@ -0,0 +1,349 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::{parse_json_list, wildcard_match};
+use crate::powershell;
+use crate::status::ItemState;
+
+/// Optional features (`Get-WindowsOptionalFeature`) are switched on and off
+/// in place; capabilities (`Get-WindowsCapability`, "Features on demand") are
+/// added and removed as packages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureKind {
+    OptionalFeature,
+    Capability,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureAction {
+    Enable,
+    Disable,
+    /// Disable an optional feature and delete its payload, or uninstall a
+    /// capability.
+    Remove,
+}
+
+/// One feature change of a catalog item. Capability names usually end in a
+/// `~~~~*` wildcard because their version suffix differs between builds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureChange {
+    pub kind: FeatureKind,
+    pub name: String,
+    pub action: FeatureAction,
+}
+
+impl FeatureChange {
+    fn inverse(&self) -> FeatureChange {
+        FeatureChange {
+            action: match self.action {
+                FeatureAction::Enable => FeatureAction::Disable,
+                FeatureAction::Disable | FeatureAction::Remove => FeatureAction::Enable,
+            },
+            ..self.clone()
+        }
+    }
+
+    fn query(&self) -> String {
+        match self.kind {
+            FeatureKind::OptionalFeature => format!(
+                "Get-WindowsOptionalFeature -Online -FeatureName {} -ErrorAction SilentlyContinue",
+                powershell::quote(&self.name)
+            ),
+            FeatureKind::Capability => format!(
+                "Get-WindowsCapability -Online -Name {} -ErrorAction SilentlyContinue",
+                powershell::quote(&self.name)
+            ),
+        }
+    }
+
+    /// States in which this change is already in effect.
+    fn applied_states(&self) -> &'static [&'static str] {
+        match (self.kind, self.action) {
+            (FeatureKind::OptionalFeature, FeatureAction::Enable) => &["Enabled", "EnablePending"],
+            (FeatureKind::OptionalFeature, FeatureAction::Disable) => {
+                &["Disabled", "DisabledWithPayloadRemoved", "DisablePending"]
+            }
+            (FeatureKind::OptionalFeature, FeatureAction::Remove) => &["DisabledWithPayloadRemoved"],
+            (FeatureKind::Capability, FeatureAction::Enable) => &["Installed", "InstallPending"],
+            (FeatureKind::Capability, _) => &["NotPresent", "UninstallPending"],
+        }
+    }
+
+    fn cmdlet(&self) -> &'static str {
+        match (self.kind, self.action) {
+            (FeatureKind::OptionalFeature, FeatureAction::Enable) => {
+                "Enable-WindowsOptionalFeature -Online -All -NoRestart"
+            }
+            (FeatureKind::OptionalFeature, FeatureAction::Disable) => {
+                "Disable-WindowsOptionalFeature -Online -NoRestart"
+            }
+            (FeatureKind::OptionalFeature, FeatureAction::Remove) => {
+                "Disable-WindowsOptionalFeature -Online -Remove -NoRestart"
+            }
+            (FeatureKind::Capability, FeatureAction::Enable) => "Add-WindowsCapability -Online",
+            (FeatureKind::Capability, _) => "Remove-WindowsCapability -Online",
+        }
+    }
+
+    /// Pipes the matching features that are not yet in the target state into
+    /// the cmdlet, so features missing from this build are skipped.
+    fn statement(&self) -> String {
+        let states: Vec<String> = self.applied_states().iter().map(|state| powershell::quote(state)).collect();
+        format!(
+            "{} | Where-Object {{ @({}) -notcontains $_.State.ToString() }} | {} -ErrorAction Stop",
+            self.query(),
+            states.join(", "),
+            self.cmdlet()
+        )
+    }
+
+    pub fn matches(&self, feature: &FeatureInfo) -> bool {
+        self.kind == feature.kind && wildcard_match(&self.name, &feature.name)
+    }
+}
+
+pub fn validate(changes: &[FeatureChange]) -> Result<(), String> {
+    for change in changes {
+        if change.name.trim().is_empty() {
+            return Err("Feature change has no name".to_string());
+        }
+        if change.kind == FeatureKind::Capability && change.action == FeatureAction::Disable {
+            return Err(format!(
+                "Capability {} cannot be disabled, only removed",
+                change.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Runs every change and prints `{"RestartNeeded": bool}` as its last line,
+/// combining the `RestartNeeded` flags the DISM cmdlets return.
+fn script(changes: &[FeatureChange]) -> String {
+    let statements: Vec<String> = changes
+        .iter()
+        .map(|change| {
+            format!(
+                "foreach ($r in @({})) {{ if ($r.RestartNeeded) {{ $restart = $true }} }}",
+                change.statement()
+            )
+        })
+        .collect();
+
+    format!(
+        "$restart = $false; {}; [pscustomobject]@{{ RestartNeeded = $restart }} | ConvertTo-Json -Compress",
+        statements.join("; ")
+    )
+}
+
+pub fn apply_script(changes: &[FeatureChange]) -> String {
+    script(changes)
+}
+
+/// Undoes the changes in reverse order. Removed features are restored from
+/// Windows Update, so rollback needs a network connection.
+pub fn rollback_script(changes: &[FeatureChange]) -> String {
+    let inverse: Vec<FeatureChange> = changes.iter().rev().map(FeatureChange::inverse).collect();
+    script(&inverse)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RestartReport {
+    restart_needed: bool,
+}
+
+/// Reads the restart flag from the last line of [`apply_script`] or
+/// [`rollback_script`] output.
+pub fn parse_restart_needed(output: &str) -> bool {
+    output
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| serde_json::from_str::<RestartReport>(line.trim()).ok())
+        .is_some_and(|report| report.restart_needed)
+}
+
+/// Reads the state of every feature the changes refer to, in the shape
+/// [`parse_features`] expects.
+pub fn status_script<'a>(changes: impl IntoIterator<Item = &'a FeatureChange>) -> String {
+    let queries: Vec<String> = changes
+        .into_iter()
+        .map(|change| {
+            let (kind, name) = match change.kind {
+                FeatureKind::OptionalFeature => ("optional_feature", "$_.FeatureName"),
+                FeatureKind::Capability => ("capability", "$_.Name"),
+            };
+            format!(
+                "{} | ForEach-Object {{ [pscustomobject]@{{ Kind = '{}'; Name = {}; State = $_.State.ToString() }} }}",
+                change.query(),
+                kind,
+                name
+            )
+        })
+        .collect();
+
+    format!("@({}) | ConvertTo-Json -Compress", queries.join("; "))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FeatureInfo {
+    pub kind: FeatureKind,
+    pub name: String,
+    pub state: String,
+}
+
+pub fn parse_features(json: &str) -> Result<Vec<FeatureInfo>, String> {
+    parse_json_list(json)
+}
+
+/// `None` when none of the features exist on this build.
+pub fn state(changes: &[FeatureChange], features: &[FeatureInfo]) -> Option<ItemState> {
+    let mut total = 0;
+    let mut applied = 0;
+
+    for change in changes {
+        for feature in features.iter().filter(|feature| change.matches(feature)) {
+            total += 1;
+            if change.applied_states().iter().any(|state| state.eq_ignore_ascii_case(&feature.state)) {
+                applied += 1;
+            }
+        }
+    }
+
+    (total > 0).then(|| ItemState::from_counts(applied, total))
+}
+
+/// Outcome of applying or rolling back a feature item.
+#[derive(Debug, Serialize)]
+pub struct FeatureResult {
+    pub success: bool,
+    pub output: String,
+    pub error: Option<String>,
+    /// Windows only finishes the change after a restart.
+    pub restart_needed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes() -> Vec<FeatureChange> {
+        serde_json::from_str(
+            r#"[
+                { "kind": "optional_feature", "name": "Internet-Explorer-Optional-amd64", "action": "disable" },
+                { "kind": "capability", "name": "Browser.InternetExplorer~~~~*", "action": "remove" }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    fn feature(kind: FeatureKind, name: &str, state: &str) -> FeatureInfo {
+        FeatureInfo {
+            kind,
+            name: name.to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn rejects_nameless_changes_and_disabled_capabilities() {
+        assert!(validate(&changes()).is_ok());
+
+        let mut nameless = changes();
+        nameless[0].name = " ".to_string();
+        assert_eq!(validate(&nameless), Err("Feature change has no name".to_string()));
+
+        let mut disabled = changes();
+        disabled[1].action = FeatureAction::Disable;
+        assert_eq!(
+            validate(&disabled),
+            Err("Capability Browser.InternetExplorer~~~~* cannot be disabled, only removed".to_string())
+        );
+    }
+
+    #[test]
+    fn apply_skips_features_already_in_the_target_state() {
+        let script = apply_script(&changes()[..1]);
+
+        assert_eq!(
+            script,
+            "$restart = $false; foreach ($r in @(Get-WindowsOptionalFeature -Online -FeatureName 'Internet-Explorer-Optional-amd64' -ErrorAction SilentlyContinue | Where-Object { @('Disabled', 'DisabledWithPayloadRemoved', 'DisablePending') -notcontains $_.State.ToString() } | Disable-WindowsOptionalFeature -Online -NoRestart -ErrorAction Stop)) { if ($r.RestartNeeded) { $restart = $true } }; [pscustomobject]@{ RestartNeeded = $restart } | ConvertTo-Json -Compress"
+        );
+        assert!(apply_script(&changes()).contains(
+            "Where-Object { @('NotPresent', 'UninstallPending') -notcontains $_.State.ToString() } | Remove-WindowsCapability -Online -ErrorAction Stop"
+        ));
+    }
+
+    #[test]
+    fn rollback_inverts_changes_in_reverse_order() {
+        let script = rollback_script(&changes());
+
+        let capability = script.find("Add-WindowsCapability -Online").unwrap();
+        let feature = script.find("Enable-WindowsOptionalFeature -Online -All -NoRestart").unwrap();
+        assert!(capability < feature);
+        assert!(!script.contains("Remove-WindowsCapability"));
+    }
+
+    #[test]
+    fn reads_the_restart_flag_from_the_last_line() {
+        assert!(parse_restart_needed("Path :\r\nOnline : True\r\n{\"RestartNeeded\":true}\r\n\r\n"));
+        assert!(!parse_restart_needed("{\"RestartNeeded\":false}"));
+        assert!(!parse_restart_needed("{\"RestartNeeded\":true}\nWARNING: something"));
+        assert!(!parse_restart_needed(""));
+    }
+
+    #[test]
+    fn status_queries_both_kinds() {
+        let script = status_script(&changes());
+
+        assert_eq!(
+            script,
+            "@(Get-WindowsOptionalFeature -Online -FeatureName 'Internet-Explorer-Optional-amd64' -ErrorAction SilentlyContinue | ForEach-Object { [pscustomobject]@{ Kind = 'optional_feature'; Name = $_.FeatureName; State = $_.State.ToString() } }; \
+             Get-WindowsCapability -Online -Name 'Browser.InternetExplorer~~~~*' -ErrorAction SilentlyContinue | ForEach-Object { [pscustomobject]@{ Kind = 'capability'; Name = $_.Name; State = $_.State.ToString() } }) | ConvertTo-Json -Compress"
+        );
+    }
+
+    #[test]
+    fn parses_feature_states() {
+        let features = parse_features(
+            r#"[
+                {"Kind":"optional_feature","Name":"Internet-Explorer-Optional-amd64","State":"Enabled"},
+                {"Kind":"capability","Name":"Browser.InternetExplorer~~~~0.0.11.0","State":"Installed"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            features,
+            [
+                feature(FeatureKind::OptionalFeature, "Internet-Explorer-Optional-amd64", "Enabled"),
+                feature(FeatureKind::Capability, "Browser.InternetExplorer~~~~0.0.11.0", "Installed"),
+            ]
+        );
+        assert!(parse_features("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn state_follows_the_matched_features() {
+        let removed = [
+            feature(FeatureKind::OptionalFeature, "Internet-Explorer-Optional-amd64", "DisablePending"),
+            feature(FeatureKind::Capability, "Browser.InternetExplorer~~~~0.0.11.0", "NotPresent"),
+        ];
+        let half = [
+            feature(FeatureKind::OptionalFeature, "Internet-Explorer-Optional-amd64", "disabled"),
+            feature(FeatureKind::Capability, "Browser.InternetExplorer~~~~0.0.11.0", "Installed"),
+        ];
+        let installed = [feature(FeatureKind::OptionalFeature, "Internet-Explorer-Optional-amd64", "Enabled")];
+        // Same name, other kind: not the feature the change refers to.
+        let other_kind = [feature(FeatureKind::Capability, "Internet-Explorer-Optional-amd64", "NotPresent")];
+
+        assert_eq!(state(&changes(), &removed), Some(ItemState::Applied));
+        assert_eq!(state(&changes(), &half), Some(ItemState::Partial));
+        assert_eq!(state(&changes(), &installed), Some(ItemState::NotApplied));
+        assert_eq!(state(&changes(), &other_kind), None);
+        assert_eq!(state(&changes(), &[]), None);
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::parse_json_list;
+use crate::powershell;
+use crate::status::ItemState;
+
+/// Rule group every Debloater firewall rule is created in, so the rules can
+/// be told apart from the user's own and found again for rollback.
+pub const RULE_GROUP: &str = "Debloater Telemetry Blocking";
+
+/// An outbound block rule for one program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirewallRule {
+    /// Display name of the rule; unique within [`RULE_GROUP`].
+    pub name: String,
+    /// Path of the executable, environment variables such as `%SystemRoot%`
+    /// are expanded by the firewall service.
+    pub program: String,
+}
+
+pub fn validate(rules: &[FirewallRule]) -> Result<(), String> {
+    for rule in rules {
+        if rule.name.trim().is_empty() {
+            return Err(format!("Firewall rule for {} has no name", rule.program));
+        }
+        if !rule.program.to_lowercase().ends_with(".exe") {
+            return Err(format!("Firewall rule {} must target an executable", rule.name));
+        }
+    }
+    Ok(())
+}
+
+fn remove_statement(rule: &FirewallRule) -> String {
+    format!(
+        "Get-NetFirewallRule -DisplayName {} -ErrorAction SilentlyContinue | Where-Object {{ $_.Group -eq {} }} | Remove-NetFirewallRule",
+        powershell::quote(&powershell::escape_wildcards(&rule.name)),
+        powershell::quote(RULE_GROUP)
+    )
+}
+
+/// Creates the rules, replacing earlier copies so applying twice leaves one
+/// rule per program.
+pub fn apply_script(rules: &[FirewallRule]) -> String {
+    rules
+        .iter()
+        .map(|rule| {
+            format!(
+                "{}; New-NetFirewallRule -DisplayName {} -Group {} -Direction Outbound -Action Block -Profile Any -Program {} | Out-Null",
+                remove_statement(rule),
+                powershell::quote(&rule.name),
+                powershell::quote(RULE_GROUP),
+                powershell::quote(&rule.program)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn rollback_script(rules: &[FirewallRule]) -> String {
+    rules.iter().map(remove_statement).collect::<Vec<_>>().join("; ")
+}
+
+/// Lists the enabled rules of [`RULE_GROUP`], in the shape [`parse_rules`]
+/// expects.
+pub fn status_script() -> String {
+    format!(
+        "@(Get-NetFirewallRule -ErrorAction SilentlyContinue | \
+            Where-Object {{ $_.Group -eq {} -and $_.Enabled -eq 'True' }} | \
+            ForEach-Object {{ [pscustomobject]@{{ DisplayName = $_.DisplayName }} }}) | ConvertTo-Json -Compress",
+        powershell::quote(RULE_GROUP)
+    )
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawRule {
+    display_name: String,
+}
+
+/// Display names of the enabled Debloater rules.
+pub fn parse_rules(json: &str) -> Result<Vec<String>, String> {
+    Ok(parse_json_list::<RawRule>(json)?
+        .into_iter()
+        .map(|rule| rule.display_name)
+        .collect())
+}
+
+pub fn state(rules: &[FirewallRule], enabled: &[String]) -> ItemState {
+    let applied = rules
+        .iter()
+        .filter(|rule| enabled.iter().any(|name| name.eq_ignore_ascii_case(&rule.name)))
+        .count();
+
+    ItemState::from_counts(applied, rules.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<FirewallRule> {
+        serde_json::from_str(
+            r#"[
+                { "name": "Block CompatTelRunner", "program": "%SystemRoot%\\System32\\CompatTelRunner.exe" },
+                { "name": "Block DeviceCensus", "program": "%SystemRoot%\\System32\\DeviceCensus.EXE" }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_names_and_programs() {
+        assert!(validate(&rules()).is_ok());
+
+        let mut unnamed = rules();
+        unnamed[0].name = "  ".to_string();
+        assert_eq!(
+            validate(&unnamed),
+            Err("Firewall rule for %SystemRoot%\\System32\\CompatTelRunner.exe has no name".to_string())
+        );
+
+        let mut folder = rules();
+        folder[1].program = "%SystemRoot%\\System32".to_string();
+        assert_eq!(
+            validate(&folder),
+            Err("Firewall rule Block DeviceCensus must target an executable".to_string())
+        );
+    }
+
+    #[test]
+    fn apply_replaces_earlier_copies() {
+        assert_eq!(
+            apply_script(&rules()[..1]),
+            "Get-NetFirewallRule -DisplayName 'Block CompatTelRunner' -ErrorAction SilentlyContinue | Where-Object { $_.Group -eq 'Debloater Telemetry Blocking' } | Remove-NetFirewallRule; \
+             New-NetFirewallRule -DisplayName 'Block CompatTelRunner' -Group 'Debloater Telemetry Blocking' -Direction Outbound -Action Block -Profile Any -Program '%SystemRoot%\\System32\\CompatTelRunner.exe' | Out-Null"
+        );
+    }
+
+    #[test]
+    fn rollback_removes_only_grouped_rules() {
+        let script = rollback_script(&rules());
+
+        assert_eq!(script.matches("Remove-NetFirewallRule").count(), 2);
+        assert_eq!(script.matches("Where-Object { $_.Group -eq 'Debloater Telemetry Blocking' }").count(), 2);
+        assert!(!script.contains("New-NetFirewallRule"));
+    }
+
+    #[test]
+    fn removal_matches_display_names_literally() {
+        let rule = FirewallRule {
+            name: "Block [Telemetry] *".to_string(),
+            program: "C:\\Tools\\o'brien.exe".to_string(),
+        };
+
+        assert!(rollback_script(std::slice::from_ref(&rule)).starts_with("Get-NetFirewallRule -DisplayName 'Block `[Telemetry`] `*' "));
+        assert!(apply_script(&[rule]).contains("-DisplayName 'Block [Telemetry] *' -Group 'Debloater Telemetry Blocking' -Direction Outbound -Action Block -Profile Any -Program 'C:\\Tools\\o''brien.exe'"));
+    }
+
+    #[test]
+    fn status_lists_enabled_grouped_rules() {
+        assert_eq!(
+            status_script(),
+            "@(Get-NetFirewallRule -ErrorAction SilentlyContinue | Where-Object { $_.Group -eq 'Debloater Telemetry Blocking' -and $_.Enabled -eq 'True' } | ForEach-Object { [pscustomobject]@{ DisplayName = $_.DisplayName } }) | ConvertTo-Json -Compress"
+        );
+    }
+
+    #[test]
+    fn parses_rule_names() {
+        assert_eq!(parse_rules(r#"{"DisplayName":"Block CompatTelRunner"}"#).unwrap(), ["Block CompatTelRunner"]);
+        assert_eq!(
+            parse_rules(r#"[{"DisplayName":"Block CompatTelRunner"},{"DisplayName":"Block DeviceCensus"}]"#).unwrap(),
+            ["Block CompatTelRunner", "Block DeviceCensus"]
+        );
+        assert!(parse_rules("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn state_counts_enabled_rules() {
+        let names = |names: &[&str]| -> Vec<String> { names.iter().map(|name| name.to_string()).collect() };
+
+        assert_eq!(state(&rules(), &names(&["block compattelrunner", "Block DeviceCensus"])), ItemState::Applied);
+        assert_eq!(state(&rules(), &names(&["Block DeviceCensus", "Someone else's rule"])), ItemState::Partial);
+        assert_eq!(state(&rules(), &[]), ItemState::NotApplied);
+    }
+}
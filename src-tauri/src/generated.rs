@@ -0,0 +1,30 @@
+/// Cases each property test checks.
+pub const CASES: usize = 2000;
+
+/// Seeded random strings for the property tests of the package matcher and
+/// the quoting helpers. A xorshift64* generator: deterministic, so a failing
+/// case reproduces on every run.
+pub struct Strings(u64);
+
+impl Strings {
+    pub fn new(seed: u64) -> Self {
+        Strings(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Up to `max_len` characters drawn from `alphabet`.
+    pub fn string(&mut self, alphabet: &[char], max_len: usize) -> String {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| alphabet[self.below(alphabet.len())]).collect()
+    }
+}
@@ -0,0 +1,253 @@
+/// Marker comments around the block Debloater manages in the hosts file.
+/// Everything outside them belongs to the user and is kept byte for byte.
+pub const BEGIN_MARKER: &str = "# BEGIN Debloater telemetry block";
+pub const END_MARKER: &str = "# END Debloater telemetry block";
+
+/// Address the blocked hostnames resolve to. `0.0.0.0` fails immediately
+/// instead of waiting on a connection to the local machine.
+const SINK_ADDRESS: &str = "0.0.0.0";
+
+/// The hosts file of the running system.
+#[cfg(target_os = "windows")]
+pub fn hosts_path() -> std::path::PathBuf {
+    let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+    std::path::Path::new(&system_root).join("System32\\drivers\\etc\\hosts")
+}
+
+pub fn validate_hostname(hostname: &str) -> Result<(), String> {
+    let valid = !hostname.is_empty()
+        && hostname.len() <= 253
+        && hostname
+            .split('.')
+            .all(|label| !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid hostname: {}", hostname))
+    }
+}
+
+/// The line ending the file already uses; Windows' own hosts file uses CRLF,
+/// so that is also the choice for an empty file.
+fn line_ending(content: &str) -> &'static str {
+    match content.find('\n') {
+        Some(index) if index > 0 && content.as_bytes()[index - 1] == b'\r' => "\r\n",
+        Some(_) => "\n",
+        None => "\r\n",
+    }
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.trim_end_matches(['\r', '\n']).trim() == marker
+}
+
+/// Byte range of the managed block, from the start of the begin marker line
+/// to the end of the end marker line including its line break.
+fn block_range(content: &str) -> Result<Option<(usize, usize)>, String> {
+    let mut offset = 0;
+    let mut begin = None;
+
+    for line in content.split_inclusive('\n') {
+        let end = offset + line.len();
+        match begin {
+            None if is_marker(line, BEGIN_MARKER) => begin = Some(offset),
+            None if is_marker(line, END_MARKER) => {
+                return Err("Hosts file has an end marker without a begin marker".to_string());
+            }
+            Some(start) if is_marker(line, END_MARKER) => return Ok(Some((start, end))),
+            Some(_) if is_marker(line, BEGIN_MARKER) => {
+                return Err("Hosts file has nested Debloater blocks".to_string());
+            }
+            _ => {}
+        }
+        offset = end;
+    }
+
+    match begin {
+        Some(_) => Err("Hosts file has an unterminated Debloater block".to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Hostnames currently listed in the managed block.
+pub fn blocked_hosts(content: &str) -> Result<Vec<String>, String> {
+    let Some((start, end)) = block_range(content)? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(content[start..end]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Removes the managed block, leaving every other line untouched.
+pub fn remove_block(content: &str) -> Result<String, String> {
+    Ok(match block_range(content)? {
+        Some((start, end)) => format!("{}{}", &content[..start], &content[end..]),
+        None => content.to_string(),
+    })
+}
+
+fn render_block(hosts: &[String], eol: &str) -> String {
+    let mut block = format!("{}{}", BEGIN_MARKER, eol);
+    for host in hosts {
+        block.push_str(&format!("{} {}{}", SINK_ADDRESS, host, eol));
+    }
+    block.push_str(END_MARKER);
+    block.push_str(eol);
+    block
+}
+
+/// Replaces the managed block with one listing `hosts`, in place if the file
+/// already has one and appended otherwise. An empty list removes the block.
+pub fn write_block(content: &str, hosts: &[String]) -> Result<String, String> {
+    for host in hosts {
+        validate_hostname(host)?;
+    }
+    if hosts.is_empty() {
+        return remove_block(content);
+    }
+
+    let eol = line_ending(content);
+    let block = render_block(hosts, eol);
+
+    Ok(match block_range(content)? {
+        Some((start, end)) => format!("{}{}{}", &content[..start], block, &content[end..]),
+        None if content.is_empty() || content.ends_with('\n') => format!("{}{}", content, block),
+        None => format!("{}{}{}", content, eol, block),
+    })
+}
+
+/// Adds `hosts` to whatever the block already lists, keeping its order.
+pub fn merged(current: &[String], hosts: &[String]) -> Vec<String> {
+    let mut merged = current.to_vec();
+    for host in hosts {
+        if !merged.iter().any(|existing| existing.eq_ignore_ascii_case(host)) {
+            merged.push(host.clone());
+        }
+    }
+    merged
+}
+
+/// `current` without `hosts`.
+pub fn without(current: &[String], hosts: &[String]) -> Vec<String> {
+    current
+        .iter()
+        .filter(|existing| !hosts.iter().any(|host| host.eq_ignore_ascii_case(existing)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS_DEFAULT: &str = "# Copyright (c) 1993-2009 Microsoft Corp.\r\n#\r\n# localhost name resolution is handled within DNS itself.\r\n#\t127.0.0.1       localhost\r\n#\t::1             localhost\r\n";
+
+    fn hosts(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn appends_block_with_existing_line_endings() {
+        let written = write_block(WINDOWS_DEFAULT, &hosts(&["vortex.data.microsoft.com"])).unwrap();
+
+        assert!(written.starts_with(WINDOWS_DEFAULT));
+        assert!(written.ends_with(
+            "# BEGIN Debloater telemetry block\r\n0.0.0.0 vortex.data.microsoft.com\r\n# END Debloater telemetry block\r\n"
+        ));
+        assert!(!written.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn keeps_lf_files_lf() {
+        let written = write_block("127.0.0.1 dev.local\n", &hosts(&["a.example"])).unwrap();
+
+        assert_eq!(
+            written,
+            "127.0.0.1 dev.local\n# BEGIN Debloater telemetry block\n0.0.0.0 a.example\n# END Debloater telemetry block\n"
+        );
+    }
+
+    #[test]
+    fn insert_is_idempotent() {
+        let list = hosts(&["a.example", "b.example"]);
+        let once = write_block(WINDOWS_DEFAULT, &list).unwrap();
+        let twice = write_block(&once, &list).unwrap();
+
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn remove_restores_original() {
+        let written = write_block(WINDOWS_DEFAULT, &hosts(&["a.example"])).unwrap();
+
+        assert_eq!(remove_block(&written).unwrap(), WINDOWS_DEFAULT);
+        assert_eq!(remove_block(WINDOWS_DEFAULT).unwrap(), WINDOWS_DEFAULT);
+    }
+
+    #[test]
+    fn preserves_user_lines_around_block() {
+        let content = "10.0.0.1 nas\r\n# BEGIN Debloater telemetry block\r\n0.0.0.0 old.example\r\n# END Debloater telemetry block\r\n10.0.0.2 printer\r\n";
+        let written = write_block(content, &hosts(&["new.example"])).unwrap();
+
+        assert_eq!(
+            written,
+            "10.0.0.1 nas\r\n# BEGIN Debloater telemetry block\r\n0.0.0.0 new.example\r\n# END Debloater telemetry block\r\n10.0.0.2 printer\r\n"
+        );
+        assert_eq!(remove_block(&written).unwrap(), "10.0.0.1 nas\r\n10.0.0.2 printer\r\n");
+    }
+
+    #[test]
+    fn terminates_last_user_line() {
+        let written = write_block("10.0.0.1 nas", &hosts(&["a.example"])).unwrap();
+
+        assert!(written.starts_with("10.0.0.1 nas\r\n# BEGIN"));
+    }
+
+    #[test]
+    fn empty_list_removes_block() {
+        let written = write_block(WINDOWS_DEFAULT, &hosts(&["a.example"])).unwrap();
+
+        assert_eq!(write_block(&written, &[]).unwrap(), WINDOWS_DEFAULT);
+        assert_eq!(write_block("", &[]).unwrap(), "");
+    }
+
+    #[test]
+    fn reads_blocked_hosts() {
+        let written = write_block(WINDOWS_DEFAULT, &hosts(&["a.example", "b.example"])).unwrap();
+
+        assert_eq!(blocked_hosts(&written).unwrap(), hosts(&["a.example", "b.example"]));
+        assert!(blocked_hosts(WINDOWS_DEFAULT).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_blocks() {
+        assert!(remove_block("# BEGIN Debloater telemetry block\r\n0.0.0.0 a.example\r\n").is_err());
+        assert!(remove_block("# END Debloater telemetry block\r\n").is_err());
+        assert!(write_block(
+            "# BEGIN Debloater telemetry block\r\n# BEGIN Debloater telemetry block\r\n# END Debloater telemetry block\r\n",
+            &hosts(&["a.example"])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_hostnames() {
+        assert!(write_block("", &hosts(&["a.example\r\n0.0.0.0 other"])).is_err());
+        assert!(write_block("", &hosts(&["bad..example"])).is_err());
+        assert!(validate_hostname("v10.events.data.microsoft.com").is_ok());
+    }
+
+    #[test]
+    fn merges_and_removes_case_insensitively() {
+        let current = hosts(&["a.example", "b.example"]);
+
+        assert_eq!(merged(&current, &hosts(&["B.example", "c.example"])), hosts(&["a.example", "b.example", "c.example"]));
+        assert_eq!(without(&current, &hosts(&["A.EXAMPLE"])), hosts(&["b.example"]));
+    }
+}
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::package_match::{parse_json_list, PackageIdentity};
+
+/// Lists the current user's packages with the fields [`parse_inventory`]
+/// expects. Enum-valued properties are stringified so the JSON carries names
+/// rather than numeric values, and the install size is summed on disk since
+/// Appx does not record it.
+pub const INVENTORY_SCRIPT: &str = "Get-AppxPackage | ForEach-Object { \
+    $size = $null; \
+    if ($_.InstallLocation -and (Test-Path -LiteralPath $_.InstallLocation)) { \
+        $size = (Get-ChildItem -LiteralPath $_.InstallLocation -Recurse -File -Force -ErrorAction SilentlyContinue | Measure-Object -Property Length -Sum).Sum \
+    }; \
+    [pscustomobject]@{ \
+        Name = $_.Name; \
+        PackageFamilyName = $_.PackageFamilyName; \
+        PackageFullName = $_.PackageFullName; \
+        Version = \"$($_.Version)\"; \
+        Publisher = $_.Publisher; \
+        Architecture = \"$($_.Architecture)\"; \
+        InstallLocation = $_.InstallLocation; \
+        InstallSize = $size; \
+        IsFramework = $_.IsFramework; \
+        NonRemovable = $_.NonRemovable; \
+        SignatureKind = \"$($_.SignatureKind)\"; \
+        Dependencies = @($_.Dependencies | ForEach-Object { $_.PackageFullName }) \
+    } \
+} | ConvertTo-Json -Depth 3 -Compress";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawPackage {
+    name: String,
+    package_family_name: String,
+    package_full_name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    publisher: String,
+    #[serde(default)]
+    architecture: String,
+    install_location: Option<String>,
+    install_size: Option<u64>,
+    #[serde(default)]
+    is_framework: bool,
+    #[serde(default)]
+    non_removable: bool,
+    #[serde(default)]
+    signature_kind: String,
+    /// `null` rather than `[]` for packages without dependencies on some
+    /// PowerShell versions.
+    #[serde(default)]
+    dependencies: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub family_name: String,
+    pub full_name: String,
+    pub version: String,
+    pub publisher: String,
+    pub architecture: String,
+    pub install_location: Option<String>,
+    pub install_size: Option<u64>,
+    pub is_framework: bool,
+    pub non_removable: bool,
+    pub signature_kind: String,
+    /// Full names of packages this one depends on.
+    pub dependencies: Vec<String>,
+    /// Full names of installed packages that depend on this one. Removing a
+    /// package with dependents breaks them.
+    pub dependents: Vec<String>,
+    /// Whether removing the package is sensible on its own: Windows refuses
+    /// NonRemovable packages and frameworks go away with their last dependent.
+    pub removable: bool,
+}
+
+impl PackageInfo {
+    pub fn identity(&self) -> PackageIdentity {
+        PackageIdentity {
+            name: self.name.clone(),
+            family_name: self.family_name.clone(),
+            full_name: self.full_name.clone(),
+            user_sids: Vec::new(),
+            install_location: self.install_location.clone(),
+        }
+    }
+}
+
+/// Parses [`INVENTORY_SCRIPT`] output and fills in the reverse dependency
+/// edges. Packages are returned largest first.
+pub fn parse_inventory(json: &str) -> Result<Vec<PackageInfo>, String> {
+    let raw: Vec<RawPackage> = parse_json_list(json)?;
+
+    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+    for package in &raw {
+        for dependency in package.dependencies.iter().flatten() {
+            dependents
+                .entry(dependency.to_lowercase())
+                .or_default()
+                .push(package.package_full_name.clone());
+        }
+    }
+
+    let mut packages: Vec<PackageInfo> = raw
+        .into_iter()
+        .map(|raw| {
+            let mut package_dependents = dependents
+                .remove(&raw.package_full_name.to_lowercase())
+                .unwrap_or_default();
+            package_dependents.sort();
+            package_dependents.dedup();
+
+            PackageInfo {
+                removable: !raw.non_removable && !raw.is_framework && package_dependents.is_empty(),
+                name: raw.name,
+                family_name: raw.package_family_name,
+                full_name: raw.package_full_name,
+                version: raw.version,
+                publisher: raw.publisher,
+                architecture: raw.architecture,
+                install_location: raw.install_location.filter(|l| !l.is_empty()),
+                install_size: raw.install_size,
+                is_framework: raw.is_framework,
+                non_removable: raw.non_removable,
+                signature_kind: raw.signature_kind,
+                dependencies: raw.dependencies.unwrap_or_default(),
+                dependents: package_dependents,
+            }
+        })
+        .collect();
+
+    packages.sort_by(|a, b| {
+        b.install_size
+            .unwrap_or(0)
+            .cmp(&a.install_size.unwrap_or(0))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHOTOS: &str = r#"{"Name":"Microsoft.Windows.Photos","PackageFamilyName":"Microsoft.Windows.Photos_8wekyb3d8bbwe","PackageFullName":"Microsoft.Windows.Photos_2024.11_x64__8wekyb3d8bbwe","Version":"2024.11","Publisher":"CN=Microsoft Corporation, O=Microsoft Corporation, L=Redmond, S=Washington, C=US","Architecture":"X64","InstallLocation":"C:\\Program Files\\WindowsApps\\Microsoft.Windows.Photos_2024.11_x64__8wekyb3d8bbwe","InstallSize":52428800,"IsFramework":false,"NonRemovable":false,"SignatureKind":"Store","Dependencies":["Microsoft.VCLibs.140.00_14.0.33519.0_x64__8wekyb3d8bbwe"]}"#;
+
+    const VCLIBS: &str = r#"{"Name":"Microsoft.VCLibs.140.00","PackageFamilyName":"Microsoft.VCLibs.140.00_8wekyb3d8bbwe","PackageFullName":"Microsoft.VCLibs.140.00_14.0.33519.0_x64__8wekyb3d8bbwe","Version":"14.0.33519.0","Publisher":"CN=Microsoft Corporation","Architecture":"X64","InstallLocation":"C:\\Program Files\\WindowsApps\\Microsoft.VCLibs.140.00_14.0.33519.0_x64__8wekyb3d8bbwe","InstallSize":1048576,"IsFramework":true,"NonRemovable":false,"SignatureKind":"Store","Dependencies":null}"#;
+
+    const SHELL: &str = r#"{"Name":"Microsoft.Windows.ShellExperienceHost","PackageFamilyName":"Microsoft.Windows.ShellExperienceHost_cw5n1h2txyewy","PackageFullName":"Microsoft.Windows.ShellExperienceHost_10.0.26100.1_neutral_neutral_cw5n1h2txyewy","Version":"10.0.26100.1","Publisher":"CN=Microsoft Windows","Architecture":"Neutral","InstallLocation":"","InstallSize":null,"IsFramework":false,"NonRemovable":true,"SignatureKind":"System","Dependencies":[]}"#;
+
+    #[test]
+    fn parses_a_single_object() {
+        let packages = parse_inventory(PHOTOS).unwrap();
+
+        assert_eq!(packages.len(), 1);
+        let photos = &packages[0];
+        assert_eq!(photos.name, "Microsoft.Windows.Photos");
+        assert_eq!(photos.install_size, Some(52428800));
+        assert_eq!(photos.dependencies, ["Microsoft.VCLibs.140.00_14.0.33519.0_x64__8wekyb3d8bbwe"]);
+        assert!(photos.dependents.is_empty());
+        assert!(photos.removable);
+        assert_eq!(photos.identity().family_name, "Microsoft.Windows.Photos_8wekyb3d8bbwe");
+    }
+
+    #[test]
+    fn parses_an_array_largest_first() {
+        let packages = parse_inventory(&format!("[{}, {}, {}]", VCLIBS, SHELL, PHOTOS)).unwrap();
+
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Microsoft.Windows.Photos", "Microsoft.VCLibs.140.00", "Microsoft.Windows.ShellExperienceHost"]
+        );
+        assert!(parse_inventory("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn accepts_null_dependencies() {
+        let vclibs = parse_inventory(VCLIBS).unwrap().remove(0);
+
+        assert!(vclibs.dependencies.is_empty());
+    }
+
+    #[test]
+    fn frameworks_with_dependents_are_not_removable() {
+        let packages = parse_inventory(&format!("[{}, {}]", PHOTOS, VCLIBS)).unwrap();
+        let vclibs = packages.iter().find(|p| p.name == "Microsoft.VCLibs.140.00").unwrap();
+
+        assert!(vclibs.is_framework);
+        assert_eq!(vclibs.dependents, ["Microsoft.Windows.Photos_2024.11_x64__8wekyb3d8bbwe"]);
+        assert!(!vclibs.removable);
+    }
+
+    #[test]
+    fn non_removable_system_packages() {
+        let shell = parse_inventory(SHELL).unwrap().remove(0);
+
+        assert!(shell.non_removable);
+        assert!(!shell.removable);
+        assert_eq!(shell.install_location, None);
+        assert_eq!(shell.install_size, None);
+        assert_eq!(shell.signature_kind, "System");
+    }
+}
@@ -0,0 +1,30 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Reads the JSON file at `path`, or the default value when it does not
+/// exist yet. `what` names the contents in error messages, e.g. "custom apps".
+pub fn load<T: DeserializeOwned + Default>(path: &Path, what: &str) -> Result<T, String> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", what, e))?;
+
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", what, e))
+}
+
+/// Writes `value` as pretty JSON to `path`, creating the data directory first.
+pub fn save<T: Serialize + ?Sized>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", what, e))?;
+
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", what, e))
+}
@@ -1,30 +1,169 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use tauri::Manager;
 
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
+mod applicability;
+mod batch;
+mod composite;
+mod context_menu;
+mod discovery;
+mod effects;
+mod elevation;
+mod features;
+mod firewall;
+#[cfg(test)]
+mod generated;
+mod hosts;
+mod locale;
+mod inventory;
+mod json_store;
+mod package_match;
+mod package_scope;
+mod plan;
+mod policy;
+mod powershell;
+mod preflight;
+mod reinstall;
+mod risk;
+mod search;
+mod startup;
+mod status;
+mod system_info;
+mod tasks;
+mod win32;
+mod winget;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DebloatItem {
     pub id: String,
     pub name: String,
     pub description: String,
     pub category: String,
-    pub safe: bool,
+    #[serde(default)]
+    pub risk: risk::Risk,
+    #[serde(default)]
+    pub tags: Vec<search::Tag>,
+    /// Empty for composite items, which run `steps` instead, for hosts file
+    /// items, and for policy, task, firewall, feature and context menu items,
+    /// whose commands are rendered on load.
+    #[serde(default)]
     pub command: String,
     pub rollback_command: Option<String>,
+    #[serde(default)]
+    pub requires_admin: bool,
+    /// What it takes for the item to take effect once applied.
+    #[serde(default)]
+    pub effect: effects::Effect,
+    /// Builds, editions and architectures the item exists on.
+    #[serde(flatten)]
+    pub requirements: applicability::Requirements,
+    /// Whether the item applies to the running system, filled in on load.
+    #[serde(default, skip_deserializing)]
+    pub applicability: applicability::Applicability,
+    /// Items this one requires, conflicts with or runs after.
+    #[serde(flatten)]
+    pub relations: plan::Relations,
+    #[serde(default)]
+    pub steps: Vec<composite::Step>,
+    #[serde(default)]
+    pub policies: Vec<policy::Policy>,
+    /// Full scheduled task paths, wildcards allowed.
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    /// Hostnames to add to the managed block of the hosts file.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    #[serde(default)]
+    pub firewall_rules: Vec<firewall::FirewallRule>,
+    #[serde(default)]
+    pub features: Vec<features::FeatureChange>,
+    #[serde(default)]
+    pub context_menu: Option<context_menu::ContextMenuChange>,
+}
+
+impl DebloatItem {
+    fn task_patterns(&self) -> Result<Vec<tasks::TaskPattern>, String> {
+        self.tasks.iter().map(|task| tasks::TaskPattern::parse(task)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppSource {
+    Appx,
+    Winget,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct BloatwareApp {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
-    pub safe: bool,
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub risk: risk::Risk,
+    pub tags: Vec<search::Tag>,
+    pub source: AppSource,
+    /// Appx package pattern, or the package identifier for winget apps.
     pub package_pattern: String,
     pub is_installed: bool,
+    pub installed_for_other_users: Option<bool>,
+    pub provisioned: Option<bool>,
+    pub matched_packages: Vec<String>,
+    pub reinstall: Vec<reinstall::ReinstallStrategy>,
+    /// Why `is_installed` is unknown, e.g. winget could not be queried.
+    pub status_error: Option<String>,
+}
+
+impl BloatwareApp {
+    fn store(mut self, product_id: &str) -> Self {
+        self.reinstall.push(reinstall::ReinstallStrategy::Store { product_id: product_id.to_string() });
+        self
+    }
+    
+    fn winget(mut self, id: &str) -> Self {
+        self.reinstall.push(reinstall::ReinstallStrategy::Winget { id: id.to_string() });
+        self
+    }
+    
+    fn because(mut self, reasons: &[risk::RiskReason]) -> Self {
+        self.risk = self.risk.because(reasons);
+        self
+    }
+    
+    fn tagged(mut self, tags: &[search::Tag]) -> Self {
+        self.tags.extend_from_slice(tags);
+        self
+    }
+}
+
+fn winget_app(entry: &winget::WingetCatalogEntry) -> BloatwareApp {
+    BloatwareApp {
+        source: AppSource::Winget,
+        risk: entry.risk.clone(),
+        ..bloatware(&entry.id, &entry.name, &entry.description, entry.risk.level, &entry.winget_id)
+    }
+    .winget(&entry.winget_id)
+    .tagged(&entry.tags)
+}
+
+fn bloatware(id: &str, name: &str, description: &str, level: risk::RiskLevel, package_pattern: &str) -> BloatwareApp {
+    BloatwareApp {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        risk: risk::Risk::level(level),
+        tags: Vec::new(),
+        source: AppSource::Appx,
+        package_pattern: package_pattern.to_string(),
+        is_installed: false,
+        installed_for_other_users: None,
+        provisioned: None,
+        matched_packages: Vec::new(),
+        reinstall: Vec::new(),
+        status_error: None,
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +179,23 @@ pub struct PresetsData {
     pub presets: Vec<Preset>,
 }
 
+/// A selection expanded by [`plan::resolve`], with the catalog items it
+/// runs in plan order. Apps are only listed in the steps.
+#[derive(Debug, Serialize)]
+pub struct BatchPlan {
+    #[serde(flatten)]
+    pub plan: plan::Plan,
+    pub items: Vec<DebloatItem>,
+}
+
+/// A batch that passed [`begin_apply`]: the ticket its apply commands present
+/// and the pre-flight report it passed with.
+#[derive(Debug, Serialize)]
+pub struct ApplyTicket {
+    pub ticket: String,
+    pub preflight: preflight::PreflightReport,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CommandResult {
     pub success: bool,
@@ -48,403 +204,2038 @@ pub struct CommandResult {
 }
 
 fn get_bloatware_definitions() -> Vec<BloatwareApp> {
+    use risk::RiskLevel::{Critical, High, Low, Medium};
+    use risk::RiskReason::{BreaksDevices, BreaksGaming, BreaksStoreUpdates, BreaksSync, HardToReinstall};
+    use search::Tag::{Enterprise, Gaming, Laptop, Performance, Privacy, Security};
+    
     vec![
-        BloatwareApp { id: "clipchamp", name: "Clipchamp", description: "Video editor from Microsoft", safe: true, package_pattern: "Clipchamp.Clipchamp".to_string(), is_installed: false },
-        BloatwareApp { id: "cortana", name: "Cortana", description: "Microsoft Cortana voice assistant (Discontinued)", safe: true, package_pattern: "Microsoft.549981C3F5F10".to_string(), is_installed: false },
-        BloatwareApp { id: "copilot", name: "Microsoft Copilot", description: "AI assistant integrated into Windows", safe: true, package_pattern: "Microsoft.Copilot".to_string(), is_installed: false },
-        BloatwareApp { id: "bing-weather", name: "Weather App", description: "Weather forecast via Bing", safe: true, package_pattern: "Microsoft.BingWeather".to_string(), is_installed: false },
-        BloatwareApp { id: "bing-news", name: "News App", description: "News aggregator via Bing", safe: true, package_pattern: "Microsoft.BingNews".to_string(), is_installed: false },
-        BloatwareApp { id: "bing-sports", name: "Bing Sports", description: "Sports news and scores via Bing (Discontinued)", safe: true, package_pattern: "Microsoft.BingSports".to_string(), is_installed: false },
-        BloatwareApp { id: "bing-finance", name: "Bing Finance", description: "Finance news and tracking via Bing (Discontinued)", safe: true, package_pattern: "Microsoft.BingFinance".to_string(), is_installed: false },
-        BloatwareApp { id: "bing-search", name: "Bing Search", description: "Web Search from Microsoft Bing", safe: true, package_pattern: "Microsoft.BingSearch".to_string(), is_installed: false },
-        BloatwareApp { id: "3d-builder", name: "3D Builder", description: "Basic 3D modeling software", safe: true, package_pattern: "Microsoft.3DBuilder".to_string(), is_installed: false },
-        BloatwareApp { id: "3d-viewer", name: "3D Viewer", description: "Viewer for 3D models", safe: true, package_pattern: "Microsoft.Microsoft3DViewer".to_string(), is_installed: false },
-        BloatwareApp { id: "print-3d", name: "Print 3D", description: "3D printing preparation software", safe: true, package_pattern: "Microsoft.Print3D".to_string(), is_installed: false },
-        BloatwareApp { id: "paint-3d", name: "Paint 3D", description: "Modern paint application with 3D features", safe: true, package_pattern: "Microsoft.MSPaint".to_string(), is_installed: false },
-        BloatwareApp { id: "office-hub", name: "Office Hub", description: "Hub to access Microsoft Office apps and documents", safe: true, package_pattern: "Microsoft.MicrosoftOfficeHub".to_string(), is_installed: false },
-        BloatwareApp { id: "office-sway", name: "Sway", description: "Presentation and storytelling app", safe: true, package_pattern: "Microsoft.Office.Sway".to_string(), is_installed: false },
-        BloatwareApp { id: "onenote", name: "OneNote (UWP)", description: "Digital note-taking app (UWP version)", safe: true, package_pattern: "Microsoft.Office.OneNote".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-solitaire", name: "Solitaire Collection", description: "Collection of solitaire card games", safe: true, package_pattern: "Microsoft.MicrosoftSolitaireCollection".to_string(), is_installed: false },
-        BloatwareApp { id: "sticky-notes", name: "Sticky Notes", description: "Digital sticky notes app (Deprecated)", safe: true, package_pattern: "Microsoft.MicrosoftStickyNotes".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-todo", name: "Microsoft To Do", description: "To-do list and task management app", safe: true, package_pattern: "Microsoft.Todos".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-journal", name: "Microsoft Journal", description: "Digital note-taking app optimized for pen input", safe: true, package_pattern: "Microsoft.MicrosoftJournal".to_string(), is_installed: false },
-        BloatwareApp { id: "power-automate", name: "Power Automate", description: "Desktop automation tool (RPA)", safe: true, package_pattern: "Microsoft.PowerAutomateDesktop".to_string(), is_installed: false },
-        BloatwareApp { id: "power-bi", name: "Power BI", description: "Business analytics service client", safe: true, package_pattern: "Microsoft.MicrosoftPowerBIForWindows".to_string(), is_installed: false },
-        BloatwareApp { id: "dev-home", name: "Dev Home", description: "Developer dashboard and tool configuration (Discontinued)", safe: true, package_pattern: "Microsoft.Windows.DevHome".to_string(), is_installed: false },
-        BloatwareApp { id: "mixed-reality-portal", name: "Mixed Reality Portal", description: "Portal for Windows Mixed Reality headsets", safe: true, package_pattern: "Microsoft.MixedReality.Portal".to_string(), is_installed: false },
-        BloatwareApp { id: "network-speed-test", name: "Network Speed Test", description: "Internet connection speed test utility", safe: true, package_pattern: "Microsoft.NetworkSpeedTest".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-news", name: "Microsoft News", description: "News aggregator (now part of Microsoft Start)", safe: true, package_pattern: "Microsoft.News".to_string(), is_installed: false },
-        BloatwareApp { id: "messaging", name: "Messaging", description: "Messaging app, often integrates with Skype (Deprecated)", safe: true, package_pattern: "Microsoft.Messaging".to_string(), is_installed: false },
-        BloatwareApp { id: "skype-app", name: "Skype (UWP)", description: "Skype communication app, UWP version (Discontinued)", safe: true, package_pattern: "Microsoft.SkypeApp".to_string(), is_installed: false },
-        BloatwareApp { id: "oneconnect", name: "One Connect", description: "Mobile Operator management app (Replaced by Mobile Plans)", safe: true, package_pattern: "Microsoft.OneConnect".to_string(), is_installed: false },
-        BloatwareApp { id: "windows-maps", name: "Windows Maps", description: "Mapping and navigation app", safe: true, package_pattern: "Microsoft.WindowsMaps".to_string(), is_installed: false },
-        BloatwareApp { id: "windows-alarms", name: "Alarms & Clock", description: "Alarms & Clock app", safe: true, package_pattern: "Microsoft.WindowsAlarms".to_string(), is_installed: false },
-        BloatwareApp { id: "sound-recorder", name: "Sound Recorder", description: "Basic audio recording app", safe: true, package_pattern: "Microsoft.WindowsSoundRecorder".to_string(), is_installed: false },
-        BloatwareApp { id: "windows-feedback-hub", name: "Feedback Hub", description: "App for providing feedback to Microsoft on Windows", safe: true, package_pattern: "Microsoft.WindowsFeedbackHub".to_string(), is_installed: false },
-        BloatwareApp { id: "get-help", name: "Get Help", description: "Required for some Windows 11 Troubleshooters and support", safe: true, package_pattern: "Microsoft.GetHelp".to_string(), is_installed: false },
-        BloatwareApp { id: "get-started", name: "Get Started", description: "Tips and introductory guide for Windows", safe: true, package_pattern: "Microsoft.Getstarted".to_string(), is_installed: false },
-        BloatwareApp { id: "zune-music", name: "Media Player", description: "Modern Media Player (Replaced Groove Music)", safe: true, package_pattern: "Microsoft.ZuneMusic".to_string(), is_installed: false },
-        BloatwareApp { id: "zune-video", name: "Movies & TV", description: "Movies & TV app for video content", safe: true, package_pattern: "Microsoft.ZuneVideo".to_string(), is_installed: false },
-        BloatwareApp { id: "people-app", name: "People", description: "Contacts management app", safe: true, package_pattern: "Microsoft.People".to_string(), is_installed: false },
-        BloatwareApp { id: "mail-calendar", name: "Mail & Calendar", description: "Mail & Calendar app suite (Discontinued)", safe: true, package_pattern: "Microsoft.windowscommunicationsapps".to_string(), is_installed: false },
-        BloatwareApp { id: "outlook-new", name: "Outlook for Windows", description: "New Outlook for Windows mail client", safe: true, package_pattern: "Microsoft.OutlookForWindows".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-app", name: "Xbox Console Companion", description: "Old Xbox Console Companion App (Discontinued)", safe: true, package_pattern: "Microsoft.XboxApp".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-tcui", name: "Xbox TCUI Framework", description: "UI framework required for Microsoft Store and certain games", safe: false, package_pattern: "Microsoft.Xbox.TCUI".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-game-overlay", name: "Xbox Game Overlay", description: "Game overlay, part of Xbox Game Bar", safe: false, package_pattern: "Microsoft.XboxGameOverlay".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-gaming-overlay", name: "Xbox Gaming Overlay", description: "Game overlay, required/useful for some games", safe: false, package_pattern: "Microsoft.XboxGamingOverlay".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-identity", name: "Xbox Identity Provider", description: "Xbox sign-in framework, required for some games", safe: false, package_pattern: "Microsoft.XboxIdentityProvider".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-speech", name: "Xbox Speech To Text", description: "Accessibility feature required for some games", safe: false, package_pattern: "Microsoft.XboxSpeechToTextOverlay".to_string(), is_installed: false },
-        BloatwareApp { id: "xbox-gaming-app", name: "Xbox Gaming App", description: "Modern Xbox Gaming App, required for installing some PC games", safe: false, package_pattern: "Microsoft.GamingApp".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-teams-old", name: "Microsoft Teams (Old)", description: "Old Microsoft Teams personal (MS Store version)", safe: true, package_pattern: "MicrosoftTeams".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-teams-new", name: "Microsoft Teams (New)", description: "New Microsoft Teams app (Work/School or Personal)", safe: true, package_pattern: "MSTeams".to_string(), is_installed: false },
-        BloatwareApp { id: "family-safety", name: "Family Safety", description: "Family Safety App for managing family accounts", safe: true, package_pattern: "MicrosoftCorporationII.MicrosoftFamily".to_string(), is_installed: false },
-        BloatwareApp { id: "quick-assist", name: "Quick Assist", description: "Remote assistance tool", safe: true, package_pattern: "MicrosoftCorporationII.QuickAssist".to_string(), is_installed: false },
-        BloatwareApp { id: "cross-device", name: "Cross Device Experience", description: "Phone integration within File Explorer and more", safe: true, package_pattern: "MicrosoftWindows.CrossDevice".to_string(), is_installed: false },
-        BloatwareApp { id: "phone-link", name: "Phone Link", description: "Phone link (Connects Android/iOS phone to PC)", safe: true, package_pattern: "Microsoft.YourPhone".to_string(), is_installed: false },
-        BloatwareApp { id: "whiteboard", name: "Whiteboard", description: "Digital collaborative whiteboard app", safe: true, package_pattern: "Microsoft.Whiteboard".to_string(), is_installed: false },
-        BloatwareApp { id: "widgets-experience", name: "Widgets Experience", description: "This app powers Windows Widgets My Feed", safe: true, package_pattern: "Microsoft.StartExperiencesApp".to_string(), is_installed: false },
-        BloatwareApp { id: "m365-companions", name: "Microsoft 365 Companions", description: "Microsoft 365 Calendar, Files and People mini-apps", safe: true, package_pattern: "Microsoft.M365Companions".to_string(), is_installed: false },
-        BloatwareApp { id: "remote-desktop", name: "Remote Desktop", description: "Remote Desktop client app", safe: true, package_pattern: "Microsoft.RemoteDesktop".to_string(), is_installed: false },
-        BloatwareApp { id: "photos", name: "Photos", description: "Default photo viewing and basic editing app", safe: true, package_pattern: "Microsoft.Windows.Photos".to_string(), is_installed: false },
-        BloatwareApp { id: "paint", name: "Paint", description: "Classic Paint (Traditional 2D paint application)", safe: true, package_pattern: "Microsoft.Paint".to_string(), is_installed: false },
-        BloatwareApp { id: "notepad", name: "Notepad", description: "Notepad text editor app", safe: true, package_pattern: "Microsoft.WindowsNotepad".to_string(), is_installed: false },
-        BloatwareApp { id: "calculator", name: "Calculator", description: "Calculator app", safe: true, package_pattern: "Microsoft.WindowsCalculator".to_string(), is_installed: false },
-        BloatwareApp { id: "camera", name: "Camera", description: "Camera app for using built-in or connected cameras", safe: false, package_pattern: "Microsoft.WindowsCamera".to_string(), is_installed: false },
-        BloatwareApp { id: "snipping-tool", name: "Snipping Tool", description: "Screenshot and annotation tool", safe: true, package_pattern: "Microsoft.ScreenSketch".to_string(), is_installed: false },
-        BloatwareApp { id: "terminal", name: "Windows Terminal", description: "Default terminal app in Windows 11", safe: true, package_pattern: "Microsoft.WindowsTerminal".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-store", name: "Microsoft Store", description: "Microsoft Store - WARNING: Cannot be reinstalled easily!", safe: false, package_pattern: "Microsoft.WindowsStore".to_string(), is_installed: false },
-        BloatwareApp { id: "microsoft-edge", name: "Microsoft Edge", description: "Edge browser (Can only be uninstalled in EEA)", safe: false, package_pattern: "Microsoft.Edge".to_string(), is_installed: false },
-        BloatwareApp { id: "onedrive", name: "OneDrive", description: "OneDrive consumer cloud storage client", safe: false, package_pattern: "Microsoft.OneDrive".to_string(), is_installed: false },
-        BloatwareApp { id: "spotify", name: "Spotify", description: "Spotify music streaming app", safe: true, package_pattern: "SpotifyAB.SpotifyMusic".to_string(), is_installed: false },
-        BloatwareApp { id: "netflix", name: "Netflix", description: "Netflix streaming service app", safe: true, package_pattern: "4DF9E0F8.Netflix".to_string(), is_installed: false },
-        BloatwareApp { id: "prime-video", name: "Prime Video", description: "Amazon Prime Video streaming service app", safe: true, package_pattern: "AmazonVideo.PrimeVideo".to_string(), is_installed: false },
-        BloatwareApp { id: "hulu", name: "Hulu", description: "Hulu streaming service app", safe: true, package_pattern: "HULULLC.HULUPLUS".to_string(), is_installed: false },
-        BloatwareApp { id: "tiktok", name: "TikTok", description: "TikTok short-form video app", safe: true, package_pattern: "BytedancePte.Ltd.TikTok".to_string(), is_installed: false },
-        BloatwareApp { id: "instagram", name: "Instagram", description: "Instagram social media app", safe: true, package_pattern: "Facebook.Instagram".to_string(), is_installed: false },
-        BloatwareApp { id: "facebook", name: "Facebook", description: "Facebook social media app", safe: true, package_pattern: "Facebook.Facebook".to_string(), is_installed: false },
-        BloatwareApp { id: "twitter", name: "Twitter (X)", description: "Twitter (now X) social media app", safe: true, package_pattern: "9E2F88E3.Twitter".to_string(), is_installed: false },
-        BloatwareApp { id: "linkedin", name: "LinkedIn", description: "LinkedIn professional networking app", safe: true, package_pattern: "LinkedInforWindows".to_string(), is_installed: false },
-        BloatwareApp { id: "flipboard", name: "Flipboard", description: "News and social network aggregator styled as a magazine", safe: true, package_pattern: "Flipboard.Flipboard".to_string(), is_installed: false },
-        BloatwareApp { id: "candy-crush-saga", name: "Candy Crush Saga", description: "Puzzle game from King", safe: true, package_pattern: "king.com.CandyCrushSaga".to_string(), is_installed: false },
-        BloatwareApp { id: "candy-crush-soda", name: "Candy Crush Soda", description: "Puzzle game from King", safe: true, package_pattern: "king.com.CandyCrushSodaSaga".to_string(), is_installed: false },
-        BloatwareApp { id: "bubble-witch-3", name: "Bubble Witch 3", description: "Puzzle game from King", safe: true, package_pattern: "king.com.BubbleWitch3Saga".to_string(), is_installed: false },
-        BloatwareApp { id: "asphalt-8", name: "Asphalt 8", description: "Racing game", safe: true, package_pattern: "GAMELOFTSA.Asphalt8Airborne".to_string(), is_installed: false },
-        BloatwareApp { id: "cooking-fever", name: "Cooking Fever", description: "Restaurant simulation game", safe: true, package_pattern: "Nordcurrent.CookingFever".to_string(), is_installed: false },
-        BloatwareApp { id: "disney-magic-kingdoms", name: "Disney Magic Kingdoms", description: "Disney theme park building game", safe: true, package_pattern: "A278AB0D.DisneyMagicKingdoms".to_string(), is_installed: false },
-        BloatwareApp { id: "march-of-empires", name: "March of Empires", description: "Strategy game", safe: true, package_pattern: "Glu.MarchofEmpires".to_string(), is_installed: false },
-        BloatwareApp { id: "royal-revolt", name: "Royal Revolt", description: "Tower defense / strategy game", safe: true, package_pattern: "Microsoft.RoyalRevolt2".to_string(), is_installed: false },
-        BloatwareApp { id: "duolingo", name: "Duolingo", description: "Language learning app", safe: true, package_pattern: "DuoLLC.Duolingo-LearnLanguagesforFree".to_string(), is_installed: false },
-        BloatwareApp { id: "photoshop-express", name: "Adobe Photoshop Express", description: "Basic photo editing app from Adobe", safe: true, package_pattern: "AdobeSystemsIncorporated.AdobePhotoshopExpress".to_string(), is_installed: false },
-        BloatwareApp { id: "picsart", name: "PicsArt", description: "Photo editing and creative app", safe: true, package_pattern: "PicsArt.PicsArt-PhotoStudio".to_string(), is_installed: false },
-        BloatwareApp { id: "polarr", name: "Polarr Photo Editor", description: "Photo editing app (Academic Edition)", safe: true, package_pattern: "AcquiredOnline.PolarrPhotoEditorAcademicEdition".to_string(), is_installed: false },
-        BloatwareApp { id: "drawboard-pdf", name: "Drawboard PDF", description: "PDF viewing and annotation app", safe: true, package_pattern: "Drawboard.DrawboardPDF".to_string(), is_installed: false },
-        BloatwareApp { id: "cyberlink-media-suite", name: "CyberLink Media Suite", description: "Multimedia software suite (often preinstalled by OEMs)", safe: true, package_pattern: "CyberLinkCorp.ac.CyberLinkMediaSuiteEssentials".to_string(), is_installed: false },
-        BloatwareApp { id: "plex", name: "Plex", description: "Media server and player app", safe: true, package_pattern: "PlexInc.Plex".to_string(), is_installed: false },
-        BloatwareApp { id: "iheartradio", name: "iHeartRadio", description: "Internet radio streaming app", safe: true, package_pattern: "ClearChannel.iHeartRadio".to_string(), is_installed: false },
-        BloatwareApp { id: "tunein-radio", name: "TuneIn Radio", description: "Internet radio streaming app", safe: true, package_pattern: "TuneIn.TuneInRadio".to_string(), is_installed: false },
-        BloatwareApp { id: "pandora", name: "Pandora", description: "Pandora music streaming app", safe: true, package_pattern: "PandoraMediaInc.29680B314EFC2".to_string(), is_installed: false },
-        BloatwareApp { id: "shazam", name: "Shazam", description: "Music identification app", safe: true, package_pattern: "ShazamEntertainmentLtd.Shazam".to_string(), is_installed: false },
-        BloatwareApp { id: "amazon", name: "Amazon", description: "Amazon shopping app", safe: true, package_pattern: "Amazon.com.Amazon".to_string(), is_installed: false },
-        BloatwareApp { id: "wallet", name: "Wallet", description: "Wallet app", safe: true, package_pattern: "Microsoft.Wallet".to_string(), is_installed: false },
-        BloatwareApp { id: "winzip", name: "WinZip", description: "File compression utility (UWP version)", safe: true, package_pattern: "WinZipComputing.WinZipUniversal".to_string(), is_installed: false },
-        BloatwareApp { id: "fitbit", name: "Fitbit", description: "Fitbit activity tracker companion app", safe: true, package_pattern: "Fitbit.FitbitCoach".to_string(), is_installed: false },
-        BloatwareApp { id: "viber", name: "Viber", description: "Messaging and calling app", safe: true, package_pattern: "2414FC7A.Viber".to_string(), is_installed: false },
-        BloatwareApp { id: "sling-tv", name: "Sling TV", description: "Live TV streaming service app", safe: true, package_pattern: "SlingTVLLC.SlingTV".to_string(), is_installed: false },
-        BloatwareApp { id: "acg-media-player", name: "ACG Media Player", description: "Media player app", safe: true, package_pattern: "Acgeditor.ACGMediaPlayer".to_string(), is_installed: false },
-        BloatwareApp { id: "one-calendar", name: "One Calendar", description: "Calendar aggregation app", safe: true, package_pattern: "johnlangen.OneCalendar".to_string(), is_installed: false },
-        BloatwareApp { id: "phototastic", name: "Phototastic Collage", description: "Photo collage creation app", safe: true, package_pattern: "Xerysoft.PhototasticCollage".to_string(), is_installed: false },
-        BloatwareApp { id: "actipro", name: "Actipro Software", description: "UI controls or software components, often bundled by OEMs", safe: true, package_pattern: "ActiproSoftwareLLC".to_string(), is_installed: false },
-        BloatwareApp { id: "autodesk-sketchbook", name: "Autodesk SketchBook", description: "Digital drawing and sketching app", safe: true, package_pattern: "AutodeskInc.AutodeskSketchBook".to_string(), is_installed: false },
-        BloatwareApp { id: "caesars-slots", name: "Caesars Slots", description: "Casino slot machine game", safe: true, package_pattern: "Playtika.CaesarsSlotsFreeCasino".to_string(), is_installed: false },
-        BloatwareApp { id: "farmville-2", name: "FarmVille 2", description: "Farming simulation game", safe: true, package_pattern: "Zynga.FarmVille2CountryEscape".to_string(), is_installed: false },
-        BloatwareApp { id: "hidden-city", name: "Hidden City", description: "Hidden object puzzle adventure game", safe: true, package_pattern: "G5E.HiddenCityMysteryofShadows".to_string(), is_installed: false },
-        BloatwareApp { id: "nyt-crossword", name: "NYT Crossword", description: "New York Times crossword puzzle app", safe: true, package_pattern: "NYTimes.DailyCrossword".to_string(), is_installed: false },
-        BloatwareApp { id: "eclipse-manager", name: "Eclipse Manager", description: "OEM software or utilities", safe: true, package_pattern: "EclipseManager".to_string(), is_installed: false },
-        BloatwareApp { id: "live-wallpaper", name: "Live Wallpaper", description: "Live wallpaper app", safe: true, package_pattern: "Sidia.LiveWallpaper".to_string(), is_installed: false },
-        BloatwareApp { id: "wunderlist", name: "Wunderlist", description: "To-do list app (Acquired by Microsoft, moved to To Do)", safe: true, package_pattern: "6Wunderkinder.Wunderlist".to_string(), is_installed: false },
-        BloatwareApp { id: "xing", name: "XING", description: "Professional networking platform", safe: true, package_pattern: "XINGAG.XING".to_string(), is_installed: false },
-        BloatwareApp { id: "disney", name: "Disney", description: "General Disney content app", safe: true, package_pattern: "Disney.37853FC22B2CE".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-ai-center", name: "HP AI Experience Center", description: "HP OEM software, AI-enhanced features and support", safe: true, package_pattern: "AD2F1837.HPAIExperienceCenter".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-connected-music", name: "HP Connected Music", description: "HP OEM software for music", safe: true, package_pattern: "AD2F1837.HPConnectedMusic".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-connected-photo", name: "HP Connected Photo", description: "HP OEM software for photos", safe: true, package_pattern: "AD2F1837.HPConnectedPhotopoweredbySnapfish".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-desktop-support", name: "HP Desktop Support Utilities", description: "HP OEM software providing desktop support tools", safe: true, package_pattern: "AD2F1837.HPDesktopSupportUtilities".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-easy-clean", name: "HP Easy Clean", description: "HP OEM software for system cleaning", safe: true, package_pattern: "AD2F1837.HPEasyClean".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-file-viewer", name: "HP File Viewer", description: "HP OEM software for viewing files", safe: true, package_pattern: "AD2F1837.HPFileViewer".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-jumpstart", name: "HP JumpStarts", description: "HP OEM software for tutorials and quick access", safe: true, package_pattern: "AD2F1837.HPJumpStarts".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-pc-diagnostics", name: "HP PC Hardware Diagnostics", description: "HP OEM software for PC hardware diagnostics", safe: true, package_pattern: "AD2F1837.HPPCHardwareDiagnosticsWindows".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-power-manager", name: "HP Power Manager", description: "HP OEM software for managing power settings", safe: true, package_pattern: "AD2F1837.HPPowerManager".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-printer-control", name: "HP Printer Control", description: "HP OEM software for managing HP printers", safe: true, package_pattern: "AD2F1837.HPPrinterControl".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-privacy-settings", name: "HP Privacy Settings", description: "HP OEM software for managing privacy settings", safe: true, package_pattern: "AD2F1837.HPPrivacySettings".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-quickdrop", name: "HP QuickDrop", description: "HP OEM software for quick file transfer", safe: true, package_pattern: "AD2F1837.HPQuickDrop".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-quicktouch", name: "HP QuickTouch", description: "HP OEM software for touch-specific shortcuts", safe: true, package_pattern: "AD2F1837.HPQuickTouch".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-registration", name: "HP Registration", description: "HP OEM software for product registration", safe: true, package_pattern: "AD2F1837.HPRegistration".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-support-assistant", name: "HP Support Assistant", description: "HP OEM software for support and troubleshooting", safe: true, package_pattern: "AD2F1837.HPSupportAssistant".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-sure-shield", name: "HP Sure Shield AI", description: "HP OEM security software, AI-based threat protection", safe: true, package_pattern: "AD2F1837.HPSureShieldAI".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-system-info", name: "HP System Information", description: "HP OEM software for displaying system information", safe: true, package_pattern: "AD2F1837.HPSystemInformation".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-welcome", name: "HP Welcome", description: "HP OEM software providing welcome experience", safe: true, package_pattern: "AD2F1837.HPWelcome".to_string(), is_installed: false },
-        BloatwareApp { id: "hp-workwell", name: "HP WorkWell", description: "HP OEM software focused on well-being", safe: true, package_pattern: "AD2F1837.HPWorkWell".to_string(), is_installed: false },
-        BloatwareApp { id: "myhp", name: "myHP", description: "HP OEM central hub app for device info and services", safe: true, package_pattern: "AD2F1837.myHP".to_string(), is_installed: false },
+        bloatware("clipchamp", "Clipchamp", "Video editor from Microsoft", Low, "Clipchamp.Clipchamp").store("9P1J8S7CCWWT"),
+        bloatware("cortana", "Cortana", "Microsoft Cortana voice assistant (Discontinued)", Low, "Microsoft.549981C3F5F10").tagged(&[Privacy]),
+        bloatware("copilot", "Microsoft Copilot", "AI assistant integrated into Windows", Low, "Microsoft.Copilot").store("9NHT9RB2F4HD").tagged(&[Privacy]),
+        bloatware("bing-weather", "Weather App", "Weather forecast via Bing", Low, "Microsoft.BingWeather").store("9WZDNCRFJ3Q2"),
+        bloatware("bing-news", "News App", "News aggregator via Bing", Low, "Microsoft.BingNews").store("9WZDNCRFHVFW"),
+        bloatware("bing-sports", "Bing Sports", "Sports news and scores via Bing (Discontinued)", Low, "Microsoft.BingSports"),
+        bloatware("bing-finance", "Bing Finance", "Finance news and tracking via Bing (Discontinued)", Low, "Microsoft.BingFinance"),
+        bloatware("bing-search", "Bing Search", "Web Search from Microsoft Bing", Low, "Microsoft.BingSearch").tagged(&[Privacy]),
+        bloatware("3d-builder", "3D Builder", "Basic 3D modeling software", Low, "Microsoft.3DBuilder"),
+        bloatware("3d-viewer", "3D Viewer", "Viewer for 3D models", Low, "Microsoft.Microsoft3DViewer"),
+        bloatware("print-3d", "Print 3D", "3D printing preparation software", Low, "Microsoft.Print3D"),
+        bloatware("paint-3d", "Paint 3D", "Modern paint application with 3D features", Low, "Microsoft.MSPaint"),
+        bloatware("office-hub", "Office Hub", "Hub to access Microsoft Office apps and documents", Low, "Microsoft.MicrosoftOfficeHub").tagged(&[Enterprise]),
+        bloatware("office-sway", "Sway", "Presentation and storytelling app", Low, "Microsoft.Office.Sway"),
+        bloatware("onenote", "OneNote (UWP)", "Digital note-taking app (UWP version)", Low, "Microsoft.Office.OneNote").store("9WZDNCRFHVJL").tagged(&[Enterprise]),
+        bloatware("microsoft-solitaire", "Solitaire Collection", "Collection of solitaire card games", Low, "Microsoft.MicrosoftSolitaireCollection").store("9WZDNCRFHWD2").tagged(&[Gaming]),
+        bloatware("sticky-notes", "Sticky Notes", "Digital sticky notes app (Deprecated)", Low, "Microsoft.MicrosoftStickyNotes").store("9NBLGGH4QGHW"),
+        bloatware("microsoft-todo", "Microsoft To Do", "To-do list and task management app", Low, "Microsoft.Todos").store("9NBLGGH5R558"),
+        bloatware("microsoft-journal", "Microsoft Journal", "Digital note-taking app optimized for pen input", Low, "Microsoft.MicrosoftJournal"),
+        bloatware("power-automate", "Power Automate", "Desktop automation tool (RPA)", Low, "Microsoft.PowerAutomateDesktop").store("9NFTCH6J7FHV").tagged(&[Enterprise]),
+        bloatware("power-bi", "Power BI", "Business analytics service client", Low, "Microsoft.MicrosoftPowerBIForWindows").tagged(&[Enterprise]),
+        bloatware("dev-home", "Dev Home", "Developer dashboard and tool configuration (Discontinued)", Low, "Microsoft.Windows.DevHome"),
+        bloatware("mixed-reality-portal", "Mixed Reality Portal", "Portal for Windows Mixed Reality headsets", Low, "Microsoft.MixedReality.Portal"),
+        bloatware("network-speed-test", "Network Speed Test", "Internet connection speed test utility", Low, "Microsoft.NetworkSpeedTest"),
+        bloatware("microsoft-news", "Microsoft News", "News aggregator (now part of Microsoft Start)", Low, "Microsoft.News"),
+        bloatware("messaging", "Messaging", "Messaging app, often integrates with Skype (Deprecated)", Low, "Microsoft.Messaging"),
+        bloatware("skype-app", "Skype (UWP)", "Skype communication app, UWP version (Discontinued)", Low, "Microsoft.SkypeApp"),
+        bloatware("oneconnect", "One Connect", "Mobile Operator management app (Replaced by Mobile Plans)", Low, "Microsoft.OneConnect"),
+        bloatware("windows-maps", "Windows Maps", "Mapping and navigation app", Low, "Microsoft.WindowsMaps").store("9WZDNCRDTBVB"),
+        bloatware("windows-alarms", "Alarms & Clock", "Alarms & Clock app", Low, "Microsoft.WindowsAlarms").store("9WZDNCRFJ3PR"),
+        bloatware("sound-recorder", "Sound Recorder", "Basic audio recording app", Low, "Microsoft.WindowsSoundRecorder").store("9WZDNCRFHWKN"),
+        bloatware("windows-feedback-hub", "Feedback Hub", "App for providing feedback to Microsoft on Windows", Low, "Microsoft.WindowsFeedbackHub").store("9NBLGGH4R32N"),
+        bloatware("get-help", "Get Help", "Required for some Windows 11 Troubleshooters and support", Low, "Microsoft.GetHelp").store("9PKDZBMV1H3T"),
+        bloatware("get-started", "Get Started", "Tips and introductory guide for Windows", Low, "Microsoft.Getstarted"),
+        bloatware("zune-music", "Media Player", "Modern Media Player (Replaced Groove Music)", Low, "Microsoft.ZuneMusic").store("9WZDNCRFJ3PT"),
+        bloatware("zune-video", "Movies & TV", "Movies & TV app for video content", Low, "Microsoft.ZuneVideo").store("9WZDNCRFJ3P2"),
+        bloatware("people-app", "People", "Contacts management app", Low, "Microsoft.People").tagged(&[Privacy]),
+        bloatware("mail-calendar", "Mail & Calendar", "Mail & Calendar app suite (Discontinued)", Low, "Microsoft.windowscommunicationsapps"),
+        bloatware("outlook-new", "Outlook for Windows", "New Outlook for Windows mail client", Low, "Microsoft.OutlookForWindows").store("9NRX63209R7B").tagged(&[Enterprise]),
+        bloatware("xbox-app", "Xbox Console Companion", "Old Xbox Console Companion App (Discontinued)", Low, "Microsoft.XboxApp").tagged(&[Gaming]),
+        bloatware("xbox-tcui", "Xbox TCUI Framework", "UI framework required for Microsoft Store and certain games", Medium, "Microsoft.Xbox.TCUI").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("xbox-game-overlay", "Xbox Game Overlay", "Game overlay, part of Xbox Game Bar", Medium, "Microsoft.XboxGameOverlay").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("xbox-gaming-overlay", "Xbox Gaming Overlay", "Game overlay, required/useful for some games", Medium, "Microsoft.XboxGamingOverlay").store("9NZKPSTSNW4P").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("xbox-identity", "Xbox Identity Provider", "Xbox sign-in framework, required for some games", Medium, "Microsoft.XboxIdentityProvider").store("9WZDNCRD1HKW").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("xbox-speech", "Xbox Speech To Text", "Accessibility feature required for some games", Medium, "Microsoft.XboxSpeechToTextOverlay").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("xbox-gaming-app", "Xbox Gaming App", "Modern Xbox Gaming App, required for installing some PC games", Medium, "Microsoft.GamingApp").store("9MV0B5HZVK9Z").because(&[BreaksGaming]).tagged(&[Gaming]),
+        bloatware("microsoft-teams-old", "Microsoft Teams (Old)", "Old Microsoft Teams personal (MS Store version)", Low, "MicrosoftTeams").tagged(&[Enterprise]),
+        bloatware("microsoft-teams-new", "Microsoft Teams (New)", "New Microsoft Teams app (Work/School or Personal)", Low, "MSTeams").winget("Microsoft.Teams").tagged(&[Enterprise]),
+        bloatware("family-safety", "Family Safety", "Family Safety App for managing family accounts", Low, "MicrosoftCorporationII.MicrosoftFamily").tagged(&[Privacy]),
+        bloatware("quick-assist", "Quick Assist", "Remote assistance tool", Low, "MicrosoftCorporationII.QuickAssist").store("9P7BP5VNWKX5").tagged(&[Enterprise]),
+        bloatware("cross-device", "Cross Device Experience", "Phone integration within File Explorer and more", Low, "MicrosoftWindows.CrossDevice").tagged(&[Privacy]),
+        bloatware("phone-link", "Phone Link", "Phone link (Connects Android/iOS phone to PC)", Low, "Microsoft.YourPhone").store("9NMPJ99VJBWV").tagged(&[Privacy]),
+        bloatware("whiteboard", "Whiteboard", "Digital collaborative whiteboard app", Low, "Microsoft.Whiteboard").store("9MSPC6MP8FM4").tagged(&[Enterprise]),
+        bloatware("widgets-experience", "Widgets Experience", "This app powers Windows Widgets My Feed", Low, "Microsoft.StartExperiencesApp").tagged(&[Privacy, Performance]),
+        bloatware("m365-companions", "Microsoft 365 Companions", "Microsoft 365 Calendar, Files and People mini-apps", Low, "Microsoft.M365Companions").tagged(&[Enterprise]),
+        bloatware("remote-desktop", "Remote Desktop", "Remote Desktop client app", Low, "Microsoft.RemoteDesktop").store("9WZDNCRFJ3PS").tagged(&[Enterprise]),
+        bloatware("photos", "Photos", "Default photo viewing and basic editing app", Low, "Microsoft.Windows.Photos").store("9WZDNCRFJBH4"),
+        bloatware("paint", "Paint", "Classic Paint (Traditional 2D paint application)", Low, "Microsoft.Paint").store("9PCFS5B6T72H"),
+        bloatware("notepad", "Notepad", "Notepad text editor app", Low, "Microsoft.WindowsNotepad").store("9MSMLRH6LZF3"),
+        bloatware("calculator", "Calculator", "Calculator app", Low, "Microsoft.WindowsCalculator").store("9WZDNCRFHVN5"),
+        bloatware("camera", "Camera", "Camera app for using built-in or connected cameras", Medium, "Microsoft.WindowsCamera").store("9WZDNCRFJBBG").because(&[BreaksDevices]),
+        bloatware("snipping-tool", "Snipping Tool", "Screenshot and annotation tool", Low, "Microsoft.ScreenSketch").store("9MZ95KL8MR0L"),
+        bloatware("terminal", "Windows Terminal", "Default terminal app in Windows 11", Low, "Microsoft.WindowsTerminal").store("9N0DX20HK701").winget("Microsoft.WindowsTerminal"),
+        bloatware("microsoft-store", "Microsoft Store", "Microsoft Store - WARNING: Cannot be reinstalled easily!", Critical, "Microsoft.WindowsStore").because(&[BreaksStoreUpdates, HardToReinstall]),
+        bloatware("microsoft-edge", "Microsoft Edge", "Edge browser (Can only be uninstalled in EEA)", High, "Microsoft.Edge").because(&[HardToReinstall]),
+        bloatware("onedrive", "OneDrive", "OneDrive consumer cloud storage client", Medium, "Microsoft.OneDrive").because(&[BreaksSync]),
+        bloatware("spotify", "Spotify", "Spotify music streaming app", Low, "SpotifyAB.SpotifyMusic").store("9NCBCSZSJRSB"),
+        bloatware("netflix", "Netflix", "Netflix streaming service app", Low, "4DF9E0F8.Netflix").store("9WZDNCRFJ3TJ"),
+        bloatware("prime-video", "Prime Video", "Amazon Prime Video streaming service app", Low, "AmazonVideo.PrimeVideo"),
+        bloatware("hulu", "Hulu", "Hulu streaming service app", Low, "HULULLC.HULUPLUS"),
+        bloatware("tiktok", "TikTok", "TikTok short-form video app", Low, "BytedancePte.Ltd.TikTok").tagged(&[Privacy]),
+        bloatware("instagram", "Instagram", "Instagram social media app", Low, "Facebook.Instagram").tagged(&[Privacy]),
+        bloatware("facebook", "Facebook", "Facebook social media app", Low, "Facebook.Facebook").tagged(&[Privacy]),
+        bloatware("twitter", "Twitter (X)", "Twitter (now X) social media app", Low, "9E2F88E3.Twitter"),
+        bloatware("linkedin", "LinkedIn", "LinkedIn professional networking app", Low, "*.LinkedInforWindows"),
+        bloatware("flipboard", "Flipboard", "News and social network aggregator styled as a magazine", Low, "Flipboard.Flipboard"),
+        bloatware("candy-crush-saga", "Candy Crush Saga", "Puzzle game from King", Low, "king.com.CandyCrushSaga").tagged(&[Gaming]),
+        bloatware("candy-crush-soda", "Candy Crush Soda", "Puzzle game from King", Low, "king.com.CandyCrushSodaSaga").tagged(&[Gaming]),
+        bloatware("bubble-witch-3", "Bubble Witch 3", "Puzzle game from King", Low, "king.com.BubbleWitch3Saga").tagged(&[Gaming]),
+        bloatware("asphalt-8", "Asphalt 8", "Racing game", Low, "GAMELOFTSA.Asphalt8Airborne").tagged(&[Gaming]),
+        bloatware("cooking-fever", "Cooking Fever", "Restaurant simulation game", Low, "Nordcurrent.CookingFever").tagged(&[Gaming]),
+        bloatware("disney-magic-kingdoms", "Disney Magic Kingdoms", "Disney theme park building game", Low, "A278AB0D.DisneyMagicKingdoms").tagged(&[Gaming]),
+        bloatware("march-of-empires", "March of Empires", "Strategy game", Low, "Glu.MarchofEmpires").tagged(&[Gaming]),
+        bloatware("royal-revolt", "Royal Revolt", "Tower defense / strategy game", Low, "Microsoft.RoyalRevolt2").tagged(&[Gaming]),
+        bloatware("duolingo", "Duolingo", "Language learning app", Low, "DuoLLC.Duolingo-LearnLanguagesforFree"),
+        bloatware("photoshop-express", "Adobe Photoshop Express", "Basic photo editing app from Adobe", Low, "AdobeSystemsIncorporated.AdobePhotoshopExpress"),
+        bloatware("picsart", "PicsArt", "Photo editing and creative app", Low, "PicsArt.PicsArt-PhotoStudio"),
+        bloatware("polarr", "Polarr Photo Editor", "Photo editing app (Academic Edition)", Low, "AcquiredOnline.PolarrPhotoEditorAcademicEdition"),
+        bloatware("drawboard-pdf", "Drawboard PDF", "PDF viewing and annotation app", Low, "Drawboard.DrawboardPDF"),
+        bloatware("cyberlink-media-suite", "CyberLink Media Suite", "Multimedia software suite (often preinstalled by OEMs)", Low, "CyberLinkCorp.ac.CyberLinkMediaSuiteEssentials"),
+        bloatware("plex", "Plex", "Media server and player app", Low, "PlexInc.Plex"),
+        bloatware("iheartradio", "iHeartRadio", "Internet radio streaming app", Low, "ClearChannel.iHeartRadio"),
+        bloatware("tunein-radio", "TuneIn Radio", "Internet radio streaming app", Low, "TuneIn.TuneInRadio"),
+        bloatware("pandora", "Pandora", "Pandora music streaming app", Low, "PandoraMediaInc.29680B314EFC2"),
+        bloatware("shazam", "Shazam", "Music identification app", Low, "ShazamEntertainmentLtd.Shazam"),
+        bloatware("amazon", "Amazon", "Amazon shopping app", Low, "Amazon.com.Amazon"),
+        bloatware("wallet", "Wallet", "Wallet app", Low, "Microsoft.Wallet"),
+        bloatware("winzip", "WinZip", "File compression utility (UWP version)", Low, "WinZipComputing.WinZipUniversal"),
+        bloatware("fitbit", "Fitbit", "Fitbit activity tracker companion app", Low, "Fitbit.FitbitCoach"),
+        bloatware("viber", "Viber", "Messaging and calling app", Low, "2414FC7A.Viber"),
+        bloatware("sling-tv", "Sling TV", "Live TV streaming service app", Low, "SlingTVLLC.SlingTV"),
+        bloatware("acg-media-player", "ACG Media Player", "Media player app", Low, "Acgeditor.ACGMediaPlayer"),
+        bloatware("one-calendar", "One Calendar", "Calendar aggregation app", Low, "johnlangen.OneCalendar"),
+        bloatware("phototastic", "Phototastic Collage", "Photo collage creation app", Low, "Xerysoft.PhototasticCollage"),
+        bloatware("actipro", "Actipro Software", "UI controls or software components, often bundled by OEMs", Low, "ActiproSoftwareLLC.*"),
+        bloatware("autodesk-sketchbook", "Autodesk SketchBook", "Digital drawing and sketching app", Low, "AutodeskInc.AutodeskSketchBook"),
+        bloatware("caesars-slots", "Caesars Slots", "Casino slot machine game", Low, "Playtika.CaesarsSlotsFreeCasino").tagged(&[Gaming]),
+        bloatware("farmville-2", "FarmVille 2", "Farming simulation game", Low, "Zynga.FarmVille2CountryEscape").tagged(&[Gaming]),
+        bloatware("hidden-city", "Hidden City", "Hidden object puzzle adventure game", Low, "G5E.HiddenCityMysteryofShadows").tagged(&[Gaming]),
+        bloatware("nyt-crossword", "NYT Crossword", "New York Times crossword puzzle app", Low, "NYTimes.DailyCrossword"),
+        bloatware("eclipse-manager", "Eclipse Manager", "OEM software or utilities", Low, "*EclipseManager").tagged(&[Laptop]),
+        bloatware("live-wallpaper", "Live Wallpaper", "Live wallpaper app", Low, "Sidia.LiveWallpaper"),
+        bloatware("wunderlist", "Wunderlist", "To-do list app (Acquired by Microsoft, moved to To Do)", Low, "6Wunderkinder.Wunderlist"),
+        bloatware("xing", "XING", "Professional networking platform", Low, "XINGAG.XING"),
+        bloatware("disney", "Disney", "General Disney content app", Low, "Disney.37853FC22B2CE"),
+        bloatware("hp-ai-center", "HP AI Experience Center", "HP OEM software, AI-enhanced features and support", Low, "AD2F1837.HPAIExperienceCenter").tagged(&[Laptop]),
+        bloatware("hp-connected-music", "HP Connected Music", "HP OEM software for music", Low, "AD2F1837.HPConnectedMusic").tagged(&[Laptop]),
+        bloatware("hp-connected-photo", "HP Connected Photo", "HP OEM software for photos", Low, "AD2F1837.HPConnectedPhotopoweredbySnapfish").tagged(&[Laptop]),
+        bloatware("hp-desktop-support", "HP Desktop Support Utilities", "HP OEM software providing desktop support tools", Low, "AD2F1837.HPDesktopSupportUtilities").tagged(&[Laptop]),
+        bloatware("hp-easy-clean", "HP Easy Clean", "HP OEM software for system cleaning", Low, "AD2F1837.HPEasyClean").tagged(&[Laptop]),
+        bloatware("hp-file-viewer", "HP File Viewer", "HP OEM software for viewing files", Low, "AD2F1837.HPFileViewer").tagged(&[Laptop]),
+        bloatware("hp-jumpstart", "HP JumpStarts", "HP OEM software for tutorials and quick access", Low, "AD2F1837.HPJumpStarts").tagged(&[Laptop]),
+        bloatware("hp-pc-diagnostics", "HP PC Hardware Diagnostics", "HP OEM software for PC hardware diagnostics", Low, "AD2F1837.HPPCHardwareDiagnosticsWindows").tagged(&[Laptop]),
+        bloatware("hp-power-manager", "HP Power Manager", "HP OEM software for managing power settings", Low, "AD2F1837.HPPowerManager").tagged(&[Laptop]),
+        bloatware("hp-printer-control", "HP Printer Control", "HP OEM software for managing HP printers", Low, "AD2F1837.HPPrinterControl").tagged(&[Laptop]),
+        bloatware("hp-privacy-settings", "HP Privacy Settings", "HP OEM software for managing privacy settings", Low, "AD2F1837.HPPrivacySettings").tagged(&[Privacy, Laptop]),
+        bloatware("hp-quickdrop", "HP QuickDrop", "HP OEM software for quick file transfer", Low, "AD2F1837.HPQuickDrop").tagged(&[Laptop]),
+        bloatware("hp-quicktouch", "HP QuickTouch", "HP OEM software for touch-specific shortcuts", Low, "AD2F1837.HPQuickTouch").tagged(&[Laptop]),
+        bloatware("hp-registration", "HP Registration", "HP OEM software for product registration", Low, "AD2F1837.HPRegistration").tagged(&[Laptop]),
+        bloatware("hp-support-assistant", "HP Support Assistant", "HP OEM software for support and troubleshooting", Low, "AD2F1837.HPSupportAssistant").tagged(&[Laptop]),
+        bloatware("hp-sure-shield", "HP Sure Shield AI", "HP OEM security software, AI-based threat protection", Low, "AD2F1837.HPSureShieldAI").tagged(&[Security, Laptop]),
+        bloatware("hp-system-info", "HP System Information", "HP OEM software for displaying system information", Low, "AD2F1837.HPSystemInformation").tagged(&[Laptop]),
+        bloatware("hp-welcome", "HP Welcome", "HP OEM software providing welcome experience", Low, "AD2F1837.HPWelcome").tagged(&[Laptop]),
+        bloatware("hp-workwell", "HP WorkWell", "HP OEM software focused on well-being", Low, "AD2F1837.HPWorkWell").tagged(&[Laptop]),
+        bloatware("myhp", "myHP", "HP OEM central hub app for device info and services", Low, "AD2F1837.myHP").tagged(&[Laptop]),
     ]
 }
 
+/// Categories whose data file is a list of [`DebloatItem`]s.
+const ITEM_CATEGORIES: &[&str] = &[
+    "privacy",
+    "services",
+    "registry",
+    "updates",
+    "system",
+    "edge",
+    "tasks",
+    "network",
+    "features",
+    "contextmenu",
+    "ai",
+];
+
 fn get_resource_path(app: tauri::AppHandle) -> Result<PathBuf, String> {
     let resource_path = app.path().resource_dir()
         .map_err(|e| format!("Failed to get resource directory: {}", e))?;
     Ok(resource_path.join("data"))
 }
 
-#[tauri::command]
-fn load_items(category: String, app: tauri::AppHandle) -> Result<Vec<DebloatItem>, String> {
-    let data_path = get_resource_path(app)?;
-    let file_path = data_path.join(format!("{}.json", category));
-    
-    if !file_path.exists() {
-        return Err(format!("File not found: {}", file_path.to_string_lossy()));
+fn get_app_data_file(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(data_dir.join(file_name))
+}
+
+fn get_custom_apps_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "custom_apps.json")
+}
+
+fn get_reinstall_records_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "reinstall.json")
+}
+
+fn get_policy_records_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "policies.json")
+}
+
+fn get_task_records_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "scheduled_tasks.json")
+}
+
+fn get_context_menu_records_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "context_menu.json")
+}
+
+fn get_pending_effects_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "pending_effects.json")
+}
+
+fn get_risk_policy_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    get_app_data_file(app, "risk_policy.json")
+}
+
+fn get_locales_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(get_resource_path(app.clone())?.join("locales"))
+}
+
+/// Translations for `locale`; English, the catalog's own language, when none
+/// is given.
+fn string_table(app: &tauri::AppHandle, locale: Option<&str>) -> Result<locale::StringTable, String> {
+    match locale {
+        Some(locale) => locale::load_table(&get_locales_path(app)?, locale),
+        None => Ok(locale::StringTable::default()),
     }
+}
+
+fn load_winget_catalog(app: &tauri::AppHandle) -> Result<Vec<winget::WingetCatalogEntry>, String> {
+    let file_path = get_resource_path(app.clone())?.join("winget.json");
     
     let contents = fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+        .map_err(|e| format!("Failed to read winget catalog: {}", e))?;
     
-    let items: Vec<DebloatItem> = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse winget catalog: {}", e))
+}
+
+/// Built-in Appx catalog, then the winget catalog, then the apps the user
+/// promoted from discovery.
+fn get_app_catalog(app: &tauri::AppHandle) -> Result<Vec<BloatwareApp>, String> {
+    let mut catalog = get_bloatware_definitions();
+    catalog.extend(load_winget_catalog(app)?.iter().map(winget_app));
     
-    Ok(items)
+    let custom_apps: Vec<discovery::CustomApp> = json_store::load(&get_custom_apps_path(app)?, "custom apps")?;
+    catalog.extend(custom_apps.iter().map(|custom| {
+        BloatwareApp {
+            risk: custom.risk.clone(),
+            ..bloatware(&custom.id, &custom.name, &custom.description, custom.risk.level, &custom.package_pattern)
+        }
+    }));
+    
+    Ok(catalog)
+}
+
+fn save_reinstall_record(app: &tauri::AppHandle, id: String, record: reinstall::ReinstallRecord) -> Result<(), String> {
+    let path = get_reinstall_records_path(app)?;
+    let mut records: BTreeMap<String, reinstall::ReinstallRecord> = json_store::load(&path, "reinstall records")?;
+    records.insert(id, record);
+    json_store::save(&path, &records, "reinstall records")
 }
 
 #[tauri::command]
-fn load_presets(app: tauri::AppHandle) -> Result<Vec<Preset>, String> {
+fn load_items(category: String, locale: Option<String>, app: tauri::AppHandle) -> Result<Vec<DebloatItem>, String> {
+    let platform = current_platform(&app);
+    let strings = string_table(&app, locale.as_deref())?;
     let data_path = get_resource_path(app)?;
-    let file_path = data_path.join("presets.json");
+    let file_path = data_path.join(format!("{}.json", category));
     
     if !file_path.exists() {
-        return Err(format!("Presets file not found: {}", file_path.to_string_lossy()));
+        return Err(format!("File not found: {}", file_path.to_string_lossy()));
     }
     
     let contents = fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read presets file: {}", e))?;
+        .map_err(|e| format!("Failed to read file: {}", e))?;
     
-    let data: PresetsData = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse presets JSON: {}", e))?;
+    let mut items: Vec<DebloatItem> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
     
-    Ok(data.presets)
-}
-
-#[tauri::command]
-async fn get_installed_package_names() -> Result<Vec<String>, String> {
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        
-        let ps_script = "Get-AppxPackage | Select-Object -ExpandProperty Name";
-        
-        let output = Command::new("powershell")
-            .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-WindowStyle", "Hidden", "-Command", ps_script])
-            .creation_flags(0x08000000)
-            .output();
-        
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let packages: Vec<String> = stdout
-                    .lines()
-                    .map(|s| s.trim().to_lowercase())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                Ok(packages)
-            }
-            Err(e) => Err(format!("Failed to get installed packages: {}", e))
+    for item in &mut items {
+        if !item.policies.is_empty() {
+            policy::validate(&item.policies)?;
+            item.command = policy::apply_script(&item.policies);
+        }
+        if !item.tasks.is_empty() {
+            item.command = tasks::disable_script(&item.task_patterns()?);
         }
+        if !item.firewall_rules.is_empty() {
+            firewall::validate(&item.firewall_rules)?;
+            item.command = firewall::apply_script(&item.firewall_rules);
+            item.rollback_command = Some(firewall::rollback_script(&item.firewall_rules));
+        }
+        if !item.features.is_empty() {
+            features::validate(&item.features)?;
+            item.command = features::apply_script(&item.features);
+            item.rollback_command = Some(features::rollback_script(&item.features));
+        }
+        if let Some(change) = &item.context_menu {
+            change.validate()?;
+            item.command = change.apply_script();
+        }
+        for host in &item.hosts {
+            hosts::validate_hostname(host)?;
+        }
+        item.requires_admin = elevation::requires_admin(&item.command)
+            || item.steps.iter().any(|step| elevation::requires_admin(&step.command))
+            || !item.firewall_rules.is_empty()
+            || !item.hosts.is_empty();
     }
     
-    #[cfg(not(target_os = "windows"))]
-    {
-        Ok(Vec::new())
+    for item in &mut items {
+        item.applicability = applicability::evaluate(&item.requirements, &platform);
+        strings.localize(locale::Section::Items, &item.id, &mut item.name, &mut item.description);
     }
+    
+    Ok(items)
 }
 
+/// Current state of the items in `category` that can be detected: policy
+/// items from their registry values, task items from their scheduled tasks,
+/// hosts and firewall items from the managed block and rule group, feature
+/// items from DISM (only when elevated, as DISM refuses otherwise), context
+/// menu items from the Blocked lists and the classic menu registration.
 #[tauri::command]
-async fn get_all_bloatware_with_status() -> Result<Vec<BloatwareApp>, String> {
-    let mut bloatware = get_bloatware_definitions();
+async fn get_item_status(category: String, app: tauri::AppHandle) -> Result<Vec<status::ItemStatus>, String> {
+    let items: Vec<DebloatItem> = load_items(category, None, app)?
+        .into_iter()
+        .filter(|item| {
+            let detectable = !item.policies.is_empty()
+                || !item.tasks.is_empty()
+                || !item.hosts.is_empty()
+                || !item.firewall_rules.is_empty()
+                || !item.features.is_empty()
+                || item.context_menu.is_some();
+            detectable && item.applicability.applicable
+        })
+        .collect();
     
-    #[cfg(target_os = "windows")]
-    {
-        let installed_packages = get_installed_package_names().await?;
-        
-        for app in &mut bloatware {
-            let pattern_lower = app.package_pattern.to_lowercase();
-            app.is_installed = installed_packages.iter().any(|pkg| {
-                pkg.contains(&pattern_lower) || pattern_lower.contains(pkg)
-            });
-        }
+    if items.is_empty() {
+        return Ok(Vec::new());
     }
     
-    Ok(bloatware)
-}
-
-#[tauri::command]
-async fn remove_app(package_pattern: String) -> CommandResult {
     #[cfg(target_os = "windows")]
     {
-        use std::process::Command;
+        let mut statuses = Vec::new();
         
-        let command = format!(
-            "Get-AppxPackage *{}* | Remove-AppxPackage -AllUsers; Get-AppxProvisionedPackage -Online | Where-Object {{ $_.PackageName -like '*{}*' }} | Remove-AppxProvisionedPackage -Online -AllUsers",
-            package_pattern, package_pattern
-        );
-        
-        let output = Command::new("powershell")
-            .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-WindowStyle", "Hidden", "-Command", &command])
-            .creation_flags(0x08000000)
-            .output();
+        let policy_items: Vec<&DebloatItem> = items.iter().filter(|item| !item.policies.is_empty()).collect();
+        if !policy_items.is_empty() {
+            let script = policy::status_script(policy_items.iter().flat_map(|item| &item.policies));
+            let output = powershell::run(&script)
+                .map_err(|e| format!("Failed to read policies: {}", e))?;
+            let current = policy::parse_values(&String::from_utf8_lossy(&output.stdout))?;
+            
+            statuses.extend(policy_items.iter().map(|item| status::ItemStatus {
+                id: item.id.clone(),
+                state: policy::state(&item.policies, &current),
+            }));
+        }
         
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                
-                if output.status.success() || stdout.contains("not found") {
-                    CommandResult {
-                        success: true,
-                        output: if stdout.is_empty() { "App removed successfully".to_string() } else { stdout },
-                        error: None,
-                    }
-                } else {
-                    CommandResult {
-                        success: false,
-                        output: stdout,
-                        error: Some(if stderr.is_empty() {
-                            "Failed to remove app".to_string()
-                        } else {
-                            stderr
-                        }),
-                    }
-                }
+        let task_items: Vec<&DebloatItem> = items.iter().filter(|item| !item.tasks.is_empty()).collect();
+        if !task_items.is_empty() {
+            let mut patterns = Vec::new();
+            for item in &task_items {
+                patterns.extend(item.task_patterns()?);
             }
-            Err(e) => {
-                CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("{}", e)),
+            let output = powershell::run(&tasks::query_script(&patterns))
+                .map_err(|e| format!("Failed to read scheduled tasks: {}", e))?;
+            let current = tasks::parse_tasks(&String::from_utf8_lossy(&output.stdout))?;
+            
+            for item in task_items {
+                let matched = tasks::matching(&item.task_patterns()?, &current);
+                if let Some(state) = tasks::state(&matched) {
+                    statuses.push(status::ItemStatus { id: item.id.clone(), state });
                 }
             }
         }
-    }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("This application only runs on Windows".to_string()),
-        }
-    }
-}
-
-#[tauri::command]
-async fn execute_command(command: String, _is_rollback: bool) -> CommandResult {
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
         
-        let output = Command::new("powershell")
-            .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-WindowStyle", "Hidden", "-Command", &command])
-            .creation_flags(0x08000000)
-            .output();
+        if items.iter().any(|item| !item.hosts.is_empty()) {
+            let blocked = read_hosts_file().and_then(|content| hosts::blocked_hosts(&content))?;
+            
+            statuses.extend(items.iter().filter(|item| !item.hosts.is_empty()).map(|item| {
+                let applied = item
+                    .hosts
+                    .iter()
+                    .filter(|host| blocked.iter().any(|blocked| blocked.eq_ignore_ascii_case(host)))
+                    .count();
+                status::ItemStatus {
+                    id: item.id.clone(),
+                    state: status::ItemState::from_counts(applied, item.hosts.len()),
+                }
+            }));
+        }
         
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                
-                if output.status.success() {
-                    CommandResult {
-                        success: true,
-                        output: stdout,
-                        error: None,
-                    }
-                } else {
-                    CommandResult {
-                        success: false,
-                        output: stdout,
-                        error: Some(if stderr.is_empty() {
-                            format!("Command failed with exit code: {:?}", output.status.code())
-                        } else {
-                            stderr
-                        }),
-                    }
+        if items.iter().any(|item| !item.firewall_rules.is_empty()) {
+            let output = powershell::run(&firewall::status_script())
+                .map_err(|e| format!("Failed to read firewall rules: {}", e))?;
+            let enabled = firewall::parse_rules(&String::from_utf8_lossy(&output.stdout))?;
+            
+            statuses.extend(items.iter().filter(|item| !item.firewall_rules.is_empty()).map(|item| {
+                status::ItemStatus {
+                    id: item.id.clone(),
+                    state: firewall::state(&item.firewall_rules, &enabled),
                 }
-            }
-            Err(e) => {
-                CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("{}", e)),
+            }));
+        }
+        
+        let feature_items: Vec<&DebloatItem> = items.iter().filter(|item| !item.features.is_empty()).collect();
+        if !feature_items.is_empty() && elevation::is_elevated() {
+            let script = features::status_script(feature_items.iter().flat_map(|item| &item.features));
+            let output = powershell::run(&script)
+                .map_err(|e| format!("Failed to read Windows features: {}", e))?;
+            let current = features::parse_features(&String::from_utf8_lossy(&output.stdout))?;
+            
+            for item in feature_items {
+                if let Some(state) = features::state(&item.features, &current) {
+                    statuses.push(status::ItemStatus { id: item.id.clone(), state });
                 }
             }
         }
+        
+        if items.iter().any(|item| item.context_menu.is_some()) {
+            let state = read_shell_state()?;
+            
+            statuses.extend(items.iter().filter_map(|item| {
+                item.context_menu.as_ref().map(|change| status::ItemStatus {
+                    id: item.id.clone(),
+                    state: change.state(&state),
+                })
+            }));
+        }
+        
+        Ok(statuses)
     }
     
     #[cfg(not(target_os = "windows"))]
     {
-        CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("This application only runs on Windows".to_string()),
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_shell_state() -> Result<context_menu::ShellState, String> {
+    let output = powershell::run(context_menu::SHELL_STATE_SCRIPT)
+        .map_err(|e| format!("Failed to read shell extensions: {}", e))?;
+    context_menu::parse_state(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+fn read_hosts_file() -> Result<String, String> {
+    match fs::read(hosts::hosts_path()) {
+        // Re-encoding a legacy ANSI file would corrupt the user's comments.
+        Ok(bytes) => String::from_utf8(bytes)
+            .map_err(|_| "The hosts file is not UTF-8 encoded; leaving it untouched".to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read hosts file: {}", e)),
+    }
+}
+
+/// Every item of every category in [`ITEM_CATEGORIES`].
+fn load_all_items(app: &tauri::AppHandle) -> Result<Vec<DebloatItem>, String> {
+    let mut items = Vec::new();
+    for category in ITEM_CATEGORIES {
+        items.extend(load_items(category.to_string(), None, app.clone())?);
+    }
+    Ok(items)
+}
+
+/// Relations of every catalog item by id, checked with [`plan::validate`].
+fn catalog_relations(items: &[DebloatItem]) -> Result<BTreeMap<String, plan::Relations>, String> {
+    let mut catalog = BTreeMap::new();
+    for item in items {
+        if catalog.insert(item.id.clone(), item.relations.clone()).is_some() {
+            return Err(format!("Duplicate item id: {}", item.id));
         }
     }
+    plan::validate(&catalog)?;
+    Ok(catalog)
 }
 
+/// Expands the selection with the items it requires and puts it in the
+/// order it runs in, reporting conflicts and dependency cycles.
 #[tauri::command]
-async fn execute_commands(commands: Vec<String>, is_rollback: bool) -> Vec<CommandResult> {
-    let mut results = Vec::new();
+fn plan_batch(ids: Vec<String>, app: tauri::AppHandle) -> Result<BatchPlan, String> {
+    let items = load_all_items(&app)?;
+    let plan = plan::resolve(&catalog_relations(&items)?, &ids);
+    
+    let mut by_id: BTreeMap<String, DebloatItem> = items.into_iter().map(|item| (item.id.clone(), item)).collect();
+    let items = plan.steps.iter().filter_map(|step| by_id.remove(&step.id)).collect();
+    
+    Ok(BatchPlan { plan, items })
+}
+
+/// Runs the pre-flight checks for the items in `ids` against freshly read
+/// system info. Ids that are not catalog items (such as apps) are ignored.
+#[tauri::command]
+async fn preflight(
+    ids: Vec<String>,
+    app: tauri::AppHandle,
+    cache: tauri::State<'_, system_info::SystemInfoCache>,
+) -> Result<preflight::PreflightReport, String> {
+    let info = cache.refresh()?;
+    let items = load_all_items(&app)?;
+    let batch = preflight::Batch::from_items(items.iter().filter(|item| ids.contains(&item.id)));
+    
+    Ok(preflight::evaluate(&info, &batch))
+}
+
+/// Called before a batch is applied. Fails when the batch has conflicts or
+/// a dependency cycle, goes above the risk policy, or when a pre-flight check
+/// blocks it, unless `force` is set. Critical items also need a token from
+/// [`confirm_critical_items`], which is used up here. `force` overrides
+/// neither conflicts nor risk. The checks cover the items the selection
+/// requires too, and the returned ticket admits each of them once to the
+/// apply commands.
+#[tauri::command]
+async fn begin_apply(
+    ids: Vec<String>,
+    force: bool,
+    confirmation: Option<String>,
+    app: tauri::AppHandle,
+    cache: tauri::State<'_, system_info::SystemInfoCache>,
+    confirmations: tauri::State<'_, risk::Confirmations>,
+    batches: tauri::State<'_, batch::Tickets>,
+) -> Result<ApplyTicket, String> {
+    let items = load_all_items(&app)?;
+    let plan = plan::resolve(&catalog_relations(&items)?, &ids);
+    plan.ensure_runnable()?;
+    let ids: Vec<String> = plan.ids().into_iter().map(str::to_string).collect();
+    
+    let risks = batch_risks(&app, &items, &ids)?;
+    risk::check_policy(&risks, &json_store::load(&get_risk_policy_path(&app)?, "risk policy")?)?;
+    
+    let report = preflight(ids.clone(), app, cache).await?;
+    preflight::ensure_allowed(&report, force)?;
+    
+    confirmations.redeem(confirmation.as_deref(), &risk::critical_ids(&risks))?;
+    Ok(ApplyTicket {
+        ticket: batches.issue(ids.into_iter().collect()),
+        preflight: report,
+    })
+}
+
+/// Checks an apply command against the batch [`begin_apply`] opened for it.
+/// Rollbacks only restore what was there before and need no ticket.
+fn admit(app: &tauri::AppHandle, id: &str, is_rollback: bool, ticket: Option<&str>) -> Result<(), String> {
+    if is_rollback {
+        return Ok(());
+    }
+    app.state::<batch::Tickets>().admit(ticket, id)
+}
+
+/// Risk of the catalog items, apps and Win32 programs in `ids`.
+fn batch_risks(app: &tauri::AppHandle, items: &[DebloatItem], ids: &[String]) -> Result<Vec<risk::RiskyItem>, String> {
+    let mut risks: Vec<risk::RiskyItem> = items
+        .iter()
+        .filter(|item| ids.contains(&item.id))
+        .map(|item| risk::RiskyItem {
+            id: item.id.clone(),
+            name: item.name.clone(),
+            risk: item.risk.clone(),
+        })
+        .collect();
+    risks.extend(
+        get_app_catalog(app)?
+            .into_iter()
+            .filter(|entry| ids.contains(&entry.id))
+            .map(|entry| risk::RiskyItem {
+                id: entry.id,
+                name: entry.name,
+                risk: entry.risk,
+            }),
+    );
+    risks.extend(
+        load_win32_catalog(app)?
+            .into_iter()
+            .filter(|entry| ids.contains(&entry.id))
+            .map(|entry| risk::RiskyItem {
+                id: entry.id,
+                name: entry.name,
+                risk: entry.risk,
+            }),
+    );
+    Ok(risks)
+}
+
+/// Issues the token [`begin_apply`] needs for the critical items in `ids`,
+/// once the user has confirmed them.
+#[tauri::command]
+fn confirm_critical_items(
+    ids: Vec<String>,
+    app: tauri::AppHandle,
+    confirmations: tauri::State<'_, risk::Confirmations>,
+) -> Result<String, String> {
+    let risks = batch_risks(&app, &load_all_items(&app)?, &ids)?;
+    Ok(confirmations.issue(risk::critical_ids(&risks)))
+}
+
+#[tauri::command]
+fn get_risk_policy(app: tauri::AppHandle) -> Result<risk::RiskPolicy, String> {
+    json_store::load(&get_risk_policy_path(&app)?, "risk policy")
+}
+
+#[tauri::command]
+fn set_risk_policy(policy: risk::RiskPolicy, app: tauri::AppHandle) -> Result<(), String> {
+    json_store::save(&get_risk_policy_path(&app)?, &policy, "risk policy")
+}
+
+/// Called after a batch is applied or rolled back with the ids that
+/// succeeded. Records the items that still need an Explorer restart, sign-out
+/// or reboot and returns them with the strongest requirement of the batch.
+#[tauri::command]
+fn finish_apply(ids: Vec<String>, is_rollback: bool, app: tauri::AppHandle) -> Result<effects::PendingEffects, String> {
+    let changed: Vec<effects::PendingItem> = load_all_items(&app)?
+        .into_iter()
+        .filter(|item| ids.contains(&item.id) && item.effect != effects::Effect::Immediate)
+        .map(|item| effects::PendingItem {
+            id: item.id,
+            name: item.name,
+            effect: item.effect,
+        })
+        .collect();
     
-    for cmd in commands {
-        let result = execute_command(cmd, is_rollback).await;
-        results.push(result);
+    if changed.is_empty() {
+        return Ok(effects::PendingEffects::new(changed));
     }
     
-    results
+    let path = get_pending_effects_path(&app)?;
+    let mut records: BTreeMap<String, effects::PendingRecord> = json_store::load(&path, "pending effects")?;
+    let waiting = if is_rollback {
+        effects::record_rollback(&mut records, &changed, unix_millis())
+    } else {
+        effects::record(&mut records, &changed, unix_millis());
+        changed
+    };
+    json_store::save(&path, &records, "pending effects")?;
+    
+    Ok(effects::PendingEffects::new(waiting))
 }
 
+/// Items applied in this or an earlier run of the app that have not taken
+/// effect yet. Records settled by a reboot, sign-in or Explorer restart since
+/// are dropped.
 #[tauri::command]
-fn get_system_info() -> Result<serde_json::Value, String> {
+async fn get_pending_effects(app: tauri::AppHandle) -> Result<effects::PendingEffects, String> {
+    let path = get_pending_effects_path(&app)?;
+    let mut records: BTreeMap<String, effects::PendingRecord> = json_store::load(&path, "pending effects")?;
+    
+    if !records.is_empty() && effects::settle(&mut records, &read_session_times()?) {
+        json_store::save(&path, &records, "pending effects")?;
+    }
+    
+    Ok(effects::pending(&records))
+}
+
+fn read_session_times() -> Result<effects::SessionTimes, String> {
     #[cfg(target_os = "windows")]
     {
-        use std::process::Command;
-        
-        let username = std::env::var("USERNAME").unwrap_or_else(|_| "Unknown".to_string());
-        
-        let os_version = Command::new("powershell")
-            .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", "[System.Environment]::OSVersion.VersionString"])
-            .creation_flags(0x08000000)
-            .output();
-        
-        let os_version_str = match os_version {
-            Ok(output) => String::from_utf8_lossy(&output.stdout).trim().to_string(),
-            Err(_) => "Unknown".to_string(),
-        };
-        
-        let build = Command::new("powershell")
-            .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", "(Get-CimInstance Win32_OperatingSystem).BuildNumber"])
-            .creation_flags(0x08000000)
-            .output();
-        
-        let build_str = match build {
-            Ok(output) => String::from_utf8_lossy(&output.stdout).trim().to_string(),
-            Err(_) => "Unknown".to_string(),
-        };
-        
-        Ok(serde_json::json!({
-            "windowsVersion": os_version_str,
-            "buildNumber": build_str,
-            "username": username
-        }))
+        let output = powershell::run(effects::SESSION_TIMES_SCRIPT)
+            .map_err(|e| format!("Failed to read session times: {}", e))?;
+        effects::parse_session_times(&String::from_utf8_lossy(&output.stdout))
     }
     
     #[cfg(not(target_os = "windows"))]
     {
-        Err("This application only runs on Windows".to_string())
+        Ok(effects::SessionTimes::default())
+    }
+}
+
+/// Restarts Explorer so pending Explorer changes take effect without signing
+/// out. Open File Explorer windows are closed.
+#[tauri::command]
+async fn restart_explorer(app: tauri::AppHandle) -> CommandResult {
+    let mut result = execute_command(effects::RESTART_EXPLORER_SCRIPT.to_string(), false).await;
+    
+    if result.success {
+        let settled = get_pending_effects_path(&app).and_then(|path| {
+            let mut records: BTreeMap<String, effects::PendingRecord> = json_store::load(&path, "pending effects")?;
+            let times = effects::SessionTimes {
+                explorer: Some(unix_millis()),
+                ..effects::SessionTimes::default()
+            };
+            if effects::settle(&mut records, &times) {
+                json_store::save(&path, &records, "pending effects")?;
+            }
+            Ok(())
+        });
+        // Explorer restarted either way; the items just stay listed as pending.
+        if let Err(e) = settled {
+            if !result.output.is_empty() && !result.output.ends_with('\n') {
+                result.output.push('\n');
+            }
+            result.output.push_str(&format!("Pending changes were not updated: {}", e));
+        }
     }
+    
+    result
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+fn load_presets(app: tauri::AppHandle, locale: Option<String>) -> Result<Vec<Preset>, String> {
+    let strings = string_table(&app, locale.as_deref())?;
+    let data_path = get_resource_path(app)?;
+    let file_path = data_path.join("presets.json");
+    
+    if !file_path.exists() {
+        return Err(format!("Presets file not found: {}", file_path.to_string_lossy()));
+    }
+    
+    let contents = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read presets file: {}", e))?;
+    
+    let mut data: PresetsData = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse presets JSON: {}", e))?;
+    
+    for preset in &mut data.presets {
+        strings.localize(locale::Section::Presets, &preset.id, &mut preset.name, &mut preset.description);
+    }
+    
+    Ok(data.presets)
+}
+
+/// Missing and stale translations of every locale with a string table.
+/// Custom apps are left out since users name them in their own language.
+#[tauri::command]
+fn check_translations(app: tauri::AppHandle) -> Result<Vec<locale::TranslationReport>, String> {
+    let text = |section, id: &str| locale::TextId {
+        section,
+        id: id.to_string(),
+    };
+    
+    let mut catalog: Vec<locale::TextId> = load_all_items(&app)?
+        .iter()
+        .map(|item| text(locale::Section::Items, &item.id))
+        .collect();
+    catalog.extend(get_bloatware_definitions().iter().map(|app| text(locale::Section::Apps, &app.id)));
+    catalog.extend(load_winget_catalog(&app)?.iter().map(|entry| text(locale::Section::Apps, &entry.id)));
+    catalog.extend(load_win32_catalog(&app)?.iter().map(|entry| text(locale::Section::Programs, &entry.id)));
+    catalog.extend(load_presets(app.clone(), None)?.iter().map(|preset| text(locale::Section::Presets, &preset.id)));
+    
+    let dir = get_locales_path(&app)?;
+    locale::available(&dir)?
+        .into_iter()
+        .map(|code| Ok(locale::report(&code, &locale::load_table(&dir, &code)?, &catalog)))
+        .collect()
+}
+
+/// Searches every category, app and Win32 program at once, best match first.
+#[tauri::command]
+fn search_items(
+    app: tauri::AppHandle,
+    query: String,
+    filters: Option<search::SearchFilters>,
+) -> Result<Vec<search::SearchResult>, String> {
+    let mut entries: Vec<search::SearchEntry> = load_all_items(&app)?
+        .into_iter()
+        .map(|item| search::SearchEntry {
+            kind: search::EntryKind::Item,
+            id: item.id,
+            name: item.name,
+            description: item.description,
+            category: item.category,
+            tags: item.tags,
+            risk: item.risk,
+        })
+        .collect();
+    
+    entries.extend(get_app_catalog(&app)?.into_iter().map(|app| search::SearchEntry {
+        kind: search::EntryKind::App,
+        id: app.id,
+        name: app.name,
+        description: app.description,
+        category: "apps".to_string(),
+        tags: app.tags,
+        risk: app.risk,
+    }));
+    
+    entries.extend(load_win32_catalog(&app)?.into_iter().map(|entry| search::SearchEntry {
+        kind: search::EntryKind::Program,
+        id: entry.id,
+        name: entry.name,
+        description: entry.description,
+        category: entry.category,
+        tags: entry.tags,
+        risk: entry.risk,
+    }));
+    
+    Ok(search::search(entries, &query, &filters.unwrap_or_default()))
+}
+
+#[cfg(target_os = "windows")]
+fn query_packages(ps_script: &str) -> Result<String, String> {
+    let output = powershell::run(ps_script)
+        .map_err(|e| format!("Failed to get installed packages: {}", e))?;
+    
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+async fn get_installed_packages() -> Result<Vec<package_match::PackageIdentity>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let json = query_packages(
+            "Get-AppxPackage | Select-Object Name, PackageFamilyName, PackageFullName | ConvertTo-Json -Compress",
+        )?;
+        package_match::parse_installed(&json)
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+#[tauri::command]
+async fn get_installed_package_names() -> Result<Vec<String>, String> {
+    let packages = get_installed_packages().await?;
+    Ok(packages.into_iter().map(|p| p.name.to_lowercase()).collect())
+}
+
+async fn get_package_snapshot(elevated: bool) -> Result<package_scope::PackageSnapshot, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let json = query_packages(package_scope::snapshot_script(elevated))?;
+        package_scope::PackageSnapshot::parse(&json)
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = elevated;
+        Ok(package_scope::PackageSnapshot::default())
+    }
+}
+
+#[tauri::command]
+async fn get_package_inventory() -> Result<Vec<inventory::PackageInfo>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let json = query_packages(inventory::INVENTORY_SCRIPT)?;
+        inventory::parse_inventory(&json)
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+#[tauri::command]
+async fn get_winget_packages() -> Result<Vec<winget::WingetPackage>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let output = powershell::run(winget::EXPORT_SCRIPT)
+            .map_err(|e| format!("Failed to run winget: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to export winget packages: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        winget::parse_export(&String::from_utf8_lossy(&output.stdout))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Installed winget packages, from the last export while it is fresh.
+async fn installed_winget_packages(app: &tauri::AppHandle) -> Result<Vec<winget::WingetPackage>, String> {
+    if let Some(packages) = app.state::<winget::ExportCache>().get() {
+        return Ok(packages);
+    }
+    
+    let packages = get_winget_packages().await?;
+    app.state::<winget::ExportCache>().store(packages.clone());
+    Ok(packages)
+}
+
+#[tauri::command]
+async fn get_all_bloatware_with_status(
+    app: tauri::AppHandle,
+    locale: Option<String>,
+) -> Result<Vec<BloatwareApp>, String> {
+    let strings = string_table(&app, locale.as_deref())?;
+    let mut bloatware = get_app_catalog(&app)?;
+    let snapshot = get_package_snapshot(elevation::is_elevated()).await?;
+    // A winget failure only leaves the winget apps without a status.
+    let winget_packages = if bloatware.iter().any(|entry| entry.source == AppSource::Winget) {
+        installed_winget_packages(&app).await
+    } else {
+        Ok(Vec::new())
+    };
+    
+    for app in &mut bloatware {
+        strings.localize(locale::Section::Apps, &app.id, &mut app.name, &mut app.description);
+        
+        if app.source == AppSource::Winget {
+            match &winget_packages {
+                Ok(packages) => {
+                    app.matched_packages = winget::find_installed(&app.package_pattern, packages)
+                        .map(|package| package.id.clone())
+                        .into_iter()
+                        .collect();
+                    app.is_installed = !app.matched_packages.is_empty();
+                }
+                Err(e) => app.status_error = Some(e.clone()),
+            }
+            continue;
+        }
+        
+        let pattern = package_match::PackagePattern::new(&app.package_pattern);
+        let status = snapshot.status(&pattern);
+        app.installed_for_other_users = status.other_users.map(|p| !p.is_empty());
+        app.provisioned = status.provisioned.map(|p| !p.is_empty());
+        app.matched_packages = status.current_user;
+        app.is_installed = !app.matched_packages.is_empty();
+    }
+    
+    Ok(bloatware)
+}
+
+#[tauri::command]
+async fn discover_unknown_packages(app: tauri::AppHandle) -> Result<Vec<discovery::DiscoveredPackage>, String> {
+    let patterns: Vec<package_match::PackagePattern> = get_app_catalog(&app)?
+        .iter()
+        .filter(|entry| entry.source == AppSource::Appx)
+        .map(|entry| package_match::PackagePattern::new(&entry.package_pattern))
+        .collect();
+    let inventory = get_package_inventory().await?;
+    
+    Ok(discovery::discover(inventory, |package| {
+        let identity = package.identity();
+        patterns.iter().any(|pattern| pattern.matches(&identity))
+    }))
+}
+
+#[tauri::command]
+fn promote_discovered_package(
+    app: tauri::AppHandle,
+    package_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    risk: risk::RiskLevel,
+) -> Result<BloatwareApp, String> {
+    let package_name = powershell::validate_package_pattern(&package_name)
+        .map_err(|e| e.to_string())?
+        .to_string();
+    
+    let path = get_custom_apps_path(&app)?;
+    let mut custom_apps: Vec<discovery::CustomApp> = json_store::load(&path, "custom apps")?;
+    
+    let custom = discovery::CustomApp {
+        id: discovery::custom_app_id(&package_name, &custom_apps),
+        name: name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| package_name.clone()),
+        description: description.unwrap_or_else(|| "Discovered package added by the user".to_string()),
+        risk: risk::Risk::level(risk),
+        package_pattern: package_name,
+    };
+    
+    custom_apps.retain(|existing| existing.id != custom.id);
+    custom_apps.push(custom.clone());
+    json_store::save(&path, &custom_apps, "custom apps")?;
+    
+    Ok(bloatware(&custom.id, &custom.name, &custom.description, risk, &custom.package_pattern))
+}
+
+#[tauri::command]
+fn remove_custom_app(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let path = get_custom_apps_path(&app)?;
+    let mut custom_apps: Vec<discovery::CustomApp> = json_store::load(&path, "custom apps")?;
+    
+    let before = custom_apps.len();
+    custom_apps.retain(|existing| existing.id != id);
+    if custom_apps.len() == before {
+        return Err(format!("Custom app not found: {}", id));
+    }
+    
+    json_store::save(&path, &custom_apps, "custom apps")
+}
+
+#[tauri::command]
+async fn remove_app(
+    app: tauri::AppHandle,
+    id: String,
+    scopes: Option<Vec<package_scope::RemovalScope>>,
+    ticket: Option<String>,
+) -> CommandResult {
+    if let Err(e) = admit(&app, &id, false, ticket.as_deref()) {
+        return CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        };
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        // Looked up by id: Appx and winget entries can share a package name.
+        let lookup = get_app_catalog(&app).and_then(|catalog| {
+            catalog
+                .into_iter()
+                .find(|entry| entry.id == id)
+                .ok_or_else(|| format!("Unknown app: {}", id))
+        });
+        let entry = match lookup {
+            Ok(entry) => entry,
+            Err(e) => {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                };
+            }
+        };
+        
+        if entry.source == AppSource::Winget {
+            return remove_winget_app(&app, &entry).await;
+        }
+        
+        let package_pattern = entry.package_pattern.clone();
+        
+        let scopes = scopes.unwrap_or_else(package_scope::RemovalScope::defaults);
+        let needs_admin = scopes.iter().any(|scope| scope.requires_admin());
+        
+        if needs_admin {
+            if let Err(e) = elevation::ensure_elevated("Removing apps for all users or from the system image") {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                };
+            }
+        }
+        
+        let pattern = match powershell::validate_package_pattern(&package_pattern) {
+            Ok(pattern) => package_match::PackagePattern::new(pattern),
+            Err(e) => {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                };
+            }
+        };
+        
+        let snapshot = match get_package_snapshot(needs_admin).await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                };
+            }
+        };
+        
+        let statements = snapshot.removal_statements(&pattern, &scopes);
+        
+        if statements.is_empty() {
+            return CommandResult {
+                success: true,
+                output: format!("No packages matching {} found in the selected scopes", package_pattern),
+                error: None,
+            };
+        }
+        
+        // Decide how the app could come back while the packages still exist.
+        let status = snapshot.status(&pattern);
+        let surviving_manifest = reinstall::surviving_package(&status, &scopes)
+            .and_then(|full_name| reinstall::manifest_path(&full_name, &snapshot.installed));
+        let mut removed_packages = status.current_user.clone();
+        removed_packages.extend(status.other_users.clone().unwrap_or_default());
+        removed_packages.extend(status.provisioned.clone().unwrap_or_default());
+        removed_packages.sort();
+        removed_packages.dedup();
+        let record = reinstall::ReinstallRecord {
+            package_pattern: package_pattern.clone(),
+            removed_packages,
+            strategies: reinstall::viable_strategies(surviving_manifest, &entry.reinstall),
+        };
+        
+        let output = powershell::run(&statements.join("; "));
+        
+        match output {
+            Ok(output) => {
+                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                
+                if output.status.success() {
+                    // The app is gone either way; without the record only the
+                    // catalog's reinstall sources remain.
+                    let saved = save_reinstall_record(&app, entry.id.clone(), record);
+                    
+                    CommandResult {
+                        success: true,
+                        output: if stdout.is_empty() {
+                            "App removed successfully".to_string()
+                        } else {
+                            stdout
+                        },
+                        error: saved.err(),
+                    }
+                } else {
+                    CommandResult {
+                        success: false,
+                        output: stdout,
+                        error: Some(if stderr.is_empty() {
+                            "Failed to remove app".to_string()
+                        } else {
+                            stderr
+                        }),
+                    }
+                }
+            }
+            Err(e) => {
+                CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("{}", e)),
+                }
+            }
+        }
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some("This application only runs on Windows".to_string()),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn remove_winget_app(app: &tauri::AppHandle, entry: &BloatwareApp) -> CommandResult {
+    let mut result = execute_command(winget::uninstall_script(&entry.package_pattern), false).await;
+    app.state::<winget::ExportCache>().invalidate();
+    
+    if result.success {
+        let saved = save_reinstall_record(app, entry.id.clone(), reinstall::ReinstallRecord {
+            package_pattern: entry.package_pattern.clone(),
+            removed_packages: vec![entry.package_pattern.clone()],
+            strategies: entry.reinstall.clone(),
+        });
+        result.error = saved.err();
+    }
+    
+    result
+}
+
+#[tauri::command]
+async fn reinstall_app(app: tauri::AppHandle, id: String) -> CommandResult {
+    #[cfg(target_os = "windows")]
+    {
+        let lookup = get_reinstall_records_path(&app).and_then(|path| {
+            let records: BTreeMap<String, reinstall::ReinstallRecord> = json_store::load(&path, "reinstall records")?;
+            let strategies = match records.get(&id) {
+                Some(record) => record.strategies.clone(),
+                None => get_app_catalog(&app)?
+                    .into_iter()
+                    .find(|entry| entry.id == id)
+                    .map(|entry| entry.reinstall)
+                    .unwrap_or_default(),
+            };
+            Ok((path, records, strategies))
+        });
+        
+        let (path, mut records, strategies) = match lookup {
+            Ok(lookup) => lookup,
+            Err(e) => {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                };
+            }
+        };
+        
+        if strategies.is_empty() {
+            return CommandResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("No reinstall source is known for {}", id)),
+            };
+        }
+        
+        let mut errors = Vec::new();
+        
+        for strategy in &strategies {
+            match powershell::run(&strategy.render()) {
+                Ok(output) if output.status.success() => {
+                    app.state::<winget::ExportCache>().invalidate();
+                    // The app is back either way; a stale record only means
+                    // reinstalling it again would be offered.
+                    records.remove(&id);
+                    let saved = json_store::save(&path, &records, "reinstall records");
+                    
+                    return CommandResult {
+                        success: true,
+                        output: String::from_utf8_lossy(&output.stdout).to_string(),
+                        error: saved.err(),
+                    };
+                }
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    errors.push(if stderr.is_empty() {
+                        format!("{} exited with code {:?}", strategy.render(), output.status.code())
+                    } else {
+                        stderr
+                    });
+                }
+                Err(e) => errors.push(format!("{}", e)),
+            }
+        }
+        
+        CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(errors.join("\n")),
+        }
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some("This application only runs on Windows".to_string()),
+        }
+    }
+}
+
+fn load_win32_catalog(app: &tauri::AppHandle) -> Result<Vec<win32::Win32CatalogEntry>, String> {
+    let file_path = get_resource_path(app.clone())?.join("win32.json");
+    
+    let contents = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read Win32 catalog: {}", e))?;
+    
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse Win32 catalog: {}", e))
+}
+
+#[tauri::command]
+async fn get_uninstall_entries() -> Result<Vec<win32::UninstallEntry>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let output = powershell::run(win32::UNINSTALL_ENTRIES_SCRIPT)
+            .map_err(|e| format!("Failed to read installed programs: {}", e))?;
+        win32::parse_entries(&String::from_utf8_lossy(&output.stdout))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+#[tauri::command]
+async fn get_win32_programs_with_status(
+    app: tauri::AppHandle,
+    locale: Option<String>,
+) -> Result<Vec<win32::Win32App>, String> {
+    let strings = string_table(&app, locale.as_deref())?;
+    let catalog = load_win32_catalog(&app)?;
+    let entries = get_uninstall_entries().await?;
+    
+    catalog
+        .into_iter()
+        .map(|mut entry| {
+            let matcher = win32::Win32Matcher::new(&entry)?;
+            let matched: Vec<win32::UninstallEntry> = entries
+                .iter()
+                .filter(|installed| matcher.matches(installed))
+                .cloned()
+                .collect();
+            strings.localize(locale::Section::Programs, &entry.id, &mut entry.name, &mut entry.description);
+            Ok(win32::Win32App {
+                entry,
+                is_installed: !matched.is_empty(),
+                matched,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+async fn uninstall_win32_program(app: tauri::AppHandle, id: String, ticket: Option<String>) -> CommandResult {
+    if let Err(e) = admit(&app, &id, false, ticket.as_deref()) {
+        return CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        };
+    }
+    
+    let programs = match get_win32_programs_with_status(app, None).await {
+        Ok(programs) => programs,
+        Err(e) => {
+            return CommandResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            };
+        }
+    };
+    
+    let Some(program) = programs.into_iter().find(|program| program.entry.id == id) else {
+        return CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("Unknown program: {}", id)),
+        };
+    };
+    
+    if program.matched.is_empty() {
+        return CommandResult {
+            success: true,
+            output: format!("{} is not installed", program.entry.name),
+            error: None,
+        };
+    }
+    
+    if program.matched.iter().any(|entry| entry.is_machine_wide()) {
+        if let Err(e) = elevation::ensure_elevated("Uninstalling programs installed for all users") {
+            return CommandResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            };
+        }
+    }
+    
+    let mut output = Vec::new();
+    let mut errors = Vec::new();
+    
+    for entry in &program.matched {
+        let plan = match win32::uninstall_plan(entry) {
+            Ok(plan) => plan,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        
+        let result = execute_command(plan.script, false).await;
+        if result.success {
+            output.push(format!("Uninstalled {}", entry.display_name));
+        } else {
+            errors.push(format!(
+                "{}: {}",
+                entry.display_name,
+                result.error.unwrap_or_else(|| "Uninstall failed".to_string())
+            ));
+        }
+    }
+    
+    CommandResult {
+        success: errors.is_empty(),
+        output: output.join("\n"),
+        error: if errors.is_empty() { None } else { Some(errors.join("\n")) },
+    }
+}
+
+/// Runs the command of a plain catalog item, or its rollback command.
+#[tauri::command]
+async fn execute_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> CommandResult {
+    let command = admit(&app, &id, is_rollback, ticket.as_deref()).and_then(|()| {
+        let item = load_items(category, None, app)?
+            .into_iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Item not found: {}", id))?;
+        
+        if is_rollback {
+            item.rollback_command
+                .ok_or_else(|| format!("No rollback command available for {}", item.name))
+        } else {
+            Ok(item.command)
+        }
+    });
+    
+    match command {
+        Ok(command) => execute_command(command, is_rollback).await,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn execute_command(command: String, _is_rollback: bool) -> CommandResult {
+    #[cfg(target_os = "windows")]
+    {
+        if elevation::requires_admin(&command) {
+            if let Err(e) = elevation::ensure_elevated("This change") {
+                return CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                };
+            }
+        }
+        
+        let output = powershell::run(&command);
+        
+        match output {
+            Ok(output) => {
+                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                
+                if output.status.success() {
+                    CommandResult {
+                        success: true,
+                        output: stdout,
+                        error: None,
+                    }
+                } else {
+                    CommandResult {
+                        success: false,
+                        output: stdout,
+                        error: Some(if stderr.is_empty() {
+                            format!("Command failed with exit code: {:?}", output.status.code())
+                        } else {
+                            stderr
+                        }),
+                    }
+                }
+            }
+            Err(e) => {
+                CommandResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("{}", e)),
+                }
+            }
+        }
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some("This application only runs on Windows".to_string()),
+        }
+    }
+}
+
+#[tauri::command]
+async fn execute_composite(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> Result<composite::CompositeResult, String> {
+    admit(&app, &id, is_rollback, ticket.as_deref())?;
+    
+    let item = load_items(category, None, app)?
+        .into_iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| format!("Item not found: {}", id))?;
+    
+    if item.steps.is_empty() {
+        return Err(format!("{} is not a composite item", item.name));
+    }
+    
+    let mut results = Vec::new();
+    let mut stopped = false;
+    
+    for (step, command) in composite::plan(&item.steps, is_rollback) {
+        if stopped {
+            results.push(composite::skipped(step));
+            continue;
+        }
+        
+        let result = execute_command(command.to_string(), is_rollback).await;
+        stopped = composite::stops_after(step, result.success, is_rollback);
+        
+        results.push(composite::StepResult {
+            id: step.id.clone(),
+            name: step.name.clone(),
+            success: result.success,
+            skipped: false,
+            output: result.output,
+            error: result.error,
+        });
+    }
+    
+    Ok(composite::summarize(&item.steps, results))
+}
+
+/// Writes the values of a policy item after recording what each one held,
+/// or restores exactly those values on rollback.
+#[tauri::command]
+async fn apply_policy_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> CommandResult {
+    match apply_policy_item_inner(&app, category, &id, is_rollback, ticket.as_deref()).await {
+        Ok(result) => result,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn apply_policy_item_inner(
+    app: &tauri::AppHandle,
+    category: String,
+    id: &str,
+    is_rollback: bool,
+    ticket: Option<&str>,
+) -> Result<CommandResult, String> {
+    admit(app, id, is_rollback, ticket)?;
+    
+    #[cfg(target_os = "windows")]
+    {
+        let item = load_items(category, None, app.clone())?
+            .into_iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Item not found: {}", id))?;
+        if item.policies.is_empty() {
+            return Err(format!("{} has no policies", item.name));
+        }
+        
+        let path = get_policy_records_path(app)?;
+        let mut records: BTreeMap<String, Vec<policy::PolicyRecord>> = json_store::load(&path, "policy records")?;
+        
+        if is_rollback {
+            let recorded = records
+                .get(id)
+                .ok_or_else(|| format!("No recorded policy values for {}; nothing to roll back", item.name))?;
+            let result = execute_command(policy::restore_script(recorded), true).await;
+            
+            if result.success {
+                records.remove(id);
+                json_store::save(&path, &records, "policy records")?;
+            }
+            return Ok(result);
+        }
+        
+        let output = powershell::run(&policy::status_script(&item.policies))
+            .map_err(|e| format!("Failed to read policies: {}", e))?;
+        let current = policy::parse_values(&String::from_utf8_lossy(&output.stdout))?;
+        
+        // Record before changing anything so a partial failure can still be undone.
+        let record = policy::record(&item.policies, &current, records.get(id).map(Vec::as_slice).unwrap_or_default())?;
+        records.insert(id.to_string(), record);
+        json_store::save(&path, &records, "policy records")?;
+        
+        Ok(execute_command(item.command, false).await)
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+/// Disables the scheduled tasks of a task item, first recording the state
+/// each one was in, or restores exactly those recorded states on rollback.
+#[tauri::command]
+async fn apply_task_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> CommandResult {
+    match apply_task_item_inner(&app, category, &id, is_rollback, ticket.as_deref()).await {
+        Ok(result) => result,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn apply_task_item_inner(
+    app: &tauri::AppHandle,
+    category: String,
+    id: &str,
+    is_rollback: bool,
+    ticket: Option<&str>,
+) -> Result<CommandResult, String> {
+    admit(app, id, is_rollback, ticket)?;
+    
+    #[cfg(target_os = "windows")]
+    {
+        let item = load_items(category, None, app.clone())?
+            .into_iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Item not found: {}", id))?;
+        let patterns = item.task_patterns()?;
+        if patterns.is_empty() {
+            return Err(format!("{} has no scheduled tasks", item.name));
+        }
+        
+        let output = powershell::run(&tasks::query_script(&patterns))
+            .map_err(|e| format!("Failed to read scheduled tasks: {}", e))?;
+        let current = tasks::parse_tasks(&String::from_utf8_lossy(&output.stdout))?;
+        let matched = tasks::matching(&patterns, &current);
+        
+        let path = get_task_records_path(app)?;
+        let mut records: BTreeMap<String, Vec<tasks::TaskInfo>> = json_store::load(&path, "task records")?;
+        
+        if is_rollback {
+            let recorded = records
+                .get(id)
+                .ok_or_else(|| format!("No recorded task states for {}; nothing to roll back", item.name))?;
+            // Tasks removed since (e.g. by a feature update) cannot be re-enabled.
+            let restore: Vec<&tasks::TaskInfo> = tasks::to_restore(recorded)
+                .into_iter()
+                .filter(|task| matched.iter().any(|m| m.full_path().eq_ignore_ascii_case(&task.full_path())))
+                .collect();
+        
+            let result = if restore.is_empty() {
+                CommandResult {
+                    success: true,
+                    output: format!("All tasks of {} were already disabled before it was applied", item.name),
+                    error: None,
+                }
+            } else {
+                execute_command(tasks::set_enabled_script(&restore, true), true).await
+            };
+        
+            if result.success {
+                records.remove(id);
+                json_store::save(&path, &records, "task records")?;
+            }
+            return Ok(result);
+        }
+        
+        if matched.is_empty() {
+            return Ok(CommandResult {
+                success: true,
+                output: format!("None of the tasks of {} exist on this system", item.name),
+                error: None,
+            });
+        }
+        
+        // Record before changing anything so a partial failure can still be undone.
+        tasks::merge_original(records.entry(id.to_string()).or_default(), &matched);
+        json_store::save(&path, &records, "task records")?;
+        
+        let to_disable: Vec<&tasks::TaskInfo> = matched.into_iter().filter(|task| !task.is_disabled()).collect();
+        if to_disable.is_empty() {
+            return Ok(CommandResult {
+                success: true,
+                output: format!("All tasks of {} are already disabled", item.name),
+                error: None,
+            });
+        }
+        
+        Ok(execute_command(tasks::set_enabled_script(&to_disable, false), false).await)
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+/// Applies a context menu item after recording the state it replaces, or
+/// restores exactly that state on rollback. Explorer picks the change up the
+/// next time it starts.
+#[tauri::command]
+async fn apply_context_menu_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> CommandResult {
+    match apply_context_menu_item_inner(&app, category, &id, is_rollback, ticket.as_deref()).await {
+        Ok(result) => result,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn apply_context_menu_item_inner(
+    app: &tauri::AppHandle,
+    category: String,
+    id: &str,
+    is_rollback: bool,
+    ticket: Option<&str>,
+) -> Result<CommandResult, String> {
+    admit(app, id, is_rollback, ticket)?;
+    
+    #[cfg(target_os = "windows")]
+    {
+        let item = load_items(category, None, app.clone())?
+            .into_iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Item not found: {}", id))?;
+        let change = item
+            .context_menu
+            .ok_or_else(|| format!("{} is not a context menu item", item.name))?;
+        
+        let path = get_context_menu_records_path(app)?;
+        let mut records: BTreeMap<String, context_menu::ContextMenuRecord> = json_store::load(&path, "context menu records")?;
+        
+        if is_rollback {
+            let record = records
+                .get(id)
+                .ok_or_else(|| format!("No recorded context menu state for {}; nothing to roll back", item.name))?;
+            let result = execute_command(context_menu::restore_script(record), true).await;
+            
+            if result.success {
+                records.remove(id);
+                json_store::save(&path, &records, "context menu records")?;
+            }
+            return Ok(with_explorer_note(result));
+        }
+        
+        // Record before changing anything so a partial failure can still be undone.
+        let state = read_shell_state()?;
+        let record = context_menu::record(&change, &state, records.remove(id));
+        records.insert(id.to_string(), record);
+        json_store::save(&path, &records, "context menu records")?;
+        
+        Ok(with_explorer_note(execute_command(change.apply_script(), false).await))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn with_explorer_note(mut result: CommandResult) -> CommandResult {
+    if result.success {
+        let note = "Restart Explorer or sign out to see the change";
+        result.output = match result.output.trim_end() {
+            "" => note.to_string(),
+            output => format!("{}\n{}", output, note),
+        };
+    }
+    result
+}
+
+#[tauri::command]
+async fn get_shell_extensions() -> Result<Vec<context_menu::ShellExtension>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        Ok(context_menu::extensions(&read_shell_state()?))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Blocks a shell extension for the current user, or unblocks it in every
+/// Blocked list it is in. The machine-wide list needs elevation.
+#[tauri::command]
+async fn set_shell_extension_blocked(clsid: String, blocked: bool) -> CommandResult {
+    match set_shell_extension_blocked_inner(&clsid, blocked).await {
+        Ok(result) => result,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn set_shell_extension_blocked_inner(clsid: &str, blocked: bool) -> Result<CommandResult, String> {
+    if !context_menu::is_clsid(clsid) {
+        return Err(format!("Invalid CLSID: {}", clsid));
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        let state = read_shell_state()?;
+        
+        let script = if blocked {
+            let name = context_menu::extensions(&state)
+                .into_iter()
+                .find(|extension| extension.clsid.eq_ignore_ascii_case(clsid))
+                .map(|extension| extension.name)
+                .unwrap_or_default();
+            context_menu::block_script(clsid, &name)
+        } else {
+            let mut statements = vec![context_menu::unblock_script(clsid, context_menu::BlockScope::User)];
+            if state.blocked_in(clsid, context_menu::BlockScope::Machine).is_some() {
+                elevation::ensure_elevated("Unblocking a shell extension for all users")?;
+                statements.push(context_menu::unblock_script(clsid, context_menu::BlockScope::Machine));
+            }
+            statements.join("; ")
+        };
+        
+        Ok(with_explorer_note(execute_command(script, false).await))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = blocked;
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+/// Applies or rolls back the feature changes of a feature item and reports
+/// whether Windows needs a restart to finish them.
+#[tauri::command]
+async fn apply_feature_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> features::FeatureResult {
+    if let Err(e) = admit(&app, &id, is_rollback, ticket.as_deref()) {
+        return features::FeatureResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+            restart_needed: false,
+        };
+    }
+    
+    let item = match load_items(category, None, app) {
+        Ok(items) => items.into_iter().find(|item| item.id == id),
+        Err(e) => {
+            return features::FeatureResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+                restart_needed: false,
+            };
+        }
+    };
+    
+    let Some(item) = item.filter(|item| !item.features.is_empty()) else {
+        return features::FeatureResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("Not a feature item: {}", id)),
+            restart_needed: false,
+        };
+    };
+    
+    let script = if is_rollback {
+        item.rollback_command.unwrap_or_default()
+    } else {
+        item.command
+    };
+    let result = execute_command(script, is_rollback).await;
+    
+    features::FeatureResult {
+        restart_needed: result.success && features::parse_restart_needed(&result.output),
+        success: result.success,
+        output: result.output,
+        error: result.error,
+    }
+}
+
+/// Adds the hostnames of a hosts item to the managed block of the hosts file,
+/// or takes them out again on rollback.
+#[tauri::command]
+async fn apply_hosts_item(
+    app: tauri::AppHandle,
+    category: String,
+    id: String,
+    is_rollback: bool,
+    ticket: Option<String>,
+) -> CommandResult {
+    match apply_hosts_item_inner(app, category, &id, is_rollback, ticket.as_deref()).await {
+        Ok(output) => CommandResult {
+            success: true,
+            output,
+            error: None,
+        },
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+async fn apply_hosts_item_inner(
+    app: tauri::AppHandle,
+    category: String,
+    id: &str,
+    is_rollback: bool,
+    ticket: Option<&str>,
+) -> Result<String, String> {
+    admit(&app, id, is_rollback, ticket)?;
+    
+    #[cfg(target_os = "windows")]
+    {
+        let item = load_items(category, None, app)?
+            .into_iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Item not found: {}", id))?;
+        if item.hosts.is_empty() {
+            return Err(format!("{} has no hostnames to block", item.name));
+        }
+        
+        elevation::ensure_elevated("Editing the hosts file")?;
+        
+        let content = read_hosts_file()?;
+        let blocked = hosts::blocked_hosts(&content)?;
+        let desired = if is_rollback {
+            hosts::without(&blocked, &item.hosts)
+        } else {
+            hosts::merged(&blocked, &item.hosts)
+        };
+        
+        let updated = hosts::write_block(&content, &desired)?;
+        if updated != content {
+            fs::write(hosts::hosts_path(), updated)
+                .map_err(|e| format!("Failed to write hosts file: {}", e))?;
+            // Cached lookups would keep resolving until they expire.
+            let _ = powershell::run("Clear-DnsClientCache");
+        }
+        
+        Ok(if is_rollback {
+            format!("Unblocked {} hostname(s)", blocked.len() - desired.len())
+        } else {
+            format!("Blocking {} hostname(s)", desired.len())
+        })
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+/// The running system for applicability checks. Without system info every
+/// item is treated as applicable; `get_system_info` reports the failure.
+fn current_platform(app: &tauri::AppHandle) -> applicability::Platform {
+    app.state::<system_info::SystemInfoCache>()
+        .get()
+        .map(|info| info.platform())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+async fn get_system_info(cache: tauri::State<'_, system_info::SystemInfoCache>) -> Result<system_info::SystemInfo, String> {
+    cache.get()
+}
+
+/// Reads the system info again instead of returning the cached copy.
+#[tauri::command]
+async fn refresh_system_info(cache: tauri::State<'_, system_info::SystemInfoCache>) -> Result<system_info::SystemInfo, String> {
+    cache.refresh()
+}
+
+#[tauri::command]
+async fn get_startup_entries() -> Result<Vec<startup::StartupEntry>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let output = powershell::run(startup::STARTUP_ENTRIES_SCRIPT)
+            .map_err(|e| format!("Failed to read startup entries: {}", e))?;
+        startup::parse_entries(&String::from_utf8_lossy(&output.stdout))
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+#[tauri::command]
+async fn set_startup_entry_enabled(id: String, enabled: bool) -> CommandResult {
+    let entry = match get_startup_entries().await {
+        Ok(entries) => entries.into_iter().find(|entry| entry.id == id),
+        Err(e) => {
+            return CommandResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            };
+        }
+    };
+    
+    let Some(entry) = entry else {
+        return CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("Unknown startup entry: {}", id)),
+        };
+    };
+    
+    if entry.requires_admin {
+        if let Err(e) = elevation::ensure_elevated("Changing startup entries for all users") {
+            return CommandResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            };
+        }
+    }
+    
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let filetime = startup::filetime_from_unix(now.as_secs(), now.subsec_nanos());
+    
+    match startup::set_enabled_script(&entry, enabled, filetime) {
+        Ok(script) => execute_command(script, false).await,
+        Err(e) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(e),
+        },
+    }
+}
+
+#[tauri::command]
+fn get_privilege_status() -> elevation::PrivilegeStatus {
+    elevation::privilege_status()
+}
+
+#[tauri::command]
+fn relaunch_as_admin(app: tauri::AppHandle) -> Result<(), String> {
+    elevation::relaunch_elevated()?;
+    app.exit(0);
+    Ok(())
 }
 
 #[tauri::command]
@@ -456,15 +2247,52 @@ fn test_command() -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(system_info::SystemInfoCache::default())
+        .manage(risk::Confirmations::default())
+        .manage(batch::Tickets::default())
+        .manage(winget::ExportCache::default())
         .invoke_handler(tauri::generate_handler![
             load_items,
             load_presets,
+            search_items,
+            check_translations,
+            get_item_status,
+            preflight,
+            plan_batch,
+            begin_apply,
+            confirm_critical_items,
+            get_risk_policy,
+            set_risk_policy,
+            finish_apply,
+            get_pending_effects,
+            restart_explorer,
             get_installed_package_names,
+            get_package_inventory,
+            get_winget_packages,
             get_all_bloatware_with_status,
+            discover_unknown_packages,
+            promote_discovered_package,
+            remove_custom_app,
             remove_app,
-            execute_command,
-            execute_commands,
+            reinstall_app,
+            get_uninstall_entries,
+            get_win32_programs_with_status,
+            uninstall_win32_program,
+            execute_item,
+            execute_composite,
+            apply_policy_item,
+            apply_task_item,
+            apply_hosts_item,
+            apply_feature_item,
+            apply_context_menu_item,
+            get_shell_extensions,
+            set_shell_extension_blocked,
             get_system_info,
+            refresh_system_info,
+            get_startup_entries,
+            set_startup_entry_enabled,
+            get_privilege_status,
+            relaunch_as_admin,
             test_command
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The language the catalog is written in, used for anything a string table
+/// leaves out.
+pub const SOURCE_LOCALE: &str = "en";
+
+/// Which part of the catalog a translated text belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    Items,
+    Apps,
+    Presets,
+    Programs,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Text {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Translated names and descriptions of one locale, keyed by id, as stored in
+/// `data/locales/<locale>.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StringTable {
+    pub items: BTreeMap<String, Text>,
+    pub apps: BTreeMap<String, Text>,
+    pub presets: BTreeMap<String, Text>,
+    pub programs: BTreeMap<String, Text>,
+}
+
+fn translated(text: &Option<String>) -> Option<&str> {
+    text.as_deref().map(str::trim).filter(|text| !text.is_empty())
+}
+
+impl StringTable {
+    fn section(&self, section: Section) -> &BTreeMap<String, Text> {
+        match section {
+            Section::Items => &self.items,
+            Section::Apps => &self.apps,
+            Section::Presets => &self.presets,
+            Section::Programs => &self.programs,
+        }
+    }
+
+    /// Replaces `name` and `description` with their translations, keeping
+    /// the English text for whatever is not translated.
+    pub fn localize(&self, section: Section, id: &str, name: &mut String, description: &mut String) {
+        let Some(text) = self.section(section).get(id) else {
+            return;
+        };
+        if let Some(translation) = translated(&text.name) {
+            *name = translation.to_string();
+        }
+        if let Some(translation) = translated(&text.description) {
+            *description = translation.to_string();
+        }
+    }
+}
+
+/// Lowercases `locale` and uses `-` as separator, refusing anything that is
+/// not a plain language tag since it ends up in a file name.
+pub fn normalize(locale: &str) -> Result<String, String> {
+    let normalized = locale.trim().to_lowercase().replace('_', "-");
+    let valid = !normalized.is_empty()
+        && normalized
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if valid {
+        Ok(normalized)
+    } else {
+        Err(format!("Invalid locale: {}", locale))
+    }
+}
+
+/// String tables to look for, the full tag (`de-at`) before the language
+/// (`de`). None for English.
+fn candidates(locale: &str) -> Result<Vec<String>, String> {
+    let locale = normalize(locale)?;
+    let language = locale.split('-').next().unwrap_or_default().to_string();
+    if language == SOURCE_LOCALE {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates = vec![locale.clone()];
+    if language != locale {
+        candidates.push(language);
+    }
+    Ok(candidates)
+}
+
+/// The string table for `locale` from `dir`. English and locales without a
+/// table get an empty table, so everything falls back to English.
+pub fn load_table(dir: &Path, locale: &str) -> Result<StringTable, String> {
+    for candidate in candidates(locale)? {
+        let path = dir.join(format!("{}.json", candidate));
+        if !path.exists() {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {} translations: {}", candidate, e))?;
+
+        return serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {} translations: {}", candidate, e));
+    }
+
+    Ok(StringTable::default())
+}
+
+/// Locales that have a string table in `dir`, sorted.
+pub fn available(dir: &Path) -> Result<Vec<String>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read locales directory: {}", e))?;
+
+    let mut locales = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read locales directory: {}", e))?
+            .path();
+        if path.extension().is_some_and(|extension| extension == "json") {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                locales.push(stem.to_string());
+            }
+        }
+    }
+    locales.sort();
+    Ok(locales)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct TextId {
+    pub section: Section,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingTranslation {
+    #[serde(flatten)]
+    pub text: TextId,
+    /// `name`, `description` or both.
+    pub fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationReport {
+    pub locale: String,
+    pub total: usize,
+    pub translated: usize,
+    pub missing: Vec<MissingTranslation>,
+    /// Translations of ids the catalog no longer has.
+    pub unknown: Vec<TextId>,
+}
+
+/// Compares `table` with the texts of the catalog.
+pub fn report(locale: &str, table: &StringTable, catalog: &[TextId]) -> TranslationReport {
+    let mut missing = Vec::new();
+    for text in catalog {
+        let translation = table.section(text.section).get(&text.id);
+        let mut fields = Vec::new();
+        if translation.and_then(|t| translated(&t.name)).is_none() {
+            fields.push("name");
+        }
+        if translation.and_then(|t| translated(&t.description)).is_none() {
+            fields.push("description");
+        }
+        if !fields.is_empty() {
+            missing.push(MissingTranslation {
+                text: text.clone(),
+                fields,
+            });
+        }
+    }
+
+    let mut unknown = Vec::new();
+    for section in [Section::Items, Section::Apps, Section::Presets, Section::Programs] {
+        for id in table.section(section).keys() {
+            if !catalog.iter().any(|text| text.section == section && &text.id == id) {
+                unknown.push(TextId {
+                    section,
+                    id: id.clone(),
+                });
+            }
+        }
+    }
+
+    TranslationReport {
+        locale: locale.to_string(),
+        total: catalog.len(),
+        translated: catalog.len() - missing.len(),
+        missing,
+        unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> StringTable {
+        serde_json::from_str(
+            r#"{
+                "items": {
+                    "disable-telemetry": { "name": "Telemetrie deaktivieren", "description": "Schaltet die Telemetrie ab" },
+                    "disable-cortana": { "name": "Cortana deaktivieren" },
+                    "removed-item": { "name": "Entfernt" }
+                },
+                "presets": {
+                    "minimal": { "name": "Minimal", "description": " " }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn text(section: Section, id: &str) -> TextId {
+        TextId {
+            section,
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn translates_and_falls_back_to_english() {
+        let table = table();
+        let mut name = "Disable Cortana".to_string();
+        let mut description = "Disable Cortana voice assistant".to_string();
+
+        table.localize(Section::Items, "disable-cortana", &mut name, &mut description);
+        assert_eq!(name, "Cortana deaktivieren");
+        assert_eq!(description, "Disable Cortana voice assistant");
+
+        let mut name = "Cortana".to_string();
+        table.localize(Section::Apps, "disable-cortana", &mut name, &mut description);
+        assert_eq!(name, "Cortana");
+    }
+
+    #[test]
+    fn normalizes_locales() {
+        assert_eq!(normalize("de_DE").unwrap(), "de-de");
+        assert_eq!(normalize(" ja ").unwrap(), "ja");
+        assert!(normalize("../de").is_err());
+        assert!(normalize("de-").is_err());
+        assert!(normalize("").is_err());
+    }
+
+    #[test]
+    fn falls_back_from_region_to_language() {
+        assert_eq!(candidates("de-AT").unwrap(), ["de-at", "de"]);
+        assert_eq!(candidates("ja").unwrap(), ["ja"]);
+        assert!(candidates("en-US").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_missing_and_unknown_translations() {
+        let catalog = [
+            text(Section::Items, "disable-telemetry"),
+            text(Section::Items, "disable-cortana"),
+            text(Section::Apps, "cortana"),
+            text(Section::Presets, "minimal"),
+        ];
+
+        let report = report("de", &table(), &catalog);
+
+        assert_eq!(report.total, 4);
+        assert_eq!(report.translated, 1);
+        assert_eq!(
+            report.missing,
+            [
+                MissingTranslation {
+                    text: text(Section::Items, "disable-cortana"),
+                    fields: vec!["description"],
+                },
+                MissingTranslation {
+                    text: text(Section::Apps, "cortana"),
+                    fields: vec!["name", "description"],
+                },
+                MissingTranslation {
+                    text: text(Section::Presets, "minimal"),
+                    fields: vec!["description"],
+                },
+            ]
+        );
+        assert_eq!(report.unknown, [text(Section::Items, "removed-item")]);
+    }
+
+    #[test]
+    fn programs_have_their_own_section() {
+        let table: StringTable = serde_json::from_str(
+            r#"{
+                "apps": {
+                    "expressvpn": { "name": "ExpressVPN (App)", "description": "VPN-App" }
+                },
+                "programs": {
+                    "expressvpn": { "name": "ExpressVPN", "description": "VPN-Client vom Hersteller" },
+                    "dropped-program": { "name": "Entfernt" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let mut name = "ExpressVPN".to_string();
+        let mut description = "VPN client bundled as an OEM trial".to_string();
+
+        table.localize(Section::Programs, "expressvpn", &mut name, &mut description);
+        assert_eq!(description, "VPN-Client vom Hersteller");
+
+        let report = report("de", &table, &[text(Section::Programs, "expressvpn")]);
+        assert!(report.missing.is_empty());
+        assert_eq!(
+            report.unknown,
+            [text(Section::Apps, "expressvpn"), text(Section::Programs, "dropped-program")]
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use serde::Deserialize;
+
+/// Identity of an Appx package as reported by `Get-AppxPackage` (installed) or
+/// `Get-AppxProvisionedPackage` (staged for new users).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageIdentity {
+    pub name: String,
+    pub family_name: String,
+    pub full_name: String,
+    /// SIDs of users the package is installed for, when known.
+    pub user_sids: Vec<String>,
+    /// Folder holding the package files, when known.
+    pub install_location: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawInstalledPackage {
+    name: String,
+    package_family_name: String,
+    package_full_name: String,
+    #[serde(default)]
+    users: Vec<String>,
+    #[serde(default)]
+    install_location: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawProvisionedPackage {
+    display_name: String,
+    package_name: String,
+}
+
+/// PowerShell's `ConvertTo-Json` emits a bare object for a single result and
+/// nothing at all for none, so accept all three shapes.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+pub fn parse_json_list<T: for<'de> Deserialize<'de>>(json: &str) -> Result<Vec<T>, String> {
+    let json = json.trim();
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match serde_json::from_str::<OneOrMany<T>>(json)
+        .map_err(|e| format!("Failed to parse PowerShell JSON: {}", e))?
+    {
+        OneOrMany::Many(items) => Ok(items),
+        OneOrMany::One(item) => Ok(vec![item]),
+    }
+}
+
+/// Parses `Get-AppxPackage | Select-Object Name, PackageFamilyName, PackageFullName, InstallLocation | ConvertTo-Json`.
+pub fn parse_installed(json: &str) -> Result<Vec<PackageIdentity>, String> {
+    Ok(parse_json_list::<RawInstalledPackage>(json)?
+        .into_iter()
+        .map(|raw| PackageIdentity {
+            name: raw.name,
+            family_name: raw.package_family_name,
+            full_name: raw.package_full_name,
+            user_sids: raw.users,
+            install_location: raw.install_location.filter(|location| !location.is_empty()),
+        })
+        .collect())
+}
+
+/// Parses `Get-AppxProvisionedPackage -Online | Select-Object DisplayName, PackageName | ConvertTo-Json`.
+/// Provisioned packages carry no family name, so it is rebuilt from the
+/// `Name_Version_Arch_ResourceId_PublisherId` full name.
+pub fn parse_provisioned(json: &str) -> Result<Vec<PackageIdentity>, String> {
+    Ok(parse_json_list::<RawProvisionedPackage>(json)?
+        .into_iter()
+        .map(|raw| {
+            let publisher_id = raw.package_name.rsplit('_').next().unwrap_or_default();
+            PackageIdentity {
+                family_name: format!("{}_{}", raw.display_name, publisher_id),
+                name: raw.display_name,
+                full_name: raw.package_name,
+                user_sids: Vec::new(),
+                install_location: None,
+            }
+        })
+        .collect())
+}
+
+/// A catalog package pattern. Plain names must match exactly, `*`/`?`/`[...]`
+/// behave like PowerShell's `-like`, and a pattern containing `_` is matched
+/// against the publisher-qualified PackageFamilyName instead of the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackagePattern {
+    pattern: String,
+    qualified: bool,
+}
+
+impl PackagePattern {
+    pub fn new(pattern: &str) -> Self {
+        let pattern = pattern.trim();
+        PackagePattern {
+            qualified: pattern.contains('_'),
+            pattern: pattern.to_string(),
+        }
+    }
+
+    pub fn matches(&self, package: &PackageIdentity) -> bool {
+        let target = if self.qualified { &package.family_name } else { &package.name };
+        wildcard_match(&self.pattern, target)
+    }
+
+    /// Full names of every package in `packages` matched by this pattern.
+    pub fn matching<'a, I>(&self, packages: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a PackageIdentity>,
+    {
+        packages
+            .into_iter()
+            .filter(|package| self.matches(package))
+            .map(|package| package.full_name.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(char),
+    AnyOne,
+    AnyMany,
+    Set(Vec<(char, char)>),
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => match chars.next() {
+                Some(escaped) => tokens.push(Token::Literal(escaped)),
+                None => tokens.push(Token::Literal('`')),
+            },
+            '*' => {
+                if tokens.last() != Some(&Token::AnyMany) {
+                    tokens.push(Token::AnyMany);
+                }
+            }
+            '?' => tokens.push(Token::AnyOne),
+            '[' => {
+                let rest = chars.clone();
+                let mut ranges = Vec::new();
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    if c == ']' {
+                        closed = true;
+                        break;
+                    }
+                    let start = if c == '`' { chars.next().unwrap_or('`') } else { c };
+                    if chars.peek() == Some(&'-') {
+                        chars.next();
+                        match chars.next() {
+                            Some(']') => {
+                                ranges.push((start, start));
+                                ranges.push(('-', '-'));
+                                closed = true;
+                                break;
+                            }
+                            Some(end) => ranges.push((start, end)),
+                            None => ranges.push((start, start)),
+                        }
+                    } else {
+                        ranges.push((start, start));
+                    }
+                }
+                if closed {
+                    tokens.push(Token::Set(ranges));
+                } else {
+                    // An unterminated bracket is not a set: match the `[`
+                    // literally and read what follows it as pattern again.
+                    tokens.push(Token::Literal('['));
+                    chars = rest;
+                }
+            }
+            c => tokens.push(Token::Literal(c)),
+        }
+    }
+
+    tokens
+}
+
+fn char_eq(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn token_matches(token: &Token, c: char) -> bool {
+    match token {
+        Token::Literal(l) => char_eq(*l, c),
+        Token::AnyOne => true,
+        Token::AnyMany => true,
+        Token::Set(ranges) => {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            let upper = c.to_uppercase().next().unwrap_or(c);
+            ranges.iter().any(|&(start, end)| {
+                (start..=end).contains(&c) || (start..=end).contains(&lower) || (start..=end).contains(&upper)
+            })
+        }
+    }
+}
+
+/// Case-insensitive PowerShell `-like` semantics: `*`, `?`, `[abc]`, `[a-z]`
+/// and backtick escapes. The whole of `text` must match.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let tokens = tokenize(pattern);
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match tokens.get(p) {
+            Some(Token::AnyMany) => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(token) if token_matches(token, text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[p..].iter().all(|token| *token == Token::AnyMany)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::{Strings, CASES};
+    use crate::powershell::escape_wildcards;
+
+    /// Package-name characters plus everything the matcher treats specially.
+    const ALPHABET: &[char] = &['a', 'B', 'c', 'Z', '1', '.', '-', '_', '~', '*', '?', '[', ']', '`', ' ', 'é', 'É'];
+
+    fn package(name: &str, family_name: &str) -> PackageIdentity {
+        PackageIdentity {
+            name: name.to_string(),
+            family_name: family_name.to_string(),
+            full_name: format!("{}_1.0.0.0_x64__{}", name, family_name.rsplit('_').next().unwrap_or_default()),
+            user_sids: Vec::new(),
+            install_location: None,
+        }
+    }
+
+    #[test]
+    fn plain_names_match_exactly_ignoring_case() {
+        assert!(wildcard_match("Microsoft.MSPaint", "microsoft.mspaint"));
+        assert!(!wildcard_match("Microsoft.MSPaint", "Microsoft.MSPaint3D"));
+        assert!(!wildcard_match("Microsoft.MSPaint", "Microsoft.MSPain"));
+    }
+
+    #[test]
+    fn star_matches_any_run() {
+        assert!(wildcard_match("*EclipseManager", "D52A8D61.EclipseManager"));
+        assert!(wildcard_match("ActiproSoftwareLLC.*", "ActiproSoftwareLLC.562882FEEB491"));
+        assert!(wildcard_match("a*b*c", "aXXbYYc"));
+        assert!(!wildcard_match("a*b*c", "aXXbYY"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("**", "anything"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(!wildcard_match("a?c", "ac"));
+        assert!(!wildcard_match("a?c", "abbc"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(wildcard_match("[a-c]x", "Bx"));
+        assert!(!wildcard_match("[a-c]x", "dx"));
+        assert!(wildcard_match("Microsoft.Xbox[GT]*", "Microsoft.XboxGameOverlay"));
+        assert!(!wildcard_match("Microsoft.Xbox[GT]*", "Microsoft.XboxApp"));
+        assert!(wildcard_match("v[1-]", "v-"));
+    }
+
+    #[test]
+    fn backtick_escapes_wildcards() {
+        assert!(wildcard_match("a`*b", "a*b"));
+        assert!(!wildcard_match("a`*b", "axb"));
+        assert!(wildcard_match("a`[b]", "a[b]"));
+    }
+
+    #[test]
+    fn unterminated_bracket_is_literal() {
+        assert!(wildcard_match("[ab", "[ab"));
+        assert!(!wildcard_match("[ab", "a"));
+        assert!(wildcard_match("a[b-c*", "a[b-c.anything"));
+        assert!(wildcard_match("a[?", "a[x"));
+    }
+
+    #[test]
+    fn escaped_patterns_match_only_themselves() {
+        let mut strings = Strings::new(27);
+        for _ in 0..CASES {
+            let value = strings.string(ALPHABET, 8);
+            let other = strings.string(ALPHABET, 8);
+            let pattern = escape_wildcards(&value);
+
+            assert!(wildcard_match(&pattern, &value), "{:?} does not match itself", pattern);
+            assert_eq!(
+                wildcard_match(&pattern, &other),
+                value.to_lowercase() == other.to_lowercase(),
+                "{:?} against {:?}",
+                pattern,
+                other
+            );
+        }
+    }
+
+    #[test]
+    fn starred_patterns_match_any_text_containing_them() {
+        let mut strings = Strings::new(28);
+        for _ in 0..CASES {
+            let value = strings.string(ALPHABET, 6);
+            let text = format!(
+                "{}{}{}",
+                strings.string(ALPHABET, 6),
+                value,
+                strings.string(ALPHABET, 6)
+            );
+            let pattern = format!("*{}*", escape_wildcards(&value));
+
+            assert!(wildcard_match(&pattern, &text), "{:?} against {:?}", pattern, text);
+        }
+    }
+
+    #[test]
+    fn matching_ignores_ascii_case() {
+        let ascii: Vec<char> = ALPHABET.iter().copied().filter(char::is_ascii).collect();
+        let mut strings = Strings::new(29);
+        for _ in 0..CASES {
+            let value = strings.string(&ascii, 12);
+            let pattern = escape_wildcards(&value);
+
+            assert!(wildcard_match(&pattern, &value.to_ascii_uppercase()), "{:?}", value);
+            assert!(wildcard_match(&pattern, &value.to_ascii_lowercase()), "{:?}", value);
+            assert!(wildcard_match(&pattern.to_ascii_uppercase(), &value), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn qualified_patterns_match_the_family_name() {
+        let paint = package("Microsoft.MSPaint", "Microsoft.MSPaint_8wekyb3d8bbwe");
+        let impostor = package("Microsoft.MSPaint", "Microsoft.MSPaint_1234567890abc");
+
+        let qualified = PackagePattern::new("Microsoft.MSPaint_8wekyb3d8bbwe");
+        assert!(qualified.matches(&paint));
+        assert!(!qualified.matches(&impostor));
+
+        let plain = PackagePattern::new(" Microsoft.MSPaint ");
+        assert!(plain.matches(&paint));
+        assert!(plain.matches(&impostor));
+    }
+
+    #[test]
+    fn collects_full_names_of_matches() {
+        let packages = [
+            package("Microsoft.XboxGameOverlay", "Microsoft.XboxGameOverlay_8wekyb3d8bbwe"),
+            package("Microsoft.XboxApp", "Microsoft.XboxApp_8wekyb3d8bbwe"),
+            package("Microsoft.BingNews", "Microsoft.BingNews_8wekyb3d8bbwe"),
+        ];
+
+        assert_eq!(
+            PackagePattern::new("Microsoft.Xbox*").matching(&packages),
+            [
+                "Microsoft.XboxGameOverlay_1.0.0.0_x64__8wekyb3d8bbwe",
+                "Microsoft.XboxApp_1.0.0.0_x64__8wekyb3d8bbwe",
+            ]
+        );
+        assert!(PackagePattern::new("Microsoft.Xbox").matching(&packages).is_empty());
+    }
+
+    #[test]
+    fn parses_single_and_multiple_packages() {
+        let one = parse_installed(
+            r#"{"Name":"Microsoft.BingNews","PackageFamilyName":"Microsoft.BingNews_8wekyb3d8bbwe","PackageFullName":"Microsoft.BingNews_4.1.0.0_x64__8wekyb3d8bbwe"}"#,
+        )
+        .unwrap();
+        assert_eq!(one.len(), 1);
+        assert!(one[0].user_sids.is_empty());
+
+        assert!(parse_installed("").unwrap().is_empty());
+
+        let provisioned = parse_provisioned(
+            r#"[{"DisplayName":"Microsoft.BingNews","PackageName":"Microsoft.BingNews_4.1.0.0_neutral_~_8wekyb3d8bbwe"}]"#,
+        )
+        .unwrap();
+        assert_eq!(provisioned[0].family_name, "Microsoft.BingNews_8wekyb3d8bbwe");
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::{self, PackageIdentity, PackagePattern};
+use crate::powershell;
+
+/// Where an Appx package can live. Removing from one scope leaves the others
+/// untouched, e.g. deprovisioning keeps the app for users who already have it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalScope {
+    CurrentUser,
+    AllUsers,
+    Provisioned,
+}
+
+impl RemovalScope {
+    /// Scopes used when the caller does not pick any, matching the historical
+    /// "remove everywhere" behaviour of remove_app.
+    pub fn defaults() -> Vec<RemovalScope> {
+        vec![RemovalScope::AllUsers, RemovalScope::Provisioned]
+    }
+
+    pub fn requires_admin(self) -> bool {
+        self != RemovalScope::CurrentUser
+    }
+}
+
+/// Script emitting a [`PackageSnapshot`] as JSON. Other users and provisioned
+/// packages are only visible to an elevated process.
+pub fn snapshot_script(elevated: bool) -> &'static str {
+    if elevated {
+        "$me = [Security.Principal.WindowsIdentity]::GetCurrent().User.Value; \
+         $installed = @(Get-AppxPackage -AllUsers | ForEach-Object { [pscustomobject]@{ Name = $_.Name; PackageFamilyName = $_.PackageFamilyName; PackageFullName = $_.PackageFullName; InstallLocation = $_.InstallLocation; Users = @($_.PackageUserInformation | Where-Object { \"$($_.InstallState)\" -eq 'Installed' } | ForEach-Object { $_.UserSecurityId.Sid }) } }); \
+         $provisioned = @(Get-AppxProvisionedPackage -Online | Select-Object DisplayName, PackageName); \
+         [pscustomobject]@{ CurrentUserSid = $me; Installed = $installed; Provisioned = $provisioned } | ConvertTo-Json -Depth 4 -Compress"
+    } else {
+        "$installed = @(Get-AppxPackage | Select-Object Name, PackageFamilyName, PackageFullName, InstallLocation); \
+         [pscustomobject]@{ CurrentUserSid = $null; Installed = $installed; Provisioned = $null } | ConvertTo-Json -Depth 4 -Compress"
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawSnapshot {
+    current_user_sid: Option<String>,
+    installed: serde_json::Value,
+    provisioned: Option<serde_json::Value>,
+}
+
+/// Appx state of the machine. Without elevation only the current user's
+/// packages are known, in which case `current_user_sid` and `provisioned` are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct PackageSnapshot {
+    pub current_user_sid: Option<String>,
+    pub installed: Vec<PackageIdentity>,
+    pub provisioned: Option<Vec<PackageIdentity>>,
+}
+
+impl PackageSnapshot {
+    pub fn parse(json: &str) -> Result<PackageSnapshot, String> {
+        let json = json.trim();
+        if json.is_empty() {
+            return Ok(PackageSnapshot::default());
+        }
+
+        let raw: RawSnapshot = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse package snapshot: {}", e))?;
+
+        Ok(PackageSnapshot {
+            current_user_sid: raw.current_user_sid,
+            installed: package_match::parse_installed(&raw.installed.to_string())?,
+            provisioned: match raw.provisioned {
+                Some(value) if !value.is_null() => {
+                    Some(package_match::parse_provisioned(&value.to_string())?)
+                }
+                _ => None,
+            },
+        })
+    }
+
+    fn is_current_user(&self, package: &PackageIdentity) -> bool {
+        match &self.current_user_sid {
+            Some(sid) => package.user_sids.iter().any(|s| s == sid),
+            // Unelevated snapshots only list the current user's packages.
+            None => true,
+        }
+    }
+
+    fn is_other_user(&self, package: &PackageIdentity) -> bool {
+        match &self.current_user_sid {
+            Some(sid) => package.user_sids.iter().any(|s| s != sid),
+            None => false,
+        }
+    }
+
+    pub fn status(&self, pattern: &PackagePattern) -> ScopeStatus {
+        let matched: Vec<&PackageIdentity> =
+            self.installed.iter().filter(|p| pattern.matches(p)).collect();
+
+        ScopeStatus {
+            current_user: matched
+                .iter()
+                .filter(|p| self.is_current_user(p))
+                .map(|p| p.full_name.clone())
+                .collect(),
+            other_users: self.current_user_sid.as_ref().map(|_| {
+                matched
+                    .iter()
+                    .filter(|p| self.is_other_user(p))
+                    .map(|p| p.full_name.clone())
+                    .collect()
+            }),
+            provisioned: self.provisioned.as_ref().map(|provisioned| pattern.matching(provisioned)),
+        }
+    }
+
+    /// PowerShell statements removing every package matched by `pattern` from
+    /// the requested scopes. Empty when nothing matches.
+    pub fn removal_statements(&self, pattern: &PackagePattern, scopes: &[RemovalScope]) -> Vec<String> {
+        let status = self.status(pattern);
+        let mut statements = Vec::new();
+
+        if scopes.contains(&RemovalScope::AllUsers) {
+            let mut all: Vec<&String> = status.current_user.iter().collect();
+            all.extend(status.other_users.iter().flatten());
+            all.sort();
+            all.dedup();
+            statements.extend(all.into_iter().map(|name| {
+                format!("Remove-AppxPackage -Package {} -AllUsers", powershell::quote(name))
+            }));
+        } else if scopes.contains(&RemovalScope::CurrentUser) {
+            statements.extend(status.current_user.iter().map(|name| {
+                format!("Remove-AppxPackage -Package {}", powershell::quote(name))
+            }));
+        }
+
+        if scopes.contains(&RemovalScope::Provisioned) {
+            statements.extend(status.provisioned.iter().flatten().map(|name| {
+                format!(
+                    "Remove-AppxProvisionedPackage -Online -PackageName {} -AllUsers | Out-Null",
+                    powershell::quote(name)
+                )
+            }));
+        }
+
+        statements
+    }
+}
+
+/// Concrete package full names matched in each scope. `None` means the scope
+/// could not be inspected because the process is not elevated.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScopeStatus {
+    pub current_user: Vec<String>,
+    pub other_users: Option<Vec<String>>,
+    pub provisioned: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ME: &str = "S-1-5-21-1000";
+    const OTHER: &str = "S-1-5-21-1001";
+
+    fn elevated() -> PackageSnapshot {
+        PackageSnapshot::parse(&format!(
+            r#"{{
+                "CurrentUserSid": "{ME}",
+                "Installed": [
+                    {{ "Name": "Microsoft.BingNews", "PackageFamilyName": "Microsoft.BingNews_8wekyb3d8bbwe", "PackageFullName": "Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe", "Users": ["{ME}", "{OTHER}"] }},
+                    {{ "Name": "Microsoft.BingWeather", "PackageFamilyName": "Microsoft.BingWeather_8wekyb3d8bbwe", "PackageFullName": "Microsoft.BingWeather_4.5_x64__8wekyb3d8bbwe", "Users": ["{OTHER}"] }}
+                ],
+                "Provisioned": {{ "DisplayName": "Microsoft.BingNews", "PackageName": "Microsoft.BingNews_4.1_neutral_~_8wekyb3d8bbwe" }}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn unelevated() -> PackageSnapshot {
+        PackageSnapshot::parse(
+            r#"{
+                "CurrentUserSid": null,
+                "Installed": { "Name": "Microsoft.BingNews", "PackageFamilyName": "Microsoft.BingNews_8wekyb3d8bbwe", "PackageFullName": "Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe" },
+                "Provisioned": null
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_every_scope_when_elevated() {
+        let news = elevated().status(&PackagePattern::new("Microsoft.BingNews"));
+        assert_eq!(news.current_user, ["Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe"]);
+        assert_eq!(news.other_users.unwrap(), ["Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe"]);
+        assert_eq!(news.provisioned.unwrap(), ["Microsoft.BingNews_4.1_neutral_~_8wekyb3d8bbwe"]);
+
+        let weather = elevated().status(&PackagePattern::new("Microsoft.BingWeather"));
+        assert!(weather.current_user.is_empty());
+        assert_eq!(weather.other_users.unwrap(), ["Microsoft.BingWeather_4.5_x64__8wekyb3d8bbwe"]);
+        assert!(weather.provisioned.unwrap().is_empty());
+    }
+
+    #[test]
+    fn unelevated_snapshots_only_know_the_current_user() {
+        let status = unelevated().status(&PackagePattern::new("Microsoft.Bing*"));
+
+        assert_eq!(status.current_user, ["Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe"]);
+        assert!(status.other_users.is_none());
+        assert!(status.provisioned.is_none());
+        assert!(PackageSnapshot::parse("").unwrap().installed.is_empty());
+    }
+
+    #[test]
+    fn removes_for_the_current_user_only() {
+        let statements = elevated().removal_statements(&PackagePattern::new("Microsoft.Bing*"), &[RemovalScope::CurrentUser]);
+
+        assert_eq!(statements, ["Remove-AppxPackage -Package 'Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe'"]);
+    }
+
+    #[test]
+    fn removes_for_all_users_once_per_package() {
+        let statements = elevated().removal_statements(
+            &PackagePattern::new("Microsoft.Bing*"),
+            &[RemovalScope::CurrentUser, RemovalScope::AllUsers],
+        );
+
+        assert_eq!(
+            statements,
+            [
+                "Remove-AppxPackage -Package 'Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe' -AllUsers",
+                "Remove-AppxPackage -Package 'Microsoft.BingWeather_4.5_x64__8wekyb3d8bbwe' -AllUsers",
+            ]
+        );
+    }
+
+    #[test]
+    fn deprovisions_without_touching_installed_copies() {
+        let statements = elevated().removal_statements(&PackagePattern::new("Microsoft.BingNews"), &[RemovalScope::Provisioned]);
+
+        assert_eq!(
+            statements,
+            ["Remove-AppxProvisionedPackage -Online -PackageName 'Microsoft.BingNews_4.1_neutral_~_8wekyb3d8bbwe' -AllUsers | Out-Null"]
+        );
+    }
+
+    #[test]
+    fn default_scopes_remove_everywhere() {
+        let statements = elevated().removal_statements(&PackagePattern::new("Microsoft.BingNews"), &RemovalScope::defaults());
+
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("-AllUsers"));
+        assert!(statements[1].starts_with("Remove-AppxProvisionedPackage"));
+        assert!(RemovalScope::defaults().iter().all(|scope| scope.requires_admin()));
+        assert!(!RemovalScope::CurrentUser.requires_admin());
+    }
+
+    #[test]
+    fn nothing_to_remove_without_matches_or_scopes() {
+        assert!(elevated().removal_statements(&PackagePattern::new("Microsoft.Xbox*"), &RemovalScope::defaults()).is_empty());
+        assert!(elevated().removal_statements(&PackagePattern::new("Microsoft.BingNews"), &[]).is_empty());
+        // Unknown provisioned state removes nothing rather than guessing.
+        assert!(unelevated().removal_statements(&PackagePattern::new("Microsoft.BingNews"), &[RemovalScope::Provisioned]).is_empty());
+    }
+}
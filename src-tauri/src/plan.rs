@@ -0,0 +1,421 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How a catalog item relates to other items, by id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Relations {
+    /// Items added to the plan along with this one and run before it.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Items that must not be applied in the same batch. Declaring a conflict
+    /// on either side is enough.
+    #[serde(default)]
+    pub conflicts_with: Vec<String>,
+    /// Items run before this one when both are in the plan. Unlike
+    /// `requires`, they are not added.
+    #[serde(default)]
+    pub run_after: Vec<String>,
+}
+
+impl Relations {
+    fn before(&self) -> impl Iterator<Item = &String> {
+        self.requires.iter().chain(&self.run_after)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStep {
+    pub id: String,
+    /// The item that pulled this one in through `requires`, `None` when it
+    /// was selected.
+    pub required_by: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conflict {
+    pub id: String,
+    pub conflicts_with: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingItem {
+    pub id: String,
+    pub required_by: String,
+}
+
+/// A selection expanded into the order it runs in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    /// Dependencies run before the items that need them; otherwise the
+    /// selection order is kept.
+    pub steps: Vec<PlanStep>,
+    pub conflicts: Vec<Conflict>,
+    /// Required items that are not in the catalog.
+    pub missing: Vec<MissingItem>,
+    /// Ids around a dependency cycle, starting and ending with the same id.
+    /// Empty when there is none.
+    pub cycle: Vec<String>,
+}
+
+impl Plan {
+    pub fn is_runnable(&self) -> bool {
+        self.conflicts.is_empty() && self.missing.is_empty() && self.cycle.is_empty()
+    }
+
+    pub fn ids(&self) -> Vec<&str> {
+        self.steps.iter().map(|step| step.id.as_str()).collect()
+    }
+
+    /// Why the plan cannot run, `Ok` when it can.
+    pub fn ensure_runnable(&self) -> Result<(), String> {
+        let mut problems: Vec<String> = self
+            .conflicts
+            .iter()
+            .map(|conflict| format!("{} conflicts with {}", conflict.id, conflict.conflicts_with))
+            .collect();
+        problems.extend(
+            self.missing
+                .iter()
+                .map(|missing| format!("{} requires unknown item {}", missing.required_by, missing.id)),
+        );
+        if !self.cycle.is_empty() {
+            problems.push(format!("Dependency cycle: {}", self.cycle.join(" -> ")));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Cannot apply this selection: {}", problems.join("; ")))
+        }
+    }
+}
+
+/// Expands `selected` with everything it requires and orders the result.
+/// Ids that are not in `catalog`, such as apps, have no relations and keep
+/// their place in the selection.
+pub fn resolve(catalog: &BTreeMap<String, Relations>, selected: &[String]) -> Plan {
+    let mut plan = Plan::default();
+    let no_relations = Relations::default();
+    let relations = |id: &str| catalog.get(id).unwrap_or(&no_relations);
+
+    // Selected ids first, then whatever they require, breadth first.
+    let mut members: Vec<PlanStep> = Vec::new();
+    let mut seen = BTreeSet::new();
+    for id in selected {
+        if seen.insert(id.as_str()) {
+            members.push(PlanStep {
+                id: id.clone(),
+                required_by: None,
+            });
+        }
+    }
+    let mut next = 0;
+    while next < members.len() {
+        let id = members[next].id.clone();
+        for required in &relations(&id).requires {
+            if !catalog.contains_key(required) {
+                plan.missing.push(MissingItem {
+                    id: required.clone(),
+                    required_by: id.clone(),
+                });
+            } else if seen.insert(required.as_str()) {
+                members.push(PlanStep {
+                    id: required.clone(),
+                    required_by: Some(id.clone()),
+                });
+            }
+        }
+        next += 1;
+    }
+
+    let mut pairs = BTreeSet::new();
+    for step in &members {
+        for other in &relations(&step.id).conflicts_with {
+            if seen.contains(other.as_str()) && other != &step.id {
+                let pair = if step.id < *other {
+                    (step.id.clone(), other.clone())
+                } else {
+                    (other.clone(), step.id.clone())
+                };
+                pairs.insert(pair);
+            }
+        }
+    }
+    plan.conflicts = pairs
+        .into_iter()
+        .map(|(id, conflicts_with)| Conflict { id, conflicts_with })
+        .collect();
+
+    let by_id: BTreeMap<&str, &PlanStep> = members.iter().map(|step| (step.id.as_str(), step)).collect();
+    let mut sorter = Sorter {
+        before: |id: &str| {
+            relations(id)
+                .before()
+                .filter(|other| by_id.contains_key(other.as_str()))
+                .cloned()
+                .collect()
+        },
+        done: BTreeSet::new(),
+        path: Vec::new(),
+        order: Vec::new(),
+    };
+    for step in &members {
+        if let Err(cycle) = sorter.visit(&step.id) {
+            plan.cycle = cycle;
+            break;
+        }
+    }
+    // After a cycle the rest keeps the selection order, so the plan still
+    // lists everything.
+    for step in &members {
+        if !sorter.done.contains(&step.id) {
+            sorter.done.insert(step.id.clone());
+            sorter.order.push(step.id.clone());
+        }
+    }
+
+    plan.steps = sorter.order.iter().map(|id| by_id[id.as_str()].clone()).collect();
+    plan
+}
+
+/// Depth-first topological sort that reports the first cycle it runs into.
+struct Sorter<F: Fn(&str) -> Vec<String>> {
+    before: F,
+    done: BTreeSet<String>,
+    /// Ids being visited, outermost first.
+    path: Vec<String>,
+    order: Vec<String>,
+}
+
+impl<F: Fn(&str) -> Vec<String>> Sorter<F> {
+    fn visit(&mut self, id: &str) -> Result<(), Vec<String>> {
+        if self.done.contains(id) {
+            return Ok(());
+        }
+        if let Some(start) = self.path.iter().position(|visiting| visiting == id) {
+            let mut cycle = self.path[start..].to_vec();
+            cycle.push(id.to_string());
+            return Err(cycle);
+        }
+
+        self.path.push(id.to_string());
+        for other in (self.before)(id) {
+            self.visit(&other)?;
+        }
+        self.path.pop();
+
+        self.done.insert(id.to_string());
+        self.order.push(id.to_string());
+        Ok(())
+    }
+}
+
+/// Checks that every relation in the catalog points at a catalog item and
+/// that no items wait on each other.
+pub fn validate(catalog: &BTreeMap<String, Relations>) -> Result<(), String> {
+    for (id, relations) in catalog {
+        let referenced = relations.before().chain(&relations.conflicts_with);
+        if let Some(unknown) = referenced.into_iter().find(|other| !catalog.contains_key(*other)) {
+            return Err(format!("{} refers to unknown item {}", id, unknown));
+        }
+    }
+
+    let every_item: Vec<String> = catalog.keys().cloned().collect();
+    let plan = resolve(catalog, &every_item);
+    if plan.cycle.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Dependency cycle in the catalog: {}", plan.cycle.join(" -> ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    /// Id, `requires`, `conflicts_with` and `run_after`.
+    type Entry<'a> = (&'a str, &'a [&'a str], &'a [&'a str], &'a [&'a str]);
+
+    fn catalog(entries: &[Entry]) -> BTreeMap<String, Relations> {
+        entries
+            .iter()
+            .map(|(id, requires, conflicts_with, run_after)| {
+                (
+                    id.to_string(),
+                    Relations {
+                        requires: ids(requires),
+                        conflicts_with: ids(conflicts_with),
+                        run_after: ids(run_after),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_selection_order_without_relations() {
+        let catalog = catalog(&[("a", &[], &[], &[]), ("b", &[], &[], &[]), ("c", &[], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["c", "a", "b"]));
+
+        assert_eq!(plan.ids(), vec!["c", "a", "b"]);
+        assert!(plan.is_runnable());
+    }
+
+    #[test]
+    fn adds_required_items_before_their_dependents() {
+        let catalog = catalog(&[("a", &["b"], &[], &[]), ("b", &["c"], &[], &[]), ("c", &[], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["a"]));
+
+        assert_eq!(plan.ids(), vec!["c", "b", "a"]);
+        assert_eq!(plan.steps[0].required_by.as_deref(), Some("b"));
+        assert_eq!(plan.steps[1].required_by.as_deref(), Some("a"));
+        assert_eq!(plan.steps[2].required_by, None);
+    }
+
+    #[test]
+    fn selected_requirements_count_as_selected() {
+        let catalog = catalog(&[("a", &["b"], &[], &[]), ("b", &[], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["a", "b"]));
+
+        assert_eq!(plan.ids(), vec!["b", "a"]);
+        assert!(plan.steps.iter().all(|step| step.required_by.is_none()));
+    }
+
+    #[test]
+    fn run_after_orders_without_adding() {
+        let catalog = catalog(&[("a", &[], &[], &["b"]), ("b", &[], &[], &[])]);
+
+        assert_eq!(resolve(&catalog, &ids(&["a"])).ids(), vec!["a"]);
+        assert_eq!(resolve(&catalog, &ids(&["a", "b"])).ids(), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn shared_requirements_run_once() {
+        let catalog = catalog(&[("a", &["c"], &[], &[]), ("b", &["c"], &[], &[]), ("c", &[], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["a", "b", "a"]));
+
+        assert_eq!(plan.ids(), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn unknown_selected_ids_pass_through() {
+        let catalog = catalog(&[("a", &[], &[], &["app"])]);
+        let plan = resolve(&catalog, &ids(&["a", "app"]));
+
+        assert_eq!(plan.ids(), vec!["app", "a"]);
+        assert!(plan.is_runnable());
+    }
+
+    #[test]
+    fn reports_missing_requirements() {
+        let catalog = catalog(&[("a", &["gone"], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["a"]));
+
+        assert_eq!(
+            plan.missing,
+            vec![MissingItem {
+                id: "gone".to_string(),
+                required_by: "a".to_string(),
+            }]
+        );
+        assert_eq!(plan.ids(), vec!["a"]);
+        assert!(!plan.is_runnable());
+    }
+
+    #[test]
+    fn reports_each_conflict_once() {
+        let catalog = catalog(&[("a", &[], &["b"], &[]), ("b", &[], &["a"], &[]), ("c", &[], &[], &[])]);
+        let plan = resolve(&catalog, &ids(&["b", "a", "c"]));
+
+        assert_eq!(
+            plan.conflicts,
+            vec![Conflict {
+                id: "a".to_string(),
+                conflicts_with: "b".to_string(),
+            }]
+        );
+        assert_eq!(
+            plan.ensure_runnable().unwrap_err(),
+            "Cannot apply this selection: a conflicts with b"
+        );
+    }
+
+    #[test]
+    fn conflicts_pulled_in_by_requirements_are_reported() {
+        let catalog = catalog(&[("a", &["b"], &[], &[]), ("b", &[], &[], &[]), ("c", &[], &["b"], &[])]);
+        let plan = resolve(&catalog, &ids(&["a", "c"]));
+
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].id, "b");
+        assert_eq!(plan.conflicts[0].conflicts_with, "c");
+    }
+
+    #[test]
+    fn conflicts_outside_the_plan_are_ignored() {
+        let catalog = catalog(&[("a", &[], &["b"], &[]), ("b", &[], &[], &[])]);
+
+        assert!(resolve(&catalog, &ids(&["a"])).conflicts.is_empty());
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let catalog = catalog(&[("a", &["b"], &[], &[]), ("b", &["c"], &[], &[]), ("c", &[], &[], &["a"])]);
+        let plan = resolve(&catalog, &ids(&["a"]));
+
+        assert_eq!(plan.cycle, vec!["a", "b", "c", "a"]);
+        assert_eq!(plan.steps.len(), 3);
+        assert_eq!(
+            plan.ensure_runnable().unwrap_err(),
+            "Cannot apply this selection: Dependency cycle: a -> b -> c -> a"
+        );
+    }
+
+    #[test]
+    fn detects_self_dependency() {
+        let catalog = catalog(&[("a", &["a"], &[], &[])]);
+
+        assert_eq!(resolve(&catalog, &ids(&["a"])).cycle, vec!["a", "a"]);
+    }
+
+    #[test]
+    fn run_after_cycles_only_count_within_the_plan() {
+        let catalog = catalog(&[("a", &[], &[], &["b"]), ("b", &[], &[], &["a"])]);
+
+        assert!(resolve(&catalog, &ids(&["a"])).is_runnable());
+        assert_eq!(resolve(&catalog, &ids(&["a", "b"])).cycle, vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn validates_the_catalog() {
+        assert!(validate(&catalog(&[("a", &["b"], &["c"], &[]), ("b", &[], &[], &[]), ("c", &[], &[], &["b"])])).is_ok());
+        assert_eq!(
+            validate(&catalog(&[("a", &[], &["gone"], &[])])).unwrap_err(),
+            "a refers to unknown item gone"
+        );
+        assert_eq!(
+            validate(&catalog(&[("a", &[], &[], &["b"]), ("b", &["a"], &[], &[])])).unwrap_err(),
+            "Dependency cycle in the catalog: a -> b -> a"
+        );
+    }
+
+    #[test]
+    fn reads_relations_from_catalog_json() {
+        let relations: Relations = serde_json::from_str(
+            r#"{"requires": ["a"], "conflictsWith": ["b"], "runAfter": ["c"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(relations.requires, vec!["a"]);
+        assert_eq!(relations.conflicts_with, vec!["b"]);
+        assert_eq!(relations.run_after, vec!["c"]);
+    }
+}
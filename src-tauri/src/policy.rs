@@ -0,0 +1,393 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::parse_json_list;
+use crate::powershell;
+use crate::status::ItemState;
+
+/// Registry roots a policy may live under, as PowerShell drive paths. Some
+/// inbox apps such as Paint read theirs from the older CurrentVersion root.
+const POLICY_ROOTS: &[&str] = &[
+    "HKLM:\\SOFTWARE\\Policies\\",
+    "HKCU:\\SOFTWARE\\Policies\\",
+    "HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\",
+    "HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\",
+];
+
+/// Registry data of a policy value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum PolicyValue {
+    Dword(u32),
+    String(String),
+}
+
+impl PolicyValue {
+    fn property_type(&self) -> &'static str {
+        match self {
+            PolicyValue::Dword(_) => "DWord",
+            PolicyValue::String(_) => "String",
+        }
+    }
+
+    fn literal(&self) -> String {
+        match self {
+            PolicyValue::Dword(value) => value.to_string(),
+            PolicyValue::String(value) => powershell::quote(value),
+        }
+    }
+
+    /// Compares against the value `Get-ItemProperty` read back. DWORDs come
+    /// back as signed 32-bit integers.
+    fn matches(&self, current: &serde_json::Value) -> bool {
+        match self {
+            PolicyValue::Dword(expected) => current.as_i64().is_some_and(|value| value as u32 == *expected),
+            PolicyValue::String(expected) => current.as_str() == Some(expected.as_str()),
+        }
+    }
+
+    /// Reads back a value of registry kind `kind`, `None` for kinds a policy
+    /// value cannot hold.
+    fn read(kind: &str, current: &serde_json::Value) -> Option<PolicyValue> {
+        match kind {
+            "DWord" => current.as_i64().map(|value| PolicyValue::Dword(value as u32)),
+            "String" => current.as_str().map(|value| PolicyValue::String(value.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// One registry policy value: `key` is a PowerShell registry path such as
+/// `HKLM:\SOFTWARE\Policies\Microsoft\Edge`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Policy {
+    pub key: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub value: PolicyValue,
+}
+
+pub fn validate(policies: &[Policy]) -> Result<(), String> {
+    for policy in policies {
+        let in_policy_root = POLICY_ROOTS.iter().any(|root| {
+            policy.key.len() > root.len()
+                && policy.key[..root.len()].eq_ignore_ascii_case(root)
+        });
+        if !in_policy_root {
+            return Err(format!("Policy key is outside the Policies hive: {}", policy.key));
+        }
+        if policy.name.trim().is_empty() {
+            return Err(format!("Policy under {} has no value name", policy.key));
+        }
+    }
+    Ok(())
+}
+
+/// Creates the key if missing and writes the value. Existing keys are left
+/// alone because `New-Item -Force` would wipe the other policies stored in them.
+fn set_statement(key: &str, name: &str, value: &PolicyValue) -> String {
+    let key = powershell::quote(key);
+    format!(
+        "if (-not (Test-Path -LiteralPath {key})) {{ New-Item -Path {key} -Force | Out-Null }}; New-ItemProperty -LiteralPath {key} -Name {} -PropertyType {} -Value {} -Force | Out-Null",
+        powershell::quote(name),
+        value.property_type(),
+        value.literal(),
+    )
+}
+
+fn remove_statement(key: &str, name: &str) -> String {
+    format!(
+        "Remove-ItemProperty -LiteralPath {} -Name {} -ErrorAction SilentlyContinue",
+        powershell::quote(key),
+        powershell::quote(&powershell::escape_wildcards(name))
+    )
+}
+
+pub fn apply_script(policies: &[Policy]) -> String {
+    policies
+        .iter()
+        .map(|policy| set_statement(&policy.key, &policy.name, &policy.value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// What a policy value held before an item wrote it; `None` when it was not
+/// set, which is the unmanaged default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyRecord {
+    pub key: String,
+    pub name: String,
+    pub previous: Option<PolicyValue>,
+}
+
+impl PolicyRecord {
+    fn is_for(&self, key: &str, name: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key) && self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// Captures what applying `policies` is about to overwrite, so values an
+/// administrator set through Group Policy or MDM come back on rollback.
+/// Values already in `existing` keep their record, so applying twice never
+/// records the item's own values as the original. Fails for values of a kind
+/// that could not be written back.
+pub fn record(policies: &[Policy], current: &[CurrentValue], existing: &[PolicyRecord]) -> Result<Vec<PolicyRecord>, String> {
+    policies
+        .iter()
+        .map(|policy| {
+            if let Some(recorded) = existing.iter().find(|record| record.is_for(&policy.key, &policy.name)) {
+                return Ok(recorded.clone());
+            }
+
+            let value = current
+                .iter()
+                .find(|value| value.key.eq_ignore_ascii_case(&policy.key) && value.name.eq_ignore_ascii_case(&policy.name))
+                .filter(|value| !value.value.is_null());
+            let previous = match value {
+                None => None,
+                Some(value) => {
+                    let kind = value.kind.as_deref().unwrap_or_default();
+                    Some(PolicyValue::read(kind, &value.value).ok_or_else(|| {
+                        format!(
+                            "{} in {} holds a {} value that could not be restored on rollback",
+                            policy.name, policy.key, kind
+                        )
+                    })?)
+                }
+            };
+
+            Ok(PolicyRecord {
+                key: policy.key.clone(),
+                name: policy.name.clone(),
+                previous,
+            })
+        })
+        .collect()
+}
+
+/// Writes back the recorded values and deletes the ones that were not set.
+pub fn restore_script(records: &[PolicyRecord]) -> String {
+    records
+        .iter()
+        .map(|record| match &record.previous {
+            Some(value) => set_statement(&record.key, &record.name, value),
+            None => remove_statement(&record.key, &record.name),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Reads the current data of every policy value, in the shape
+/// [`parse_values`] expects.
+pub fn status_script<'a>(policies: impl IntoIterator<Item = &'a Policy>) -> String {
+    let queries: Vec<String> = policies
+        .into_iter()
+        .map(|policy| {
+            format!(
+                "@{{ Key = {}; Name = {} }}",
+                powershell::quote(&policy.key),
+                powershell::quote(&policy.name)
+            )
+        })
+        .collect();
+
+    format!(
+        "@(foreach ($q in @({})) {{ \
+            $p = Get-ItemProperty -LiteralPath $q.Key -Name $q.Name -ErrorAction SilentlyContinue; \
+            [pscustomobject]@{{ Key = $q.Key; Name = $q.Name; Value = if ($p) {{ $p.($q.Name) }} else {{ $null }}; Kind = if ($p) {{ \"$((Get-Item -LiteralPath $q.Key).GetValueKind($q.Name))\" }} else {{ $null }} }} \
+        }}) | ConvertTo-Json -Depth 2 -Compress",
+        queries.join(", ")
+    )
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CurrentValue {
+    key: String,
+    name: String,
+    #[serde(default)]
+    value: serde_json::Value,
+    /// Registry value kind, e.g. `DWord` or `String`.
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+pub fn parse_values(json: &str) -> Result<Vec<CurrentValue>, String> {
+    parse_json_list(json)
+}
+
+/// Whether all, some or none of `policies` currently hold their value.
+/// Registry keys and value names are case-insensitive.
+pub fn state(policies: &[Policy], current: &[CurrentValue]) -> ItemState {
+    let applied = policies
+        .iter()
+        .filter(|policy| {
+            current.iter().any(|value| {
+                value.key.eq_ignore_ascii_case(&policy.key)
+                    && value.name.eq_ignore_ascii_case(&policy.name)
+                    && policy.value.matches(&value.value)
+            })
+        })
+        .count();
+
+    ItemState::from_counts(applied, policies.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDGE: &str = "HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge";
+
+    fn policies() -> Vec<Policy> {
+        serde_json::from_str(
+            r#"[
+                { "key": "HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge", "name": "StartupBoostEnabled", "type": "dword", "value": 0 },
+                { "key": "HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge", "name": "NewTabPageContentEnabled", "type": "dword", "value": 4294967295 },
+                { "key": "HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge", "name": "HomepageLocation", "type": "string", "value": "about:blank" }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn accepts_keys_under_policy_roots_only() {
+        assert!(validate(&policies()).is_ok());
+
+        let paint: Vec<Policy> = serde_json::from_str(
+            r#"[{ "key": "hkcu:\\Software\\Microsoft\\Windows\\CurrentVersion\\Policies\\Paint", "name": "DisableCocreator", "type": "dword", "value": 1 }]"#,
+        )
+        .unwrap();
+        assert!(validate(&paint).is_ok());
+
+        let mut outside = policies();
+        outside[0].key = "HKLM:\\SOFTWARE\\Microsoft\\Edge".to_string();
+        assert!(validate(&outside).unwrap_err().contains("outside the Policies hive"));
+
+        let mut root_only = policies();
+        root_only[0].key = "HKLM:\\SOFTWARE\\Policies\\".to_string();
+        assert!(validate(&root_only).is_err());
+
+        let mut unnamed = policies();
+        unnamed[1].name = " ".to_string();
+        assert!(validate(&unnamed).unwrap_err().contains("has no value name"));
+    }
+
+    #[test]
+    fn renders_apply_script() {
+        let script = apply_script(&policies()[..1]);
+
+        assert_eq!(
+            script,
+            "if (-not (Test-Path -LiteralPath 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge')) { New-Item -Path 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge' -Force | Out-Null }; New-ItemProperty -LiteralPath 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge' -Name 'StartupBoostEnabled' -PropertyType DWord -Value 0 -Force | Out-Null"
+        );
+        assert!(apply_script(&policies()).contains("-Name 'HomepageLocation' -PropertyType String -Value 'about:blank' -Force"));
+    }
+
+    #[test]
+    fn records_managed_values_and_absent_ones() {
+        // An administrator set StartupBoostEnabled and HomepageLocation.
+        let current = parse_values(
+            r#"[
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"StartupBoostEnabled","Value":-1,"Kind":"DWord"},
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"NewTabPageContentEnabled","Value":null,"Kind":null},
+                {"Key":"hklm:\\software\\policies\\microsoft\\edge","Name":"homepagelocation","Value":"https://intranet","Kind":"String"}
+            ]"#,
+        )
+        .unwrap();
+
+        let record = record(&policies(), &current, &[]).unwrap();
+
+        assert_eq!(record[0].previous, Some(PolicyValue::Dword(u32::MAX)));
+        assert_eq!(record[1].previous, None);
+        assert_eq!(record[2].previous, Some(PolicyValue::String("https://intranet".to_string())));
+        assert_eq!(
+            restore_script(&record[..2]),
+            format!(
+                "if (-not (Test-Path -LiteralPath '{EDGE}')) {{ New-Item -Path '{EDGE}' -Force | Out-Null }}; New-ItemProperty -LiteralPath '{EDGE}' -Name 'StartupBoostEnabled' -PropertyType DWord -Value 4294967295 -Force | Out-Null; \
+                 Remove-ItemProperty -LiteralPath '{EDGE}' -Name 'NewTabPageContentEnabled' -ErrorAction SilentlyContinue"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_record_when_applied_again() {
+        let original = [PolicyRecord {
+            key: EDGE.to_lowercase(),
+            name: "startupboostenabled".to_string(),
+            previous: Some(PolicyValue::Dword(1)),
+        }];
+        // The item's own value is in place now.
+        let current = parse_values(&format!(
+            r#"{{"Key":"{}","Name":"StartupBoostEnabled","Value":0,"Kind":"DWord"}}"#,
+            EDGE.replace('\\', "\\\\")
+        ))
+        .unwrap();
+
+        let record = record(&policies()[..2], &current, &original).unwrap();
+
+        assert_eq!(record[0], original[0]);
+        assert_eq!(record[1].previous, None);
+    }
+
+    #[test]
+    fn refuses_values_it_could_not_restore() {
+        let current = parse_values(
+            r#"{"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"StartupBoostEnabled","Value":[1,0],"Kind":"Binary"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            record(&policies()[..1], &current, &[]).unwrap_err(),
+            format!("StartupBoostEnabled in {} holds a Binary value that could not be restored on rollback", EDGE)
+        );
+    }
+
+    #[test]
+    fn queries_every_value() {
+        let script = status_script(&policies());
+
+        assert!(script.contains(&format!("@{{ Key = '{}'; Name = 'StartupBoostEnabled' }}, ", EDGE)));
+        assert!(script.ends_with("| ConvertTo-Json -Depth 2 -Compress"));
+    }
+
+    #[test]
+    fn applied_when_every_value_matches() {
+        // DWORDs above i32::MAX come back negative.
+        let current = parse_values(
+            r#"[
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"StartupBoostEnabled","Value":0},
+                {"Key":"hklm:\\software\\policies\\microsoft\\edge","Name":"newtabpagecontentenabled","Value":-1},
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"HomepageLocation","Value":"about:blank"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(state(&policies(), &current), ItemState::Applied);
+    }
+
+    #[test]
+    fn partial_when_some_values_differ_or_are_missing() {
+        let current = parse_values(
+            r#"[
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"StartupBoostEnabled","Value":0},
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"NewTabPageContentEnabled","Value":null},
+                {"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"HomepageLocation","Value":"https://example.com"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(state(&policies(), &current), ItemState::Partial);
+    }
+
+    #[test]
+    fn not_applied_without_values() {
+        let single = parse_values(
+            r#"{"Key":"HKLM:\\SOFTWARE\\Policies\\Microsoft\\Edge","Name":"StartupBoostEnabled","Value":"0"}"#,
+        )
+        .unwrap();
+
+        // A string "0" is not the DWORD 0.
+        assert_eq!(state(&policies()[..1], &single), ItemState::NotApplied);
+        assert_eq!(state(&policies(), &parse_values("").unwrap()), ItemState::NotApplied);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+/// Longest package pattern accepted; real PackageFullNames stay well below.
+const MAX_PATTERN_LEN: usize = 255;
+
+/// PowerShell treats the typographic quotes as single quotes too, so all of
+/// them have to be doubled inside a single-quoted literal.
+const SINGLE_QUOTES: &[char] = &['\'', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    Empty,
+    TooLong(usize),
+    InvalidChar(char),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Empty => write!(f, "Package pattern is empty"),
+            PatternError::TooLong(len) => write!(
+                f,
+                "Package pattern is {} characters long (maximum {})",
+                len, MAX_PATTERN_LEN
+            ),
+            PatternError::InvalidChar(c) => {
+                write!(f, "Package pattern contains invalid character {:?}", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Renders `value` as a single-quoted PowerShell string literal. Nothing inside
+/// a single-quoted string is interpolated, so this is safe for any input.
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        if SINGLE_QUOTES.contains(&c) {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Backtick-escapes the `-like` wildcards `[ ] * ?` (and the backtick itself)
+/// so `value` matches only itself where a cmdlet parameter accepts wildcards,
+/// such as `-Name` of `Remove-ItemProperty`. Quote the result as usual.
+pub fn escape_wildcards(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '`' | '[' | ']' | '*' | '?') {
+            escaped.push('`');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Renders `@('a', 'b')`, a PowerShell array of quoted string literals.
+pub fn array<I, S>(values: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let items: Vec<String> = values.into_iter().map(|v| quote(v.as_ref())).collect();
+    format!("@({})", items.join(", "))
+}
+
+/// Package patterns may only contain characters that appear in Appx identities
+/// plus the `*`, `?` and `[...]` wildcards understood by the package matcher.
+pub fn validate_package_pattern(pattern: &str) -> Result<&str, PatternError> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(PatternError::Empty);
+    }
+    let len = pattern.chars().count();
+    if len > MAX_PATTERN_LEN {
+        return Err(PatternError::TooLong(len));
+    }
+    match pattern
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '~' | '*' | '?' | '[' | ']')))
+    {
+        Some(c) => Err(PatternError::InvalidChar(c)),
+        None => Ok(pattern),
+    }
+}
+
+/// Runs `script` in a hidden, profile-less PowerShell.
+#[cfg(target_os = "windows")]
+pub fn run(script: &str) -> std::io::Result<std::process::Output> {
+    std::process::Command::new("powershell")
+        .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-WindowStyle", "Hidden", "-Command", script])
+        .creation_flags(0x08000000)
+        .output()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::{Strings, CASES};
+
+    /// Reads a single-quoted literal the way PowerShell's tokenizer does: any
+    /// quote followed by another quote is one literal quote, any other quote
+    /// ends the string. Returns the value and whatever follows the literal.
+    fn unquote(script: &str) -> (String, String) {
+        let mut chars = script.chars().peekable();
+        assert!(chars.next().is_some_and(|c| SINGLE_QUOTES.contains(&c)), "{:?} is not quoted", script);
+
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            if SINGLE_QUOTES.contains(&c) {
+                match chars.peek() {
+                    Some(next) if SINGLE_QUOTES.contains(next) => {
+                        chars.next();
+                    }
+                    _ => return (value, chars.collect()),
+                }
+            }
+            value.push(c);
+        }
+        panic!("{:?} is never closed", script);
+    }
+
+    #[test]
+    fn quotes_strings() {
+        let cases = [
+            ("", "''"),
+            ("plain", "'plain'"),
+            ("it's", "'it''s'"),
+            ("\u{2018}left\u{2019}right", "'\u{2018}\u{2018}left\u{2019}\u{2019}right'"),
+            ("low\u{201A}high\u{201B}", "'low\u{201A}\u{201A}high\u{201B}\u{201B}'"),
+            ("\"double\"", "'\"double\"'"),
+            ("$(Remove-Item C:\\)", "'$(Remove-Item C:\\)'"),
+            ("`whoami`", "'`whoami`'"),
+            ("line\nbreak\r\n", "'line\nbreak\r\n'"),
+            ("'; Stop-Computer; '", "'''; Stop-Computer; '''"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(quote(value), expected, "quoting {:?}", value);
+        }
+    }
+
+    #[test]
+    fn quoted_strings_never_end_early() {
+        let hostile = ["'", "''", "\u{2019}", "a'\u{2018}b", "x'); Remove-Item C:\\ -Recurse; ('"];
+
+        for value in hostile {
+            let quoted = quote(value);
+            let inner: Vec<char> = quoted.chars().collect();
+            let inner = &inner[1..inner.len() - 1];
+            // Every quote inside the literal comes as an escaped pair.
+            let mut chars = inner.iter();
+            while let Some(c) = chars.next() {
+                if SINGLE_QUOTES.contains(c) {
+                    assert_eq!(chars.next(), Some(c), "unpaired quote in {}", quoted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quoted_strings_round_trip() {
+        let alphabet = [
+            'a', 'Z', '0', ' ', ';', '|', '(', ')', '"', '$', '`', '\n', '\r', '\'', '\u{2018}', '\u{2019}',
+            '\u{201A}', '\u{201B}', 'é', '中',
+        ];
+        let mut strings = Strings::new(28);
+        for _ in 0..CASES {
+            let value = strings.string(&alphabet, 16);
+            let quoted = quote(&value);
+
+            assert_eq!(unquote(&quoted), (value.clone(), String::new()), "quoting {:?}", value);
+            // Appended script stays outside the literal.
+            assert_eq!(unquote(&format!("{}; Stop-Computer", quoted)).1, "; Stop-Computer");
+        }
+    }
+
+    #[test]
+    fn renders_arrays() {
+        assert_eq!(array(Vec::<String>::new()), "@()");
+        assert_eq!(array(["a", "b'c"]), "@('a', 'b''c')");
+        assert_eq!(array(vec!["/S".to_string()]), "@('/S')");
+    }
+
+    #[test]
+    fn escapes_wildcards() {
+        let cases = [
+            ("Microsoft.Edge", "Microsoft.Edge"),
+            ("Block [Telemetry]", "Block `[Telemetry`]"),
+            ("*?", "`*`?"),
+            ("back`tick", "back``tick"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(escape_wildcards(value), expected, "escaping {:?}", value);
+        }
+        assert_eq!(quote(&escape_wildcards("it's [x]")), "'it''s `[x`]'");
+    }
+
+    #[test]
+    fn validates_package_patterns() {
+        assert_eq!(validate_package_pattern(" Microsoft.BingNews "), Ok("Microsoft.BingNews"));
+        assert!(validate_package_pattern("Microsoft.Xbox*").is_ok());
+        assert!(validate_package_pattern("Microsoft.MSPaint_8wekyb3d8bbwe").is_ok());
+        assert!(validate_package_pattern("Microsoft.[AB]?~x").is_ok());
+
+        assert_eq!(validate_package_pattern("  "), Err(PatternError::Empty));
+        assert_eq!(validate_package_pattern(&"a".repeat(256)), Err(PatternError::TooLong(256)));
+        assert!(validate_package_pattern(&"a".repeat(255)).is_ok());
+        for (pattern, invalid) in [
+            ("a;b", ';'),
+            ("a'b", '\''),
+            ("a\u{2019}b", '\u{2019}'),
+            ("a$(b)", '$'),
+            ("a`b", '`'),
+            ("a b", ' '),
+            ("a\nb", '\n'),
+            ("a|b", '|'),
+        ] {
+            assert_eq!(validate_package_pattern(pattern), Err(PatternError::InvalidChar(invalid)));
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use serde::Serialize;
+
+use crate::system_info::SystemInfo;
+use crate::DebloatItem;
+
+/// Below this much free space on the system drive a batch is refused; DISM
+/// and restore points need room to work.
+const MIN_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+/// Below this much free space a batch only warns.
+const LOW_FREE_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+/// Battery level below which a batch on battery power is refused.
+const MIN_BATTERY_PERCENT: u8 = 20;
+
+/// Command fragments that change Microsoft Defender settings, which Tamper
+/// Protection silently reverts.
+const DEFENDER_MARKERS: &[&str] = &["set-mppreference", "add-mppreference", "\\windows defender", "windefend"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Block,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightReport {
+    pub checks: Vec<CheckResult>,
+    /// Any check blocks the batch.
+    pub blocked: bool,
+}
+
+/// What the checks need to know about the items of a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Batch {
+    pub requires_admin: bool,
+    pub has_policies: bool,
+    pub has_feature_changes: bool,
+    pub touches_defender: bool,
+}
+
+impl Batch {
+    pub fn from_items<'a>(items: impl IntoIterator<Item = &'a DebloatItem>) -> Batch {
+        let mut batch = Batch::default();
+        for item in items {
+            let commands = std::iter::once(&item.command).chain(item.steps.iter().map(|step| &step.command));
+            let mut touches_defender = false;
+            let mut writes_policies = false;
+            for command in commands {
+                let command = command.to_lowercase();
+                touches_defender |= DEFENDER_MARKERS.iter().any(|marker| command.contains(marker));
+                writes_policies |= command.contains("\\policies\\");
+            }
+
+            batch.requires_admin |= item.requires_admin;
+            batch.has_policies |= writes_policies || !item.policies.is_empty();
+            batch.has_feature_changes |= !item.features.is_empty();
+            batch.touches_defender |= touches_defender;
+        }
+        batch
+    }
+}
+
+fn check(id: &'static str, name: &'static str, status: CheckStatus, message: impl Into<String>) -> CheckResult {
+    CheckResult {
+        id,
+        name,
+        status,
+        message: message.into(),
+    }
+}
+
+fn pending_reboot(info: &SystemInfo, batch: &Batch) -> CheckResult {
+    let name = "Pending restart";
+    match (info.pending_reboot, batch.has_feature_changes) {
+        (false, _) => check("pending_reboot", name, CheckStatus::Pass, "No restart is pending"),
+        (true, true) => check(
+            "pending_reboot",
+            name,
+            CheckStatus::Block,
+            "Windows is waiting for a restart to finish servicing; feature changes fail until it has restarted",
+        ),
+        (true, false) => check(
+            "pending_reboot",
+            name,
+            CheckStatus::Warn,
+            "Windows is waiting for a restart; pending updates may undo some changes",
+        ),
+    }
+}
+
+fn disk_space(info: &SystemInfo) -> CheckResult {
+    let name = "Free disk space";
+    let free_gb = info.disk_free_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+
+    if info.disk_total_bytes == 0 {
+        check("disk_space", name, CheckStatus::Warn, "Free space on the system drive could not be read")
+    } else if info.disk_free_bytes < MIN_FREE_BYTES {
+        check(
+            "disk_space",
+            name,
+            CheckStatus::Block,
+            format!("Only {:.1} GB free on {}; free up at least 1 GB first", free_gb, info.system_drive),
+        )
+    } else if info.disk_free_bytes < LOW_FREE_BYTES {
+        check(
+            "disk_space",
+            name,
+            CheckStatus::Warn,
+            format!("Only {:.1} GB free on {}", free_gb, info.system_drive),
+        )
+    } else {
+        check(
+            "disk_space",
+            name,
+            CheckStatus::Pass,
+            format!("{:.1} GB free on {}", free_gb, info.system_drive),
+        )
+    }
+}
+
+fn power(info: &SystemInfo) -> CheckResult {
+    let name = "Power";
+    match (info.on_battery, info.battery_percent) {
+        (Some(true), Some(percent)) if percent < MIN_BATTERY_PERCENT => check(
+            "power",
+            name,
+            CheckStatus::Block,
+            format!("Running on battery at {}%; plug in the charger first", percent),
+        ),
+        (Some(true), _) => check(
+            "power",
+            name,
+            CheckStatus::Warn,
+            "Running on battery; a shutdown halfway through can leave changes half applied",
+        ),
+        (Some(false), _) => check("power", name, CheckStatus::Pass, "Plugged in"),
+        (None, _) => check("power", name, CheckStatus::Pass, "No battery"),
+    }
+}
+
+fn management(info: &SystemInfo, batch: &Batch) -> CheckResult {
+    let name = "Device management";
+    let managers: Vec<&str> = [
+        info.domain.as_ref().map(|_| "an Active Directory domain"),
+        info.azure_ad_joined.then_some("Microsoft Entra ID"),
+        info.mdm_enrolled.then_some("an MDM such as Intune"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if managers.is_empty() {
+        check("management", name, CheckStatus::Pass, "This device is not centrally managed")
+    } else if batch.has_policies {
+        check(
+            "management",
+            name,
+            CheckStatus::Warn,
+            format!(
+                "This device is managed through {}; its policies can overwrite the policy changes in this batch",
+                managers.join(" and ")
+            ),
+        )
+    } else {
+        check(
+            "management",
+            name,
+            CheckStatus::Pass,
+            format!("Managed through {}, but no policies are changed", managers.join(" and ")),
+        )
+    }
+}
+
+fn tamper_protection(info: &SystemInfo, batch: &Batch) -> CheckResult {
+    let name = "Tamper Protection";
+    match (info.tamper_protected, batch.touches_defender) {
+        (Some(true), true) => check(
+            "tamper_protection",
+            name,
+            CheckStatus::Block,
+            "Defender Tamper Protection is on and would revert the Defender changes; turn it off in Windows Security first",
+        ),
+        (Some(true), false) => check(
+            "tamper_protection",
+            name,
+            CheckStatus::Pass,
+            "Tamper Protection is on, but no Defender settings are changed",
+        ),
+        _ => check("tamper_protection", name, CheckStatus::Pass, "Tamper Protection is off or not in use"),
+    }
+}
+
+fn elevation(info: &SystemInfo, batch: &Batch) -> CheckResult {
+    let name = "Administrator rights";
+    match (info.is_elevated, batch.requires_admin) {
+        (false, true) => check(
+            "elevation",
+            name,
+            CheckStatus::Block,
+            "Some items need administrator rights; restart Debloater as administrator",
+        ),
+        (true, _) => check("elevation", name, CheckStatus::Pass, "Running as administrator"),
+        (false, false) => check("elevation", name, CheckStatus::Pass, "No item needs administrator rights"),
+    }
+}
+
+pub fn evaluate(info: &SystemInfo, batch: &Batch) -> PreflightReport {
+    let checks = vec![
+        pending_reboot(info, batch),
+        disk_space(info),
+        power(info),
+        management(info, batch),
+        tamper_protection(info, batch),
+        elevation(info, batch),
+    ];
+    let blocked = checks.iter().any(|check| check.status == CheckStatus::Block);
+
+    PreflightReport { checks, blocked }
+}
+
+/// Refuses a blocked batch unless the user chose to apply it anyway.
+pub fn ensure_allowed(report: &PreflightReport, force: bool) -> Result<(), String> {
+    if !report.blocked || force {
+        return Ok(());
+    }
+
+    let reasons: Vec<&str> = report
+        .checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Block)
+        .map(|check| check.message.as_str())
+        .collect();
+    Err(format!("Pre-flight checks failed: {}", reasons.join("; ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn healthy() -> SystemInfo {
+        SystemInfo {
+            is_elevated: true,
+            system_drive: "C:".to_string(),
+            disk_free_bytes: 100 * GB,
+            disk_total_bytes: 500 * GB,
+            on_battery: Some(false),
+            battery_percent: Some(100),
+            tamper_protected: Some(true),
+            ..SystemInfo::default()
+        }
+    }
+
+    fn everything() -> Batch {
+        Batch {
+            requires_admin: true,
+            has_policies: true,
+            has_feature_changes: true,
+            touches_defender: false,
+        }
+    }
+
+    fn status(report: &PreflightReport, id: &str) -> CheckStatus {
+        report.checks.iter().find(|check| check.id == id).unwrap().status
+    }
+
+    #[test]
+    fn healthy_system_passes_everything() {
+        let report = evaluate(&healthy(), &everything());
+
+        assert!(!report.blocked);
+        assert!(report.checks.iter().all(|check| check.status == CheckStatus::Pass));
+        assert!(ensure_allowed(&report, false).is_ok());
+    }
+
+    #[test]
+    fn pending_reboot_blocks_feature_changes_only() {
+        let info = SystemInfo { pending_reboot: true, ..healthy() };
+
+        assert_eq!(status(&evaluate(&info, &everything()), "pending_reboot"), CheckStatus::Block);
+        assert_eq!(status(&evaluate(&info, &Batch::default()), "pending_reboot"), CheckStatus::Warn);
+    }
+
+    #[test]
+    fn grades_free_disk_space() {
+        let low = SystemInfo { disk_free_bytes: 3 * GB, ..healthy() };
+        let full = SystemInfo { disk_free_bytes: GB / 2, ..healthy() };
+        let unknown = SystemInfo { disk_free_bytes: 0, disk_total_bytes: 0, ..healthy() };
+
+        assert_eq!(status(&evaluate(&low, &Batch::default()), "disk_space"), CheckStatus::Warn);
+        assert_eq!(status(&evaluate(&full, &Batch::default()), "disk_space"), CheckStatus::Block);
+        assert_eq!(status(&evaluate(&unknown, &Batch::default()), "disk_space"), CheckStatus::Warn);
+    }
+
+    #[test]
+    fn grades_battery_power() {
+        let on_battery = SystemInfo { on_battery: Some(true), battery_percent: Some(80), ..healthy() };
+        let nearly_empty = SystemInfo { on_battery: Some(true), battery_percent: Some(10), ..healthy() };
+        let desktop = SystemInfo { on_battery: None, battery_percent: None, ..healthy() };
+
+        assert_eq!(status(&evaluate(&on_battery, &Batch::default()), "power"), CheckStatus::Warn);
+        assert_eq!(status(&evaluate(&nearly_empty, &Batch::default()), "power"), CheckStatus::Block);
+        assert_eq!(status(&evaluate(&desktop, &Batch::default()), "power"), CheckStatus::Pass);
+    }
+
+    #[test]
+    fn managed_devices_warn_about_policies() {
+        let info = SystemInfo { domain: Some("corp.example".to_string()), mdm_enrolled: true, ..healthy() };
+        let report = evaluate(&info, &everything());
+        let management = report.checks.iter().find(|check| check.id == "management").unwrap();
+
+        assert_eq!(management.status, CheckStatus::Warn);
+        assert!(management.message.contains("an Active Directory domain and an MDM such as Intune"));
+        assert_eq!(status(&evaluate(&info, &Batch::default()), "management"), CheckStatus::Pass);
+    }
+
+    #[test]
+    fn tamper_protection_blocks_defender_changes() {
+        let defender = Batch { touches_defender: true, ..Batch::default() };
+        let unprotected = SystemInfo { tamper_protected: Some(false), ..healthy() };
+
+        assert_eq!(status(&evaluate(&healthy(), &defender), "tamper_protection"), CheckStatus::Block);
+        assert_eq!(status(&evaluate(&unprotected, &defender), "tamper_protection"), CheckStatus::Pass);
+        assert_eq!(status(&evaluate(&healthy(), &everything()), "tamper_protection"), CheckStatus::Pass);
+    }
+
+    #[test]
+    fn unelevated_process_blocks_admin_items() {
+        let info = SystemInfo { is_elevated: false, ..healthy() };
+
+        assert_eq!(status(&evaluate(&info, &everything()), "elevation"), CheckStatus::Block);
+        assert_eq!(status(&evaluate(&info, &Batch::default()), "elevation"), CheckStatus::Pass);
+    }
+
+    #[test]
+    fn blocked_batches_need_force() {
+        let info = SystemInfo { is_elevated: false, pending_reboot: true, ..healthy() };
+        let report = evaluate(&info, &everything());
+
+        assert!(report.blocked);
+        let error = ensure_allowed(&report, false).unwrap_err();
+        assert!(error.contains("restart Debloater as administrator"));
+        assert!(error.contains("feature changes fail"));
+        assert!(ensure_allowed(&report, true).is_ok());
+    }
+}
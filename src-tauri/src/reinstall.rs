@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::PackageIdentity;
+use crate::package_scope::{RemovalScope, ScopeStatus};
+use crate::powershell;
+
+/// A way to bring a removed Appx package back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReinstallStrategy {
+    /// Re-register the package from files that are still on disk because it
+    /// stays provisioned or installed for another user.
+    RegisterManifest { manifest_path: String },
+    /// Install from the winget community repository.
+    Winget { id: String },
+    /// Install from the Microsoft Store through winget's msstore source.
+    Store { product_id: String },
+}
+
+impl ReinstallStrategy {
+    pub fn render(&self) -> String {
+        match self {
+            ReinstallStrategy::RegisterManifest { manifest_path } => format!(
+                "Add-AppxPackage -DisableDevelopmentMode -Register {}",
+                powershell::quote(manifest_path)
+            ),
+            ReinstallStrategy::Winget { id } => format!(
+                "winget install --id {} --exact --source winget --silent --accept-package-agreements --accept-source-agreements",
+                powershell::quote(id)
+            ),
+            ReinstallStrategy::Store { product_id } => format!(
+                "winget install --id {} --exact --source msstore --silent --accept-package-agreements --accept-source-agreements",
+                powershell::quote(product_id)
+            ),
+        }
+    }
+}
+
+/// Splits a `Name_Version_Arch_ResourceId_PublisherId` full name.
+fn full_name_parts(full_name: &str) -> Option<Vec<&str>> {
+    let parts: Vec<&str> = full_name.split('_').collect();
+    (parts.len() == 5).then_some(parts)
+}
+
+/// The `AppxManifest.xml` to re-register `package_full_name` from, taken from
+/// the install location in `installed`. A provisioned bundle
+/// (`..._neutral_~_...`) only holds `AppxMetadata\AppxBundleManifest.xml`,
+/// which cannot be registered, so the staged architecture-specific package of
+/// the same version is used instead.
+pub fn manifest_path(package_full_name: &str, installed: &[PackageIdentity]) -> Option<String> {
+    let wanted = full_name_parts(package_full_name)?;
+
+    installed
+        .iter()
+        .filter(|package| {
+            full_name_parts(&package.full_name).is_some_and(|parts| {
+                parts[3] != "~"
+                    && parts[0].eq_ignore_ascii_case(wanted[0])
+                    && parts[1] == wanted[1]
+                    && parts[4].eq_ignore_ascii_case(wanted[4])
+            })
+        })
+        .find_map(|package| package.install_location.as_deref())
+        .map(|location| format!("{}\\AppxManifest.xml", location.trim_end_matches('\\')))
+}
+
+/// Picks the package whose files survive removal from `scopes`, if any.
+/// Appx deletes the files once no user and no provisioning references them.
+pub fn surviving_package(status: &ScopeStatus, scopes: &[RemovalScope]) -> Option<String> {
+    if !scopes.contains(&RemovalScope::Provisioned) {
+        if let Some(name) = status.provisioned.iter().flatten().next() {
+            return Some(name.clone());
+        }
+    }
+    if !scopes.contains(&RemovalScope::AllUsers) {
+        if let Some(name) = status.other_users.iter().flatten().next() {
+            return Some(name.clone());
+        }
+    }
+    None
+}
+
+/// Strategies that will still work after the removal, best first: a local
+/// re-registration needs no network, then the catalog's declared sources.
+pub fn viable_strategies(
+    surviving_manifest: Option<String>,
+    catalog: &[ReinstallStrategy],
+) -> Vec<ReinstallStrategy> {
+    surviving_manifest
+        .map(|manifest_path| ReinstallStrategy::RegisterManifest { manifest_path })
+        .into_iter()
+        .chain(catalog.iter().cloned())
+        .collect()
+}
+
+/// What was known about an app at the moment it was removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReinstallRecord {
+    pub package_pattern: String,
+    pub removed_packages: Vec<String>,
+    pub strategies: Vec<ReinstallStrategy>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(provisioned: Option<&[&str]>, other_users: Option<&[&str]>) -> ScopeStatus {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        ScopeStatus {
+            current_user: vec!["Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe".to_string()],
+            other_users: other_users.map(names),
+            provisioned: provisioned.map(names),
+        }
+    }
+
+    fn installed(full_name: &str, install_location: Option<&str>) -> PackageIdentity {
+        PackageIdentity {
+            name: full_name.split('_').next().unwrap_or_default().to_string(),
+            family_name: String::new(),
+            full_name: full_name.to_string(),
+            user_sids: Vec::new(),
+            install_location: install_location.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn renders_quoted_commands() {
+        let manifest = ReinstallStrategy::RegisterManifest {
+            manifest_path: "C:\\Program Files\\WindowsApps\\Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe\\AppxManifest.xml"
+                .to_string(),
+        };
+        assert_eq!(
+            manifest.render(),
+            "Add-AppxPackage -DisableDevelopmentMode -Register 'C:\\Program Files\\WindowsApps\\Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe\\AppxManifest.xml'"
+        );
+
+        let winget = ReinstallStrategy::Winget { id: "Microsoft.Teams".to_string() };
+        assert_eq!(
+            winget.render(),
+            "winget install --id 'Microsoft.Teams' --exact --source winget --silent --accept-package-agreements --accept-source-agreements"
+        );
+
+        let store = ReinstallStrategy::Store { product_id: "9WZDNCRFHVFW".to_string() };
+        assert!(store.render().starts_with("winget install --id '9WZDNCRFHVFW' --exact --source msstore "));
+    }
+
+    #[test]
+    fn quotes_hostile_paths_and_ids() {
+        let manifest = ReinstallStrategy::RegisterManifest {
+            manifest_path: "C:\\Users\\O'Brien\\$(calc)\\AppxManifest.xml".to_string(),
+        };
+        assert!(manifest.render().ends_with("-Register 'C:\\Users\\O''Brien\\$(calc)\\AppxManifest.xml'"));
+
+        let winget = ReinstallStrategy::Winget { id: "Evil'; Stop-Computer; '".to_string() };
+        assert!(winget.render().starts_with("winget install --id 'Evil''; Stop-Computer; ''' --exact"));
+    }
+
+    #[test]
+    fn provisioned_copies_survive_user_removal() {
+        let status = status(Some(&["Microsoft.BingNews_4.1_neutral_~_8wekyb3d8bbwe"]), Some(&[]));
+        let installed = [
+            installed("Microsoft.BingNews_4.0_x64__8wekyb3d8bbwe", Some("C:\\Program Files\\WindowsApps\\old")),
+            installed(
+                "Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe",
+                Some("C:\\Program Files\\WindowsApps\\Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe"),
+            ),
+        ];
+
+        let surviving = surviving_package(&status, &[RemovalScope::AllUsers]).unwrap();
+        // The bundle folder has no AppxManifest.xml; its staged x64 package does.
+        assert_eq!(
+            manifest_path(&surviving, &installed).as_deref(),
+            Some("C:\\Program Files\\WindowsApps\\Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe\\AppxManifest.xml")
+        );
+        assert_eq!(surviving_package(&status, &RemovalScope::defaults()), None);
+    }
+
+    #[test]
+    fn unknown_install_locations_leave_nothing_to_register() {
+        let bundle = "Microsoft.BingNews_4.1_neutral_~_8wekyb3d8bbwe";
+
+        assert_eq!(manifest_path(bundle, &[]), None);
+        assert_eq!(manifest_path(bundle, &[installed(bundle, Some("C:\\Bundle"))]), None);
+        assert_eq!(manifest_path(bundle, &[installed("Microsoft.BingNews_4.1_arm64__8wekyb3d8bbwe", None)]), None);
+        assert_eq!(manifest_path("not-a-full-name", &[]), None);
+    }
+
+    #[test]
+    fn other_users_keep_the_files_of_current_user_removals() {
+        let status = status(Some(&[]), Some(&["Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe"]));
+
+        let installed = [installed("Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe", Some("D:\\Apps\\News\\"))];
+
+        let surviving = surviving_package(&status, &[RemovalScope::CurrentUser]).unwrap();
+        assert_eq!(surviving, "Microsoft.BingNews_4.1_x64__8wekyb3d8bbwe");
+        assert_eq!(manifest_path(&surviving, &installed).as_deref(), Some("D:\\Apps\\News\\AppxManifest.xml"));
+        assert_eq!(surviving_package(&status, &[RemovalScope::CurrentUser, RemovalScope::AllUsers]), None);
+    }
+
+    #[test]
+    fn unknown_scopes_leave_nothing_to_register() {
+        let status = status(None, None);
+
+        assert_eq!(surviving_package(&status, &[RemovalScope::CurrentUser]), None);
+    }
+
+    #[test]
+    fn local_registration_comes_before_catalog_sources() {
+        let catalog = [
+            ReinstallStrategy::Store { product_id: "9WZDNCRFHVFW".to_string() },
+            ReinstallStrategy::Winget { id: "Microsoft.BingNews".to_string() },
+        ];
+
+        let strategies = viable_strategies(Some("C:\\Manifest.xml".to_string()), &catalog);
+        assert_eq!(
+            strategies[0],
+            ReinstallStrategy::RegisterManifest { manifest_path: "C:\\Manifest.xml".to_string() }
+        );
+        assert_eq!(strategies[1..], catalog);
+
+        assert_eq!(viable_strategies(None, &catalog), catalog);
+        assert!(viable_strategies(None, &[]).is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How much can go wrong when an item is applied, lowest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    #[default]
+    Low,
+    Medium,
+    High,
+    /// Needs a confirmation token on top of being allowed by the policy.
+    Critical,
+}
+
+impl RiskLevel {
+    fn label(self) -> &'static str {
+        match self {
+            RiskLevel::Low => "low",
+            RiskLevel::Medium => "medium",
+            RiskLevel::High => "high",
+            RiskLevel::Critical => "critical",
+        }
+    }
+}
+
+/// What an item can break or weaken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskReason {
+    BreaksPrinting,
+    WeakensSecurity,
+    BreaksStoreUpdates,
+    DelaysSecurityUpdates,
+    BreaksSearch,
+    BreaksGaming,
+    /// Bluetooth, smart card readers, cameras and microphones.
+    BreaksDevices,
+    BreaksSync,
+    BreaksDependentApps,
+    AffectsPerformance,
+    ReducesTroubleshooting,
+    HardToReinstall,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Risk {
+    #[serde(default)]
+    pub level: RiskLevel,
+    #[serde(default)]
+    pub reasons: Vec<RiskReason>,
+}
+
+impl Risk {
+    pub fn level(level: RiskLevel) -> Self {
+        Risk {
+            level,
+            reasons: Vec::new(),
+        }
+    }
+
+    pub fn because(mut self, reasons: &[RiskReason]) -> Self {
+        self.reasons.extend_from_slice(reasons);
+        self
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RiskOrSafe {
+    Risk(Risk),
+    Safe(bool),
+}
+
+/// Reads a [`Risk`], or the `safe` flag records used before risk levels:
+/// safe items are low risk, the others medium.
+pub fn deserialize_legacy<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Risk, D::Error> {
+    Ok(match RiskOrSafe::deserialize(deserializer)? {
+        RiskOrSafe::Risk(risk) => risk,
+        RiskOrSafe::Safe(true) => Risk::level(RiskLevel::Low),
+        RiskOrSafe::Safe(false) => Risk::level(RiskLevel::Medium),
+    })
+}
+
+/// The highest risk the user allows to be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskPolicy {
+    pub max_level: RiskLevel,
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        RiskPolicy {
+            max_level: RiskLevel::Critical,
+        }
+    }
+}
+
+/// An item of a batch with the risk it carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskyItem {
+    pub id: String,
+    pub name: String,
+    pub risk: Risk,
+}
+
+/// Fails when any item is riskier than the policy allows.
+pub fn check_policy(items: &[RiskyItem], policy: &RiskPolicy) -> Result<(), String> {
+    let over: Vec<String> = items
+        .iter()
+        .filter(|item| item.risk.level > policy.max_level)
+        .map(|item| format!("{} ({})", item.name, item.risk.level.label()))
+        .collect();
+
+    if over.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Above the maximum risk level ({}): {}",
+            policy.max_level.label(),
+            over.join(", ")
+        ))
+    }
+}
+
+pub fn critical_ids(items: &[RiskyItem]) -> BTreeSet<String> {
+    items
+        .iter()
+        .filter(|item| item.risk.level == RiskLevel::Critical)
+        .map(|item| item.id.clone())
+        .collect()
+}
+
+/// How long a confirmation token stays valid.
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(5 * 60);
+
+struct Confirmation {
+    ids: BTreeSet<String>,
+    issued: Instant,
+}
+
+/// Single-use tokens the user obtains by confirming critical items, kept in
+/// Tauri's managed state. A token covers exactly the ids it was issued for.
+#[derive(Default)]
+pub struct Confirmations(Mutex<BTreeMap<String, Confirmation>>);
+
+impl Confirmations {
+    pub fn issue(&self, ids: BTreeSet<String>) -> String {
+        self.issue_at(ids, Instant::now())
+    }
+
+    fn issue_at(&self, ids: BTreeSet<String>, now: Instant) -> String {
+        let mut tokens = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        tokens.retain(|_, confirmation| now.duration_since(confirmation.issued) < CONFIRMATION_TTL);
+
+        let token = new_token();
+        tokens.insert(token.clone(), Confirmation { ids, issued: now });
+        token
+    }
+
+    /// Consumes `token` if it covers every id in `required`. Nothing is
+    /// needed when `required` is empty.
+    pub fn redeem(&self, token: Option<&str>, required: &BTreeSet<String>) -> Result<(), String> {
+        self.redeem_at(token, required, Instant::now())
+    }
+
+    fn redeem_at(&self, token: Option<&str>, required: &BTreeSet<String>, now: Instant) -> Result<(), String> {
+        if required.is_empty() {
+            return Ok(());
+        }
+
+        let missing = || {
+            format!(
+                "Critical items need to be confirmed first: {}",
+                required.iter().cloned().collect::<Vec<_>>().join(", ")
+            )
+        };
+        let token = token.ok_or_else(missing)?;
+
+        let mut tokens = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let confirmation = tokens.remove(token).ok_or_else(missing)?;
+        if now.duration_since(confirmation.issued) >= CONFIRMATION_TTL {
+            return Err("The confirmation has expired; confirm the critical items again".to_string());
+        }
+        if !required.is_subset(&confirmation.ids) {
+            return Err(missing());
+        }
+        Ok(())
+    }
+}
+
+/// 128 bits from two randomly keyed hashers, which is all the randomness the
+/// standard library offers.
+pub fn new_token() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let mut parts = [0u64; 2];
+    for (index, part) in parts.iter_mut().enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_usize(index);
+        *part = hasher.finish();
+    }
+    format!("{:016x}{:016x}", parts[0], parts[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, level: RiskLevel) -> RiskyItem {
+        RiskyItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            risk: Risk::level(level),
+        }
+    }
+
+    fn ids(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn policy_allows_up_to_its_maximum() {
+        let items = [item("a", RiskLevel::Low), item("b", RiskLevel::High), item("c", RiskLevel::Critical)];
+        let policy = RiskPolicy {
+            max_level: RiskLevel::Medium,
+        };
+
+        assert_eq!(
+            check_policy(&items, &policy).unwrap_err(),
+            "Above the maximum risk level (medium): b (high), c (critical)"
+        );
+        assert!(check_policy(&items[..1], &policy).is_ok());
+        assert!(check_policy(&items, &RiskPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn collects_critical_items() {
+        let items = [item("a", RiskLevel::High), item("b", RiskLevel::Critical)];
+
+        assert_eq!(critical_ids(&items), ids(&["b"]));
+    }
+
+    #[test]
+    fn reads_catalog_risk() {
+        let risk: Risk = serde_json::from_str(r#"{"level": "high", "reasons": ["breaks_printing"]}"#).unwrap();
+
+        assert_eq!(risk, Risk::level(RiskLevel::High).because(&[RiskReason::BreaksPrinting]));
+    }
+
+    #[test]
+    fn reads_legacy_safe_flag() {
+        #[derive(Deserialize)]
+        struct Saved {
+            #[serde(alias = "safe", deserialize_with = "deserialize_legacy")]
+            risk: Risk,
+        }
+
+        let parse = |json: &str| serde_json::from_str::<Saved>(json).unwrap().risk;
+
+        assert_eq!(parse(r#"{"safe": true}"#), Risk::level(RiskLevel::Low));
+        assert_eq!(parse(r#"{"safe": false}"#), Risk::level(RiskLevel::Medium));
+        assert_eq!(parse(r#"{"risk": {"level": "critical"}}"#), Risk::level(RiskLevel::Critical));
+    }
+
+    #[test]
+    fn nothing_to_confirm_without_critical_items() {
+        assert!(Confirmations::default().redeem(None, &BTreeSet::new()).is_ok());
+    }
+
+    #[test]
+    fn tokens_are_single_use() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue(ids(&["a", "b"]));
+
+        assert!(confirmations.redeem(Some(&token), &ids(&["a"])).is_ok());
+        assert!(confirmations.redeem(Some(&token), &ids(&["a"])).is_err());
+    }
+
+    #[test]
+    fn tokens_only_cover_their_items() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue(ids(&["a"]));
+
+        assert_eq!(
+            confirmations.redeem(Some(&token), &ids(&["a", "b"])).unwrap_err(),
+            "Critical items need to be confirmed first: a, b"
+        );
+        assert!(confirmations.redeem(None, &ids(&["a"])).is_err());
+        assert!(confirmations.redeem(Some("made-up"), &ids(&["a"])).is_err());
+    }
+
+    #[test]
+    fn tokens_expire() {
+        let confirmations = Confirmations::default();
+        let issued = Instant::now();
+        let token = confirmations.issue_at(ids(&["a"]), issued);
+
+        assert_eq!(
+            confirmations
+                .redeem_at(Some(&token), &ids(&["a"]), issued + CONFIRMATION_TTL)
+                .unwrap_err(),
+            "The confirmation has expired; confirm the critical items again"
+        );
+    }
+
+    #[test]
+    fn tokens_are_unique() {
+        let confirmations = Confirmations::default();
+
+        assert_ne!(confirmations.issue(ids(&["a"])), confirmations.issue(ids(&["a"])));
+    }
+}
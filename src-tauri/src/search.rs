@@ -0,0 +1,256 @@
+use crate::risk::{Risk, RiskLevel};
+use serde::{Deserialize, Serialize};
+
+/// Groups that cut across categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tag {
+    Gaming,
+    Privacy,
+    Performance,
+    Security,
+    Laptop,
+    Enterprise,
+}
+
+impl Tag {
+    fn label(self) -> &'static str {
+        match self {
+            Tag::Gaming => "gaming",
+            Tag::Privacy => "privacy",
+            Tag::Performance => "performance",
+            Tag::Security => "security",
+            Tag::Laptop => "laptop",
+            Tag::Enterprise => "enterprise",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// A tweak from one of the category data files.
+    Item,
+    /// An Appx, winget or custom app.
+    App,
+    /// A Win32 program from the uninstall catalog.
+    Program,
+}
+
+/// A catalog entry as search sees it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchEntry {
+    pub kind: EntryKind,
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub tags: Vec<Tag>,
+    pub risk: Risk,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchFilters {
+    /// Entry kinds to include; all of them when empty.
+    pub kinds: Vec<EntryKind>,
+    /// Categories to include; all of them when empty.
+    pub categories: Vec<String>,
+    /// Tags an entry needs to have, all of them.
+    pub tags: Vec<Tag>,
+    pub max_risk: Option<RiskLevel>,
+    pub limit: Option<usize>,
+}
+
+impl SearchFilters {
+    fn allows(&self, entry: &SearchEntry) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+            && (self.categories.is_empty() || self.categories.contains(&entry.category))
+            && self.tags.iter().all(|tag| entry.tags.contains(tag))
+            && self.max_risk.is_none_or(|max| entry.risk.level <= max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchResult {
+    #[serde(flatten)]
+    pub entry: SearchEntry,
+    pub score: u32,
+}
+
+/// How well a single query term matches, by the best field it appears in.
+fn term_score(entry: &SearchEntry, name: &str, description: &str, term: &str) -> u32 {
+    if entry.id == term || name == term {
+        100
+    } else if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(term))
+    {
+        60
+    } else if name.contains(term) {
+        40
+    } else if entry.tags.iter().any(|tag| tag.label() == term) {
+        30
+    } else if entry.id.contains(term) {
+        20
+    } else if description.contains(term) {
+        10
+    } else {
+        0
+    }
+}
+
+/// Scores `entry` against the lowercased query, or `None` when one of its
+/// terms matches nothing.
+fn score(entry: &SearchEntry, query: &str, terms: &[&str]) -> Option<u32> {
+    let name = entry.name.to_lowercase();
+    let description = entry.description.to_lowercase();
+
+    let mut total = 0;
+    for term in terms {
+        match term_score(entry, &name, &description, term) {
+            0 => return None,
+            score => total += score,
+        }
+    }
+
+    if terms.len() > 1 && name.contains(query) {
+        total += 50;
+    }
+    Some(total)
+}
+
+/// Entries allowed by `filters` whose name, description, tags or id match
+/// every term of `query`, best match first. An empty query lists every
+/// allowed entry by name.
+pub fn search(entries: Vec<SearchEntry>, query: &str, filters: &SearchFilters) -> Vec<SearchResult> {
+    let query = query.trim().to_lowercase();
+    let terms: Vec<&str> = query.split_whitespace().collect();
+
+    let mut results: Vec<SearchResult> = entries
+        .into_iter()
+        .filter(|entry| filters.allows(entry))
+        .filter_map(|entry| {
+            let score = score(&entry, &query, &terms)?;
+            Some(SearchResult { entry, score })
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.entry.kind.cmp(&b.entry.kind))
+            .then_with(|| a.entry.name.cmp(&b.entry.name))
+    });
+    if let Some(limit) = filters.limit {
+        results.truncate(limit);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: EntryKind, id: &str, name: &str, description: &str, tags: &[Tag]) -> SearchEntry {
+        SearchEntry {
+            kind,
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            category: match kind {
+                EntryKind::Item => "privacy".to_string(),
+                EntryKind::App => "apps".to_string(),
+                EntryKind::Program => "win32".to_string(),
+            },
+            tags: tags.to_vec(),
+            risk: Risk::default(),
+        }
+    }
+
+    fn catalog() -> Vec<SearchEntry> {
+        vec![
+            entry(EntryKind::Item, "disable-cortana", "Disable Cortana", "Turn off Cortana", &[Tag::Privacy]),
+            entry(EntryKind::App, "cortana", "Cortana", "Microsoft's voice assistant", &[Tag::Privacy]),
+            entry(EntryKind::Item, "disable-cortana-updates", "Block Assistant Updates", "Stop Cortana from updating", &[]),
+            entry(EntryKind::Item, "enable-game-mode", "Enable Game Mode", "Prioritize games", &[Tag::Gaming, Tag::Performance]),
+            entry(EntryKind::App, "xbox-app", "Xbox App", "Xbox console companion", &[Tag::Gaming]),
+            entry(EntryKind::Program, "mcafee", "McAfee Security", "Antivirus trial", &[Tag::Security, Tag::Performance]),
+        ]
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|result| result.entry.id.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_exact_names_first() {
+        let results = search(catalog(), "Cortana", &SearchFilters::default());
+
+        assert_eq!(ids(&results), ["cortana", "disable-cortana", "disable-cortana-updates"]);
+    }
+
+    #[test]
+    fn needs_every_term() {
+        let results = search(catalog(), "xbox companion", &SearchFilters::default());
+
+        assert_eq!(ids(&results), ["xbox-app"]);
+        assert!(search(catalog(), "xbox cortana", &SearchFilters::default()).is_empty());
+    }
+
+    #[test]
+    fn matches_tags_and_descriptions() {
+        assert_eq!(
+            ids(&search(catalog(), "performance", &SearchFilters::default())),
+            ["enable-game-mode", "mcafee"]
+        );
+        assert_eq!(ids(&search(catalog(), "antivirus", &SearchFilters::default())), ["mcafee"]);
+    }
+
+    #[test]
+    fn phrases_rank_above_scattered_terms() {
+        let results = search(catalog(), "game mode", &SearchFilters::default());
+
+        assert_eq!(results[0].entry.id, "enable-game-mode");
+        assert_eq!(results[0].score, 170);
+    }
+
+    #[test]
+    fn filters_by_kind_category_tags_and_risk() {
+        let mut entries = catalog();
+        entries[0].risk = Risk::level(RiskLevel::High);
+
+        let apps = SearchFilters {
+            kinds: vec![EntryKind::App],
+            ..SearchFilters::default()
+        };
+        assert_eq!(ids(&search(entries.clone(), "cortana", &apps)), ["cortana"]);
+
+        let privacy = SearchFilters {
+            categories: vec!["privacy".to_string()],
+            max_risk: Some(RiskLevel::Medium),
+            ..SearchFilters::default()
+        };
+        assert_eq!(ids(&search(entries.clone(), "cortana", &privacy)), ["disable-cortana-updates"]);
+
+        let tagged = SearchFilters {
+            tags: vec![Tag::Gaming, Tag::Performance],
+            ..SearchFilters::default()
+        };
+        assert_eq!(ids(&search(entries, "", &tagged)), ["enable-game-mode"]);
+    }
+
+    #[test]
+    fn empty_query_lists_by_name_up_to_the_limit() {
+        let filters = SearchFilters {
+            limit: Some(3),
+            ..SearchFilters::default()
+        };
+
+        assert_eq!(
+            ids(&search(catalog(), "  ", &filters)),
+            ["disable-cortana-updates", "disable-cortana", "enable-game-mode"]
+        );
+    }
+}
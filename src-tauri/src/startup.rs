@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::parse_json_list;
+use crate::powershell;
+use crate::tasks::TaskInfo;
+
+const APPROVED_ROOT: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved";
+
+/// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch.
+const FILETIME_UNIX_OFFSET_SECS: u64 = 11_644_473_600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupSource {
+    /// `HKLM\...\Run`
+    MachineRun,
+    /// `HKLM\SOFTWARE\WOW6432Node\...\Run`, 32-bit programs.
+    MachineRun32,
+    MachineRunOnce,
+    /// `HKCU\...\Run`
+    UserRun,
+    UserRunOnce,
+    /// The current user's Startup folder.
+    UserStartupFolder,
+    /// The Startup folder shared by all users.
+    CommonStartupFolder,
+    /// A scheduled task with a logon trigger.
+    LogonTask,
+}
+
+impl StartupSource {
+    /// The StartupApproved key Task Manager keeps this source's enabled
+    /// state in. Run-once entries and tasks have none.
+    pub fn approved_key(self) -> Option<String> {
+        let (hive, subkey) = match self {
+            StartupSource::MachineRun => ("HKLM", "Run"),
+            StartupSource::MachineRun32 => ("HKLM", "Run32"),
+            StartupSource::UserRun => ("HKCU", "Run"),
+            StartupSource::UserStartupFolder => ("HKCU", "StartupFolder"),
+            StartupSource::CommonStartupFolder => ("HKLM", "StartupFolder"),
+            StartupSource::MachineRunOnce | StartupSource::UserRunOnce | StartupSource::LogonTask => {
+                return None;
+            }
+        };
+        Some(format!("{}:\\{}\\{}", hive, APPROVED_ROOT, subkey))
+    }
+
+    pub fn requires_admin(self) -> bool {
+        matches!(
+            self,
+            StartupSource::MachineRun
+                | StartupSource::MachineRun32
+                | StartupSource::MachineRunOnce
+                | StartupSource::CommonStartupFolder
+                | StartupSource::LogonTask
+        )
+    }
+}
+
+/// A decoded StartupApproved value: 12 bytes, the first of which is even
+/// when the entry is enabled and odd when disabled, followed by three unused
+/// bytes and the FILETIME the entry was disabled at (zero when enabled).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovedState {
+    pub enabled: bool,
+    pub disabled_at: Option<u64>,
+}
+
+pub fn decode_approved(bytes: &[u8]) -> Option<ApprovedState> {
+    let flag = *bytes.first()?;
+    let disabled_at = bytes
+        .get(4..12)
+        .map(|time| u64::from_le_bytes(time.try_into().expect("slice of length 8")))
+        .filter(|time| *time != 0);
+
+    Some(ApprovedState {
+        enabled: flag & 1 == 0,
+        disabled_at,
+    })
+}
+
+/// Encodes the value Task Manager writes: `02` and zeros to enable, `03` and
+/// the current FILETIME to disable.
+pub fn encode_approved(enabled: bool, now_filetime: u64) -> [u8; 12] {
+    let mut bytes = [0u8; 12];
+    if enabled {
+        bytes[0] = 0x02;
+    } else {
+        bytes[0] = 0x03;
+        bytes[4..12].copy_from_slice(&now_filetime.to_le_bytes());
+    }
+    bytes
+}
+
+/// Converts a Unix timestamp to a FILETIME (100 ns intervals since 1601).
+pub fn filetime_from_unix(secs: u64, nanos: u32) -> u64 {
+    (secs + FILETIME_UNIX_OFFSET_SECS) * 10_000_000 + u64::from(nanos) / 100
+}
+
+/// Collects every startup entry in the shape [`parse_entries`] expects.
+/// Logon tasks under `\Microsoft\` belong to Windows and are left out.
+pub const STARTUP_ENTRIES_SCRIPT: &str = "$approvedRoot = 'SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved'; \
+$keys = @( \
+    @{ Source = 'machine_run'; Path = 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run'; Approved = \"HKLM:\\$approvedRoot\\Run\" }, \
+    @{ Source = 'machine_run32'; Path = 'HKLM:\\SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run'; Approved = \"HKLM:\\$approvedRoot\\Run32\" }, \
+    @{ Source = 'machine_run_once'; Path = 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce'; Approved = $null }, \
+    @{ Source = 'user_run'; Path = 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run'; Approved = \"HKCU:\\$approvedRoot\\Run\" }, \
+    @{ Source = 'user_run_once'; Path = 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce'; Approved = $null } \
+); \
+$folders = @( \
+    @{ Source = 'user_startup_folder'; Path = [Environment]::GetFolderPath('Startup'); Approved = \"HKCU:\\$approvedRoot\\StartupFolder\" }, \
+    @{ Source = 'common_startup_folder'; Path = [Environment]::GetFolderPath('CommonStartup'); Approved = \"HKLM:\\$approvedRoot\\StartupFolder\" } \
+); \
+function Get-Approved($key, $name) { \
+    if (-not $key) { return $null }; \
+    $p = Get-ItemProperty -LiteralPath $key -Name $name -ErrorAction SilentlyContinue; \
+    if ($p) { ,[int[]]$p.$name } else { $null } \
+}; \
+@( \
+    foreach ($k in $keys) { \
+        $item = Get-Item -LiteralPath $k.Path -ErrorAction SilentlyContinue; \
+        if (-not $item) { continue }; \
+        foreach ($name in $item.GetValueNames()) { \
+            if (-not $name) { continue }; \
+            [pscustomobject]@{ Source = $k.Source; Name = $name; Command = [string]$item.GetValue($name); Location = $k.Path; Approved = (Get-Approved $k.Approved $name) } \
+        } \
+    }; \
+    foreach ($f in $folders) { \
+        if (-not $f.Path -or -not (Test-Path -LiteralPath $f.Path)) { continue }; \
+        Get-ChildItem -LiteralPath $f.Path -File -ErrorAction SilentlyContinue | Where-Object { $_.Name -ne 'desktop.ini' } | ForEach-Object { \
+            [pscustomobject]@{ Source = $f.Source; Name = $_.Name; Command = $_.FullName; Location = $f.Path; Approved = (Get-Approved $f.Approved $_.Name) } \
+        } \
+    }; \
+    Get-ScheduledTask -ErrorAction SilentlyContinue | Where-Object { \
+        -not $_.TaskPath.StartsWith('\\Microsoft\\') -and ($_.Triggers | Where-Object { $_.CimClass.CimClassName -eq 'MSFT_TaskLogonTrigger' }) \
+    } | ForEach-Object { \
+        $action = $_.Actions | Select-Object -First 1; \
+        [pscustomobject]@{ Source = 'logon_task'; Name = $_.TaskName; Command = (($action.Execute, $action.Arguments) -join ' ').Trim(); Location = $_.TaskPath; TaskState = $_.State.ToString() } \
+    } \
+) | ConvertTo-Json -Depth 3 -Compress";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawEntry {
+    source: StartupSource,
+    name: String,
+    #[serde(default)]
+    command: Option<String>,
+    location: String,
+    #[serde(default)]
+    approved: Option<Vec<u8>>,
+    #[serde(default)]
+    task_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupEntry {
+    /// Stable identifier used by the enable/disable actions.
+    pub id: String,
+    pub source: StartupSource,
+    pub name: String,
+    pub command: String,
+    /// Registry key, folder or task path the entry lives in.
+    pub location: String,
+    pub enabled: bool,
+    /// FILETIME at which Task Manager (or Debloater) disabled the entry.
+    pub disabled_at: Option<u64>,
+    /// False for run-once entries, which cannot be switched off.
+    pub can_toggle: bool,
+    pub requires_admin: bool,
+}
+
+fn entry_id(source: StartupSource, location: &str, name: &str) -> String {
+    let source = serde_json::to_value(source)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    format!("{}:{}{}", source, location, name).to_lowercase()
+}
+
+/// Parses [`STARTUP_ENTRIES_SCRIPT`] output. A Run value or Startup folder
+/// file without a StartupApproved value is enabled.
+pub fn parse_entries(json: &str) -> Result<Vec<StartupEntry>, String> {
+    let mut entries: Vec<StartupEntry> = parse_json_list::<RawEntry>(json)?
+        .into_iter()
+        .map(|raw| {
+            let approved = raw.approved.as_deref().and_then(decode_approved);
+            let enabled = match (&raw.task_state, approved) {
+                (Some(state), _) => !state.eq_ignore_ascii_case("Disabled"),
+                (None, Some(approved)) => approved.enabled,
+                (None, None) => true,
+            };
+            StartupEntry {
+                id: entry_id(raw.source, &raw.location, &raw.name),
+                source: raw.source,
+                command: raw.command.unwrap_or_default(),
+                location: raw.location,
+                enabled,
+                disabled_at: approved.and_then(|approved| approved.disabled_at),
+                can_toggle: raw.source.approved_key().is_some() || raw.source == StartupSource::LogonTask,
+                requires_admin: raw.source.requires_admin(),
+                name: raw.name,
+            }
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    Ok(entries)
+}
+
+/// Renders the PowerShell that switches `entry` on or off: a StartupApproved
+/// value for Run keys and Startup folders, the task itself for logon tasks.
+pub fn set_enabled_script(entry: &StartupEntry, enabled: bool, now_filetime: u64) -> Result<String, String> {
+    if entry.source == StartupSource::LogonTask {
+        let task = TaskInfo {
+            path: entry.location.clone(),
+            name: entry.name.clone(),
+            state: String::new(),
+        };
+        return Ok(crate::tasks::set_enabled_script(&[&task], enabled));
+    }
+
+    let key = entry
+        .source
+        .approved_key()
+        .ok_or_else(|| format!("{} runs once at the next sign-in and cannot be disabled", entry.name))?;
+    let bytes: Vec<String> = encode_approved(enabled, now_filetime).iter().map(|b| b.to_string()).collect();
+    let key = powershell::quote(&key);
+
+    Ok(format!(
+        "if (-not (Test-Path -LiteralPath {key})) {{ New-Item -Path {key} -Force | Out-Null }}; New-ItemProperty -LiteralPath {key} -Name {} -PropertyType Binary -Value ([byte[]]@({})) -Force | Out-Null",
+        powershell::quote(&entry.name),
+        bytes.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_task_manager_values() {
+        let enabled = decode_approved(&[0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert!(enabled.enabled);
+        assert_eq!(enabled.disabled_at, None);
+
+        let disabled = decode_approved(&[0x03, 0, 0, 0, 0x10, 0x2f, 0x6c, 0x0d, 0x58, 0x6f, 0xda, 0x01]).unwrap();
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.disabled_at, Some(0x01da_6f58_0d6c_2f10));
+    }
+
+    #[test]
+    fn odd_flags_mean_disabled() {
+        assert!(decode_approved(&[0x06, 0, 0, 0]).unwrap().enabled);
+        assert!(!decode_approved(&[0x07, 0, 0, 0]).unwrap().enabled);
+        assert!(!decode_approved(&[0x01]).unwrap().enabled);
+        assert_eq!(decode_approved(&[]), None);
+    }
+
+    #[test]
+    fn encodes_enabled_without_timestamp() {
+        assert_eq!(encode_approved(true, 12345), [0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn encodes_disabled_with_timestamp() {
+        let bytes = encode_approved(false, 0x01da_6f58_0d6c_2f10);
+
+        assert_eq!(bytes, [0x03, 0, 0, 0, 0x10, 0x2f, 0x6c, 0x0d, 0x58, 0x6f, 0xda, 0x01]);
+        assert_eq!(
+            decode_approved(&bytes),
+            Some(ApprovedState { enabled: false, disabled_at: Some(0x01da_6f58_0d6c_2f10) })
+        );
+    }
+
+    #[test]
+    fn converts_unix_time_to_filetime() {
+        assert_eq!(filetime_from_unix(0, 0), 116_444_736_000_000_000);
+        assert_eq!(filetime_from_unix(1, 500), 116_444_736_010_000_005);
+    }
+
+    #[test]
+    fn parses_entries_from_all_sources() {
+        let json = r#"[
+            {"Source":"user_run","Name":"Spotify","Command":"\"C:\\Users\\me\\Spotify.exe\" /minimized","Location":"HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run","Approved":[3,0,0,0,16,47,108,13,88,111,218,1]},
+            {"Source":"machine_run","Name":"SecurityHealth","Command":"%windir%\\system32\\SecurityHealthSystray.exe","Location":"HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run","Approved":null},
+            {"Source":"user_run_once","Name":"Setup","Command":"setup.exe","Location":"HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce"},
+            {"Source":"logon_task","Name":"Updater","Command":"updater.exe --logon","Location":"\\Vendor\\","TaskState":"Disabled"}
+        ]"#;
+        let entries = parse_entries(json).unwrap();
+
+        let spotify = entries.iter().find(|entry| entry.name == "Spotify").unwrap();
+        assert!(!spotify.enabled);
+        assert!(spotify.can_toggle);
+        assert!(!spotify.requires_admin);
+
+        let health = entries.iter().find(|entry| entry.name == "SecurityHealth").unwrap();
+        assert!(health.enabled);
+        assert!(health.requires_admin);
+
+        let setup = entries.iter().find(|entry| entry.name == "Setup").unwrap();
+        assert!(!setup.can_toggle);
+
+        let updater = entries.iter().find(|entry| entry.name == "Updater").unwrap();
+        assert!(!updater.enabled);
+        assert_eq!(updater.id, "logon_task:\\vendor\\updater");
+    }
+
+    #[test]
+    fn renders_approved_value() {
+        let entries = parse_entries(
+            r#"{"Source":"user_run","Name":"Spotify","Command":"spotify.exe","Location":"HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run"}"#,
+        )
+        .unwrap();
+        let script = set_enabled_script(&entries[0], false, 0x01da_6f58_0d6c_2f10).unwrap();
+
+        assert!(script.contains(
+            "'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\Run'"
+        ));
+        assert!(script.contains("-Name 'Spotify' -PropertyType Binary -Value ([byte[]]@(3, 0, 0, 0, 16, 47, 108, 13, 88, 111, 218, 1))"));
+    }
+
+    #[test]
+    fn refuses_run_once_entries() {
+        let entries = parse_entries(
+            r#"{"Source":"machine_run_once","Name":"Setup","Command":"setup.exe","Location":"HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce"}"#,
+        )
+        .unwrap();
+
+        assert!(set_enabled_script(&entries[0], false, 0).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+/// How much of a catalog item is currently in effect on this machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemState {
+    Applied,
+    Partial,
+    NotApplied,
+}
+
+impl ItemState {
+    pub fn from_counts(applied: usize, total: usize) -> ItemState {
+        if applied == 0 {
+            ItemState::NotApplied
+        } else if applied >= total {
+            ItemState::Applied
+        } else {
+            ItemState::Partial
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemStatus {
+    pub id: String,
+    pub state: ItemState,
+}
@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use crate::applicability::{Architecture, Edition, Platform};
+#[cfg(target_os = "windows")]
+use crate::{elevation, powershell};
+
+/// Reads everything [`SystemInfo`] holds in one PowerShell run, in the shape
+/// [`parse`] expects. Nothing here needs elevation: Secure Boot comes from
+/// the registry instead of `Confirm-SecureBootUEFI` and the TPM from its PnP
+/// device instead of `Win32_Tpm`.
+pub const SYSTEM_INFO_SCRIPT: &str = "$cv = Get-ItemProperty -LiteralPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion'; \
+$cs = Get-CimInstance Win32_ComputerSystem; \
+$disk = Get-CimInstance Win32_LogicalDisk -Filter \"DeviceID='$env:SystemDrive'\"; \
+$dsreg = (dsregcmd.exe /status 2>$null) -join \"`n\"; \
+$secureBoot = Get-ItemProperty -LiteralPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\SecureBoot\\State' -Name UEFISecureBootEnabled -ErrorAction SilentlyContinue; \
+$tpm = @(Get-CimInstance Win32_PnPEntity -Filter \"PNPClass='SecurityDevices'\" -ErrorAction SilentlyContinue -ErrorVariable tpmError | Where-Object { $_.Name -like '*Trusted Platform Module*' }); \
+$mdm = @(Get-ChildItem -LiteralPath 'HKLM:\\SOFTWARE\\Microsoft\\Enrollments' -ErrorAction SilentlyContinue | Where-Object { $_.GetValue('ProviderID') -eq 'MS DM Server' }); \
+$battery = Get-CimInstance Win32_Battery -ErrorAction SilentlyContinue | Select-Object -First 1; \
+$defender = Get-MpComputerStatus -ErrorAction SilentlyContinue; \
+$renames = Get-ItemProperty -LiteralPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager' -Name PendingFileRenameOperations -ErrorAction SilentlyContinue; \
+[pscustomobject]@{ \
+    ProductName = [string]$cv.ProductName; \
+    DisplayVersion = [string]$cv.DisplayVersion; \
+    Build = [string]$cv.CurrentBuildNumber; \
+    Ubr = $cv.UBR; \
+    EditionId = [string]$cv.EditionID; \
+    Architecture = [string][System.Runtime.InteropServices.RuntimeInformation]::OSArchitecture; \
+    Username = $env:USERNAME; \
+    Domain = if ($cs.PartOfDomain) { [string]$cs.Domain } else { $null }; \
+    AzureAdJoined = $dsreg -match 'AzureAdJoined\\s*:\\s*YES'; \
+    MdmEnrolled = $mdm.Count -gt 0; \
+    SecureBoot = if ($secureBoot) { $secureBoot.UEFISecureBootEnabled -eq 1 } else { $null }; \
+    TpmPresent = if ($tpmError) { $null } else { $tpm.Count -gt 0 }; \
+    TotalMemory = [uint64]$cs.TotalPhysicalMemory; \
+    SystemDrive = $env:SystemDrive; \
+    DiskFree = [uint64]$disk.FreeSpace; \
+    DiskTotal = [uint64]$disk.Size; \
+    OnBattery = if ($battery) { $battery.BatteryStatus -eq 1 } else { $null }; \
+    BatteryPercent = if ($battery) { [int]$battery.EstimatedChargeRemaining } else { $null }; \
+    TamperProtected = if ($defender) { [bool]$defender.IsTamperProtected } else { $null }; \
+    PendingReboot = (Test-Path -LiteralPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Component Based Servicing\\RebootPending') -or \
+        (Test-Path -LiteralPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\WindowsUpdate\\Auto Update\\RebootRequired') -or \
+        [bool]$renames \
+} | ConvertTo-Json -Compress";
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemInfo {
+    /// Such as "Windows 11 Pro".
+    pub windows_version: String,
+    /// Such as "23H2" or "24H2".
+    pub display_version: Option<String>,
+    pub build_number: Option<u32>,
+    /// Update build revision, the part after the dot in 22631.4037.
+    pub ubr: Option<u32>,
+    /// Raw `EditionID`, such as "Professional".
+    pub edition_id: String,
+    pub edition: Option<Edition>,
+    pub architecture: Option<Architecture>,
+    pub username: String,
+    /// Active Directory domain, `None` when not domain joined.
+    pub domain: Option<String>,
+    pub azure_ad_joined: bool,
+    /// Enrolled in an MDM such as Intune.
+    pub mdm_enrolled: bool,
+    pub is_elevated: bool,
+    /// `None` on legacy BIOS systems.
+    pub secure_boot: Option<bool>,
+    /// `None` when the security devices could not be queried.
+    pub tpm_present: Option<bool>,
+    pub total_memory_bytes: u64,
+    pub system_drive: String,
+    pub disk_free_bytes: u64,
+    pub disk_total_bytes: u64,
+    /// Running on battery power; `None` on machines without a battery.
+    pub on_battery: Option<bool>,
+    pub battery_percent: Option<u8>,
+    /// Defender Tamper Protection; `None` when Defender is not the active
+    /// antivirus.
+    pub tamper_protected: Option<bool>,
+    pub pending_reboot: bool,
+}
+
+impl SystemInfo {
+    pub fn platform(&self) -> Platform {
+        Platform {
+            build: self.build_number,
+            edition: self.edition,
+            architecture: self.architecture,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawSystemInfo {
+    #[serde(default)]
+    product_name: String,
+    #[serde(default)]
+    display_version: String,
+    #[serde(default)]
+    build: String,
+    #[serde(default)]
+    ubr: Option<u32>,
+    #[serde(default)]
+    edition_id: String,
+    #[serde(default)]
+    architecture: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    domain: Option<String>,
+    #[serde(default)]
+    azure_ad_joined: bool,
+    #[serde(default)]
+    mdm_enrolled: bool,
+    #[serde(default)]
+    secure_boot: Option<bool>,
+    #[serde(default)]
+    tpm_present: Option<bool>,
+    #[serde(default)]
+    total_memory: u64,
+    #[serde(default)]
+    system_drive: String,
+    #[serde(default)]
+    disk_free: u64,
+    #[serde(default)]
+    disk_total: u64,
+    #[serde(default)]
+    on_battery: Option<bool>,
+    #[serde(default)]
+    battery_percent: Option<u8>,
+    #[serde(default)]
+    tamper_protected: Option<bool>,
+    #[serde(default)]
+    pending_reboot: bool,
+}
+
+/// Windows 11 still reports "Windows 10" as its ProductName; the build is the
+/// only reliable way to tell them apart.
+fn windows_version(product_name: &str, build: Option<u32>) -> String {
+    match build {
+        Some(build) if build >= 22000 => product_name.replacen("Windows 10", "Windows 11", 1),
+        _ => product_name.to_string(),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+pub fn parse(json: &str, is_elevated: bool) -> Result<SystemInfo, String> {
+    let raw: RawSystemInfo = serde_json::from_str(json.trim())
+        .map_err(|e| format!("Failed to parse system info: {}", e))?;
+    let build_number = raw.build.trim().parse().ok();
+
+    Ok(SystemInfo {
+        windows_version: windows_version(raw.product_name.trim(), build_number),
+        display_version: non_empty(raw.display_version),
+        build_number,
+        ubr: raw.ubr,
+        edition: Edition::from_edition_id(&raw.edition_id),
+        edition_id: raw.edition_id,
+        architecture: Architecture::parse(&raw.architecture),
+        username: raw.username,
+        domain: raw.domain.and_then(non_empty),
+        azure_ad_joined: raw.azure_ad_joined,
+        mdm_enrolled: raw.mdm_enrolled,
+        is_elevated,
+        secure_boot: raw.secure_boot,
+        tpm_present: raw.tpm_present,
+        total_memory_bytes: raw.total_memory,
+        system_drive: raw.system_drive,
+        disk_free_bytes: raw.disk_free,
+        disk_total_bytes: raw.disk_total,
+        on_battery: raw.on_battery,
+        battery_percent: raw.battery_percent,
+        tamper_protected: raw.tamper_protected,
+        pending_reboot: raw.pending_reboot,
+    })
+}
+
+fn query() -> Result<SystemInfo, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let output = powershell::run(SYSTEM_INFO_SCRIPT)
+            .map_err(|e| format!("Failed to read system info: {}", e))?;
+        parse(&String::from_utf8_lossy(&output.stdout), elevation::is_elevated())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("This application only runs on Windows".to_string())
+    }
+}
+
+/// System info shared through Tauri's managed state. It is read on first use
+/// and kept until [`SystemInfoCache::refresh`], as most of it only changes
+/// with a restart.
+#[derive(Default)]
+pub struct SystemInfoCache(Mutex<Option<SystemInfo>>);
+
+impl SystemInfoCache {
+    pub fn get(&self) -> Result<SystemInfo, String> {
+        let mut cached = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(info) = cached.as_ref() {
+            return Ok(info.clone());
+        }
+
+        let info = query()?;
+        *cached = Some(info.clone());
+        Ok(info)
+    }
+
+    /// Reads the system info again, for disk space, power and pending reboot
+    /// which change while the app runs.
+    pub fn refresh(&self) -> Result<SystemInfo, String> {
+        let info = query()?;
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(info.clone());
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_laptop() {
+        let info = parse(
+            r#"{"ProductName":"Windows 10 Pro","DisplayVersion":"23H2","Build":"22631","Ubr":4037,"EditionId":"Professional","Architecture":"X64","Username":"alex","Domain":null,"AzureAdJoined":true,"MdmEnrolled":true,"SecureBoot":true,"TpmPresent":true,"TotalMemory":17179869184,"SystemDrive":"C:","DiskFree":107374182400,"DiskTotal":511101108224,"OnBattery":true,"BatteryPercent":64,"TamperProtected":true,"PendingReboot":false}"#,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(info.windows_version, "Windows 11 Pro");
+        assert_eq!(info.display_version.as_deref(), Some("23H2"));
+        assert_eq!(info.build_number, Some(22631));
+        assert_eq!(info.ubr, Some(4037));
+        assert_eq!(info.edition, Some(Edition::Pro));
+        assert_eq!(info.architecture, Some(Architecture::X64));
+        assert_eq!(info.domain, None);
+        assert!(info.azure_ad_joined && info.mdm_enrolled);
+        assert!(!info.is_elevated);
+        assert_eq!(info.secure_boot, Some(true));
+        assert_eq!(info.tpm_present, Some(true));
+        assert_eq!(info.disk_free_bytes, 107374182400);
+        assert_eq!(info.on_battery, Some(true));
+        assert_eq!(info.battery_percent, Some(64));
+        assert_eq!(info.tamper_protected, Some(true));
+        assert_eq!(
+            info.platform(),
+            Platform {
+                build: Some(22631),
+                edition: Some(Edition::Pro),
+                architecture: Some(Architecture::X64),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_legacy_desktop_without_battery_tpm_or_defender() {
+        let info = parse(
+            "{\"ProductName\":\"Windows 10 Enterprise LTSC 2021\",\"DisplayVersion\":\"\",\"Build\":\"19044\",\"Ubr\":null,\"EditionId\":\"EnterpriseS\",\"Architecture\":\"X64\",\"Username\":\"admin\",\"Domain\":\"corp.example \",\"AzureAdJoined\":false,\"MdmEnrolled\":false,\"SecureBoot\":null,\"TpmPresent\":null,\"TotalMemory\":8589934592,\"SystemDrive\":\"C:\",\"DiskFree\":0,\"DiskTotal\":0,\"OnBattery\":null,\"BatteryPercent\":null,\"TamperProtected\":null,\"PendingReboot\":true}\r\n",
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(info.windows_version, "Windows 10 Enterprise LTSC 2021");
+        assert_eq!(info.display_version, None);
+        assert_eq!(info.ubr, None);
+        assert_eq!(info.edition, Some(Edition::Enterprise));
+        assert_eq!(info.domain.as_deref(), Some("corp.example"));
+        assert_eq!(info.secure_boot, None);
+        assert_eq!(info.tpm_present, None);
+        assert_eq!(info.on_battery, None);
+        assert_eq!(info.battery_percent, None);
+        assert_eq!(info.tamper_protected, None);
+        assert!(info.pending_reboot);
+    }
+
+    #[test]
+    fn unreadable_fields_stay_unknown() {
+        let info = parse(r#"{"ProductName":"Windows 10 Home","Build":"","EditionId":"ServerStandard","Architecture":"ia64"}"#, false)
+            .unwrap();
+
+        assert_eq!(info.windows_version, "Windows 10 Home");
+        assert_eq!(info.platform(), Platform::default());
+        assert!(parse("", false).is_err());
+    }
+}
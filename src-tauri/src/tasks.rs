@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::{parse_json_list, wildcard_match};
+use crate::powershell;
+use crate::status::ItemState;
+
+/// A scheduled task reference from the catalog, split into the folder and
+/// the task name the way `Get-ScheduledTask -TaskPath -TaskName` takes them.
+/// Both parts may contain PowerShell wildcards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskPattern {
+    pub path: String,
+    pub name: String,
+}
+
+impl TaskPattern {
+    /// Parses a full task path such as
+    /// `\Microsoft\Windows\Application Experience\ProgramDataUpdater`.
+    pub fn parse(full_path: &str) -> Result<TaskPattern, String> {
+        let split = full_path
+            .rfind('\\')
+            .filter(|_| full_path.starts_with('\\'))
+            .ok_or_else(|| format!("Task path must start with a backslash: {}", full_path))?;
+        let (path, name) = full_path.split_at(split + 1);
+        if name.is_empty() {
+            return Err(format!("Task path has no task name: {}", full_path));
+        }
+        Ok(TaskPattern { path: path.to_string(), name: name.to_string() })
+    }
+
+    pub fn matches(&self, task: &TaskInfo) -> bool {
+        wildcard_match(&self.path, &task.path) && wildcard_match(&self.name, &task.name)
+    }
+}
+
+/// Lists every task matching `patterns`, in the shape [`parse_tasks`] expects.
+/// Patterns that match nothing (tasks missing on this build) are skipped.
+pub fn query_script(patterns: &[TaskPattern]) -> String {
+    let queries: Vec<String> = patterns
+        .iter()
+        .map(|pattern| {
+            format!(
+                "@{{ Path = {}; Name = {} }}",
+                powershell::quote(&pattern.path),
+                powershell::quote(&pattern.name)
+            )
+        })
+        .collect();
+
+    format!(
+        "@(foreach ($q in @({})) {{ \
+            Get-ScheduledTask -TaskPath $q.Path -TaskName $q.Name -ErrorAction SilentlyContinue | ForEach-Object {{ \
+                [pscustomobject]@{{ TaskPath = $_.TaskPath; TaskName = $_.TaskName; State = $_.State.ToString() }} \
+            }} \
+        }}) | ConvertTo-Json -Compress",
+        queries.join(", ")
+    )
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawTask {
+    task_path: String,
+    task_name: String,
+    state: String,
+}
+
+/// A scheduled task and the state it was in when read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub path: String,
+    pub name: String,
+    pub state: String,
+}
+
+impl TaskInfo {
+    pub fn full_path(&self) -> String {
+        format!("{}{}", self.path, self.name)
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.state.eq_ignore_ascii_case("Disabled")
+    }
+}
+
+/// Parses [`query_script`] output. Overlapping patterns can list a task more
+/// than once, so duplicates are dropped.
+pub fn parse_tasks(json: &str) -> Result<Vec<TaskInfo>, String> {
+    let mut tasks: Vec<TaskInfo> = parse_json_list::<RawTask>(json)?
+        .into_iter()
+        .map(|raw| TaskInfo { path: raw.task_path, name: raw.task_name, state: raw.state })
+        .collect();
+
+    tasks.sort_by_key(|task| task.full_path().to_lowercase());
+    tasks.dedup_by(|a, b| a.full_path().eq_ignore_ascii_case(&b.full_path()));
+    Ok(tasks)
+}
+
+pub fn matching<'a>(patterns: &[TaskPattern], tasks: &'a [TaskInfo]) -> Vec<&'a TaskInfo> {
+    tasks
+        .iter()
+        .filter(|task| patterns.iter().any(|pattern| pattern.matches(task)))
+        .collect()
+}
+
+/// `None` when none of the item's tasks exist on this machine.
+pub fn state(tasks: &[&TaskInfo]) -> Option<ItemState> {
+    if tasks.is_empty() {
+        return None;
+    }
+    let disabled = tasks.iter().filter(|task| task.is_disabled()).count();
+    Some(ItemState::from_counts(disabled, tasks.len()))
+}
+
+/// Disables (or enables) exactly the given tasks, by literal path and name.
+pub fn set_enabled_script(tasks: &[&TaskInfo], enabled: bool) -> String {
+    let cmdlet = if enabled { "Enable-ScheduledTask" } else { "Disable-ScheduledTask" };
+    tasks
+        .iter()
+        .map(|task| {
+            format!(
+                "{} -TaskPath {} -TaskName {} -ErrorAction Stop | Out-Null",
+                cmdlet,
+                powershell::quote(&task.path),
+                powershell::quote(&task.name)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The script shown for a task item before anything has been queried.
+pub fn disable_script(patterns: &[TaskPattern]) -> String {
+    patterns
+        .iter()
+        .map(|pattern| {
+            format!(
+                "Get-ScheduledTask -TaskPath {} -TaskName {} -ErrorAction SilentlyContinue | Disable-ScheduledTask | Out-Null",
+                powershell::quote(&pattern.path),
+                powershell::quote(&pattern.name)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Folds the states read just before disabling into the record of an item.
+/// A task already in the record keeps its first recorded state, so applying
+/// an item twice never records "Disabled" as the original.
+pub fn merge_original(recorded: &mut Vec<TaskInfo>, current: &[&TaskInfo]) {
+    for task in current {
+        let known = recorded
+            .iter()
+            .any(|original| original.full_path().eq_ignore_ascii_case(&task.full_path()));
+        if !known {
+            recorded.push((*task).clone());
+        }
+    }
+}
+
+/// Tasks from a record that were enabled before the item was applied.
+pub fn to_restore(recorded: &[TaskInfo]) -> Vec<&TaskInfo> {
+    recorded.iter().filter(|task| !task.is_disabled()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CEIP: &str = "\\Microsoft\\Windows\\Customer Experience Improvement Program\\";
+
+    fn task(path: &str, name: &str, state: &str) -> TaskInfo {
+        TaskInfo {
+            path: path.to_string(),
+            name: name.to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn splits_task_paths() {
+        assert_eq!(
+            TaskPattern::parse("\\Microsoft\\Windows\\Application Experience\\ProgramDataUpdater").unwrap(),
+            TaskPattern {
+                path: "\\Microsoft\\Windows\\Application Experience\\".to_string(),
+                name: "ProgramDataUpdater".to_string(),
+            }
+        );
+        assert_eq!(TaskPattern::parse("\\Updater").unwrap().path, "\\");
+        assert!(TaskPattern::parse("Microsoft\\Windows\\Task").is_err());
+        assert!(TaskPattern::parse("\\Microsoft\\Windows\\").is_err());
+    }
+
+    #[test]
+    fn patterns_match_with_wildcards() {
+        let all = TaskPattern::parse(&format!("{}*", CEIP)).unwrap();
+
+        assert!(all.matches(&task(CEIP, "Consolidator", "Ready")));
+        assert!(all.matches(&task(&CEIP.to_lowercase(), "UsbCeip", "Ready")));
+        assert!(!all.matches(&task("\\Microsoft\\Windows\\Feedback\\Siuf\\", "DmClient", "Ready")));
+    }
+
+    #[test]
+    fn renders_query_script() {
+        let script = query_script(&[TaskPattern::parse(&format!("{}*", CEIP)).unwrap()]);
+
+        assert!(script.starts_with(
+            "@(foreach ($q in @(@{ Path = '\\Microsoft\\Windows\\Customer Experience Improvement Program\\'; Name = '*' })) {"
+        ));
+        assert!(script.ends_with("}) | ConvertTo-Json -Compress"));
+    }
+
+    #[test]
+    fn parses_tasks_without_duplicates() {
+        let json = r#"[
+            {"TaskPath":"\\Microsoft\\Windows\\Customer Experience Improvement Program\\","TaskName":"UsbCeip","State":"Disabled"},
+            {"TaskPath":"\\Microsoft\\Windows\\Customer Experience Improvement Program\\","TaskName":"Consolidator","State":"Ready"},
+            {"TaskPath":"\\Microsoft\\Windows\\Customer Experience Improvement Program\\","TaskName":"consolidator","State":"Ready"}
+        ]"#;
+
+        let tasks = parse_tasks(json).unwrap();
+        assert_eq!(tasks, [task(CEIP, "Consolidator", "Ready"), task(CEIP, "UsbCeip", "Disabled")]);
+
+        let single = parse_tasks(r#"{"TaskPath":"\\","TaskName":"Updater","State":"Running"}"#).unwrap();
+        assert_eq!(single, [task("\\", "Updater", "Running")]);
+        assert!(parse_tasks("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn state_counts_disabled_tasks() {
+        let consolidator = task(CEIP, "Consolidator", "Ready");
+        let usb = task(CEIP, "UsbCeip", "Disabled");
+        let kernel = task(CEIP, "KernelCeipTask", "disabled");
+
+        assert_eq!(state(&[]), None);
+        assert_eq!(state(&[&usb, &kernel]), Some(ItemState::Applied));
+        assert_eq!(state(&[&usb, &consolidator]), Some(ItemState::Partial));
+        assert_eq!(state(&[&consolidator]), Some(ItemState::NotApplied));
+    }
+
+    #[test]
+    fn renders_literal_enable_and_disable_scripts() {
+        let consolidator = task(CEIP, "Consolidator", "Ready");
+
+        assert_eq!(
+            set_enabled_script(&[&consolidator], false),
+            "Disable-ScheduledTask -TaskPath '\\Microsoft\\Windows\\Customer Experience Improvement Program\\' -TaskName 'Consolidator' -ErrorAction Stop | Out-Null"
+        );
+        assert!(set_enabled_script(&[&consolidator], true).starts_with("Enable-ScheduledTask "));
+        assert_eq!(
+            disable_script(&[TaskPattern::parse("\\Updater").unwrap()]),
+            "Get-ScheduledTask -TaskPath '\\' -TaskName 'Updater' -ErrorAction SilentlyContinue | Disable-ScheduledTask | Out-Null"
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_recorded_state() {
+        let mut recorded = vec![task(CEIP, "Consolidator", "Ready")];
+        let disabled_since = task(CEIP, "consolidator", "Disabled");
+        let added = task(CEIP, "UsbCeip", "Ready");
+
+        merge_original(&mut recorded, &[&disabled_since, &added]);
+
+        assert_eq!(recorded, [task(CEIP, "Consolidator", "Ready"), task(CEIP, "UsbCeip", "Ready")]);
+    }
+
+    #[test]
+    fn restores_only_tasks_that_were_enabled() {
+        let recorded = [task(CEIP, "Consolidator", "Ready"), task(CEIP, "UsbCeip", "Disabled")];
+
+        assert_eq!(to_restore(&recorded), [&recorded[0]]);
+    }
+}
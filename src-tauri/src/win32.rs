@@ -0,0 +1,375 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::package_match::parse_json_list;
+use crate::powershell;
+use crate::risk::Risk;
+use crate::search::Tag;
+
+/// Reads every Uninstall key (machine, 32-bit machine and per-user) into the
+/// shape [`parse_entries`] expects.
+pub const UNINSTALL_ENTRIES_SCRIPT: &str = "$roots = @( \
+    @{ Hive = 'HKLM'; Wow64 = $false; Path = 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall' }, \
+    @{ Hive = 'HKLM'; Wow64 = $true; Path = 'HKLM:\\SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall' }, \
+    @{ Hive = 'HKCU'; Wow64 = $false; Path = 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall' } \
+); \
+@(foreach ($root in $roots) { \
+    Get-ChildItem -LiteralPath $root.Path -ErrorAction SilentlyContinue | ForEach-Object { \
+        $p = Get-ItemProperty -LiteralPath $_.PSPath -ErrorAction SilentlyContinue; \
+        [pscustomobject]@{ \
+            KeyName = $_.PSChildName; \
+            Hive = $root.Hive; \
+            Wow64 = $root.Wow64; \
+            DisplayName = $p.DisplayName; \
+            DisplayVersion = $p.DisplayVersion; \
+            Publisher = $p.Publisher; \
+            UninstallString = $p.UninstallString; \
+            QuietUninstallString = $p.QuietUninstallString; \
+            WindowsInstaller = $p.WindowsInstaller; \
+            SystemComponent = $p.SystemComponent; \
+            ParentKeyName = $p.ParentKeyName; \
+            EstimatedSize = $p.EstimatedSize \
+        } \
+    } \
+}) | ConvertTo-Json -Depth 3 -Compress";
+
+/// msiexec exit codes that mean the product is gone: success, "product is
+/// not installed" and "success, reboot required".
+const MSI_SUCCESS_CODES: &[i32] = &[0, 1605, 3010];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawEntry {
+    key_name: String,
+    hive: String,
+    #[serde(default)]
+    wow64: bool,
+    display_name: Option<String>,
+    display_version: Option<String>,
+    publisher: Option<String>,
+    uninstall_string: Option<String>,
+    quiet_uninstall_string: Option<String>,
+    windows_installer: Option<u32>,
+    system_component: Option<u32>,
+    parent_key_name: Option<String>,
+    estimated_size: Option<u64>,
+}
+
+/// One program from an Uninstall registry key.
+#[derive(Debug, Clone, Serialize)]
+pub struct UninstallEntry {
+    pub key_name: String,
+    pub hive: String,
+    pub wow64: bool,
+    pub display_name: String,
+    pub display_version: Option<String>,
+    pub publisher: Option<String>,
+    pub uninstall_string: Option<String>,
+    pub quiet_uninstall_string: Option<String>,
+    /// MSI product code when the entry was installed by Windows Installer.
+    pub product_code: Option<String>,
+    /// Size in KiB as reported by the installer.
+    pub estimated_size: Option<u64>,
+}
+
+impl UninstallEntry {
+    pub fn is_machine_wide(&self) -> bool {
+        self.hive.eq_ignore_ascii_case("HKLM")
+    }
+}
+
+fn is_product_code(key_name: &str) -> bool {
+    let bytes = key_name.as_bytes();
+    bytes.len() == 38
+        && bytes[0] == b'{'
+        && bytes[37] == b'}'
+        && key_name[1..37].char_indices().all(|(i, c)| {
+            if matches!(i, 8 | 13 | 18 | 23) {
+                c == '-'
+            } else {
+                c.is_ascii_hexdigit()
+            }
+        })
+}
+
+/// Parses [`UNINSTALL_ENTRIES_SCRIPT`] output, dropping what Programs and
+/// Features hides too: nameless keys, system components and updates.
+pub fn parse_entries(json: &str) -> Result<Vec<UninstallEntry>, String> {
+    let mut entries: Vec<UninstallEntry> = parse_json_list::<RawEntry>(json)?
+        .into_iter()
+        .filter(|raw| raw.system_component != Some(1))
+        .filter(|raw| raw.parent_key_name.as_deref().unwrap_or("").is_empty())
+        .filter_map(|raw| {
+            let display_name = raw.display_name?.trim().to_string();
+            if display_name.is_empty() {
+                return None;
+            }
+            let product_code = (raw.windows_installer == Some(1) && is_product_code(&raw.key_name))
+                .then(|| raw.key_name.to_uppercase());
+            Some(UninstallEntry {
+                key_name: raw.key_name,
+                hive: raw.hive,
+                wow64: raw.wow64,
+                display_name,
+                display_version: raw.display_version,
+                publisher: raw.publisher.filter(|p| !p.trim().is_empty()),
+                uninstall_string: raw.uninstall_string.filter(|s| !s.trim().is_empty()),
+                quiet_uninstall_string: raw.quiet_uninstall_string.filter(|s| !s.trim().is_empty()),
+                product_code,
+                estimated_size: raw.estimated_size,
+            })
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| entry.display_name.to_lowercase());
+    Ok(entries)
+}
+
+/// A catalog entry from `data/win32.json`. Both patterns are case-insensitive
+/// regular expressions; the publisher is optional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Win32CatalogEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    #[serde(default)]
+    pub risk: Risk,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    pub display_name: String,
+    pub publisher: Option<String>,
+}
+
+pub struct Win32Matcher {
+    display_name: Regex,
+    publisher: Option<Regex>,
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid pattern {:?}: {}", pattern, e))
+}
+
+impl Win32Matcher {
+    pub fn new(entry: &Win32CatalogEntry) -> Result<Win32Matcher, String> {
+        Ok(Win32Matcher {
+            display_name: build_regex(&entry.display_name)?,
+            publisher: entry.publisher.as_deref().map(build_regex).transpose()?,
+        })
+    }
+
+    pub fn matches(&self, entry: &UninstallEntry) -> bool {
+        self.display_name.is_match(&entry.display_name)
+            && match &self.publisher {
+                Some(publisher) => entry.publisher.as_deref().is_some_and(|p| publisher.is_match(p)),
+                None => true,
+            }
+    }
+}
+
+/// Catalog entry with the installed programs it matched.
+#[derive(Debug, Clone, Serialize)]
+pub struct Win32App {
+    #[serde(flatten)]
+    pub entry: Win32CatalogEntry,
+    pub is_installed: bool,
+    pub matched: Vec<UninstallEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UninstallPlan {
+    pub script: String,
+    /// False when only an interactive UninstallString exists and the vendor
+    /// uninstaller will show its own UI.
+    pub silent: bool,
+}
+
+/// Renders the PowerShell that uninstalls `entry` and exits with a non-zero
+/// code on failure. MSI products go through msiexec with the product code,
+/// everything else through the registered (preferably quiet) command line.
+pub fn uninstall_plan(entry: &UninstallEntry) -> Result<UninstallPlan, String> {
+    if let Some(product_code) = &entry.product_code {
+        let ok_codes: Vec<String> = MSI_SUCCESS_CODES.iter().map(|c| c.to_string()).collect();
+        return Ok(UninstallPlan {
+            script: format!(
+                "$p = Start-Process -FilePath 'msiexec.exe' -ArgumentList {} -Wait -PassThru -WindowStyle Hidden; if (@({}) -notcontains $p.ExitCode) {{ exit $p.ExitCode }}",
+                powershell::array(["/x", product_code.as_str(), "/qn", "/norestart"]),
+                ok_codes.join(", ")
+            ),
+            silent: true,
+        });
+    }
+
+    let (command_line, silent) = match (&entry.quiet_uninstall_string, &entry.uninstall_string) {
+        (Some(quiet), _) => (quiet, true),
+        (None, Some(interactive)) => (interactive, false),
+        (None, None) => {
+            return Err(format!("{} has no uninstall command registered", entry.display_name));
+        }
+    };
+
+    // `cmd /s /c "..."` strips exactly the outer quotes, so the registered
+    // command line runs verbatim whatever quoting it uses itself. Interactive
+    // uninstallers must stay visible or they would wait on a hidden window.
+    Ok(UninstallPlan {
+        script: format!(
+            "$p = Start-Process -FilePath 'cmd.exe' -ArgumentList {} -Wait -PassThru{}; if ($p.ExitCode -ne 0) {{ exit $p.ExitCode }}",
+            powershell::quote(&format!("/s /c \"{}\"", command_line)),
+            if silent { " -WindowStyle Hidden" } else { "" }
+        ),
+        silent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRIES: &str = r#"[
+        {"KeyName":"{12345678-ABCD-1234-ABCD-1234567890AB}","Hive":"HKLM","Wow64":false,"DisplayName":"McAfee WebAdvisor","DisplayVersion":"4.1.1","Publisher":"McAfee, LLC","UninstallString":"MsiExec.exe /X{12345678-ABCD-1234-ABCD-1234567890AB}","QuietUninstallString":null,"WindowsInstaller":1,"SystemComponent":null,"ParentKeyName":null,"EstimatedSize":51200},
+        {"KeyName":"Norton 360","Hive":"HKLM","Wow64":true,"DisplayName":"Norton 360","DisplayVersion":"22.24","Publisher":"Gen Digital Inc.","UninstallString":"\"C:\\Program Files\\Norton\\Uninstall.exe\"","QuietUninstallString":"\"C:\\Program Files\\Norton\\Uninstall.exe\" /S","WindowsInstaller":null,"SystemComponent":null,"ParentKeyName":null,"EstimatedSize":null},
+        {"KeyName":"Contoso","Hive":"HKCU","Wow64":false,"DisplayName":" Contoso Helper ","DisplayVersion":null,"Publisher":" ","UninstallString":"\"C:\\Users\\me\\Contoso\\unins000.exe\"","QuietUninstallString":"","WindowsInstaller":0,"SystemComponent":0,"ParentKeyName":"","EstimatedSize":10},
+        {"KeyName":"KB5034441","Hive":"HKLM","Wow64":false,"DisplayName":"Update for Windows","ParentKeyName":"Windows"},
+        {"KeyName":"Driver","Hive":"HKLM","Wow64":false,"DisplayName":"Hidden Driver","SystemComponent":1},
+        {"KeyName":"Empty","Hive":"HKLM","Wow64":false,"DisplayName":null},
+        {"KeyName":"Blank","Hive":"HKLM","Wow64":false,"DisplayName":"   "}
+    ]"#;
+
+    fn entries() -> Vec<UninstallEntry> {
+        parse_entries(ENTRIES).unwrap()
+    }
+
+    fn entry(name: &str) -> UninstallEntry {
+        entries().into_iter().find(|entry| entry.display_name == name).unwrap()
+    }
+
+    fn catalog(display_name: &str, publisher: Option<&str>) -> Win32CatalogEntry {
+        Win32CatalogEntry {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            category: "win32".to_string(),
+            risk: Risk::default(),
+            tags: Vec::new(),
+            display_name: display_name.to_string(),
+            publisher: publisher.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_visible_entries_by_name() {
+        let names: Vec<String> = entries().into_iter().map(|entry| entry.display_name).collect();
+
+        assert_eq!(names, ["Contoso Helper", "McAfee WebAdvisor", "Norton 360"]);
+    }
+
+    #[test]
+    fn reads_product_codes_of_msi_entries_only() {
+        assert_eq!(
+            entry("McAfee WebAdvisor").product_code.as_deref(),
+            Some("{12345678-ABCD-1234-ABCD-1234567890AB}")
+        );
+        assert_eq!(entry("Norton 360").product_code, None);
+        assert!(!is_product_code("{12345678-ABCD-1234-ABCD-1234567890A}"));
+        assert!(!is_product_code("{12345678_ABCD-1234-ABCD-1234567890AB}"));
+    }
+
+    #[test]
+    fn drops_blank_fields() {
+        let contoso = entry("Contoso Helper");
+
+        assert_eq!(contoso.publisher, None);
+        assert_eq!(contoso.quiet_uninstall_string, None);
+        assert!(!contoso.is_machine_wide());
+        assert!(entry("Norton 360").is_machine_wide());
+    }
+
+    #[test]
+    fn parses_a_single_entry() {
+        let single = parse_entries(
+            r#"{"KeyName":"Foo","Hive":"HKCU","DisplayName":"Foo","UninstallString":"foo.exe"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(single.len(), 1);
+        assert!(!single[0].wow64);
+        assert!(parse_entries("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn matches_name_and_publisher_ignoring_case() {
+        let matcher = Win32Matcher::new(&catalog("^norton", Some("NortonLifeLock|Gen Digital"))).unwrap();
+        assert!(matcher.matches(&entry("Norton 360")));
+        assert!(!matcher.matches(&entry("McAfee WebAdvisor")));
+
+        let wrong_publisher = Win32Matcher::new(&catalog("^Norton", Some("^Symantec"))).unwrap();
+        assert!(!wrong_publisher.matches(&entry("Norton 360")));
+    }
+
+    #[test]
+    fn publisher_patterns_need_a_publisher() {
+        let any_publisher = Win32Matcher::new(&catalog("Contoso", None)).unwrap();
+        assert!(any_publisher.matches(&entry("Contoso Helper")));
+
+        let with_publisher = Win32Matcher::new(&catalog("Contoso", Some("Contoso"))).unwrap();
+        assert!(!with_publisher.matches(&entry("Contoso Helper")));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(Win32Matcher::new(&catalog("(unclosed", None)).is_err());
+        assert!(Win32Matcher::new(&catalog("ok", Some("[bad"))).is_err());
+    }
+
+    #[test]
+    fn uninstalls_msi_products_through_msiexec() {
+        let plan = uninstall_plan(&entry("McAfee WebAdvisor")).unwrap();
+
+        assert!(plan.silent);
+        assert_eq!(
+            plan.script,
+            "$p = Start-Process -FilePath 'msiexec.exe' -ArgumentList @('/x', '{12345678-ABCD-1234-ABCD-1234567890AB}', '/qn', '/norestart') -Wait -PassThru -WindowStyle Hidden; if (@(0, 1605, 3010) -notcontains $p.ExitCode) { exit $p.ExitCode }"
+        );
+    }
+
+    #[test]
+    fn prefers_the_quiet_uninstall_string() {
+        let plan = uninstall_plan(&entry("Norton 360")).unwrap();
+
+        assert!(plan.silent);
+        assert_eq!(
+            plan.script,
+            "$p = Start-Process -FilePath 'cmd.exe' -ArgumentList '/s /c \"\"C:\\Program Files\\Norton\\Uninstall.exe\" /S\"' -Wait -PassThru -WindowStyle Hidden; if ($p.ExitCode -ne 0) { exit $p.ExitCode }"
+        );
+    }
+
+    #[test]
+    fn interactive_uninstallers_stay_visible() {
+        let plan = uninstall_plan(&entry("Contoso Helper")).unwrap();
+
+        assert!(!plan.silent);
+        assert_eq!(
+            plan.script,
+            "$p = Start-Process -FilePath 'cmd.exe' -ArgumentList '/s /c \"\"C:\\Users\\me\\Contoso\\unins000.exe\"\"' -Wait -PassThru; if ($p.ExitCode -ne 0) { exit $p.ExitCode }"
+        );
+    }
+
+    #[test]
+    fn quotes_command_lines_for_powershell() {
+        let mut program = entry("Contoso Helper");
+        program.uninstall_string = Some("C:\\O'Brien\\uninstall.exe".to_string());
+
+        let plan = uninstall_plan(&program).unwrap();
+        assert!(plan.script.contains("-ArgumentList '/s /c \"C:\\O''Brien\\uninstall.exe\"'"));
+
+        program.uninstall_string = None;
+        assert_eq!(
+            uninstall_plan(&program),
+            Err("Contoso Helper has no uninstall command registered".to_string())
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::powershell;
+use crate::risk::Risk;
+use crate::search::Tag;
+
+/// Exports the installed packages winget can map to a source into a temp
+/// file and prints it. `winget list` output is a localized, width-dependent
+/// table; the export file is stable JSON. Fails when winget is missing or
+/// writes no export, as the installed state is unknown then.
+pub const EXPORT_SCRIPT: &str = "if (-not (Get-Command winget -ErrorAction SilentlyContinue)) { [Console]::Error.WriteLine('winget is not installed'); exit 1 }; \
+    $file = Join-Path $env:TEMP ('winget-export-' + [guid]::NewGuid().ToString() + '.json'); \
+    winget export --output $file --include-versions --accept-source-agreements --disable-interactivity | Out-Null; \
+    if (-not (Test-Path -LiteralPath $file)) { [Console]::Error.WriteLine(\"winget export failed with exit code $LASTEXITCODE\"); exit 1 }; \
+    Get-Content -LiteralPath $file -Raw; Remove-Item -LiteralPath $file -Force";
+
+/// How long an export is reused. Exporting takes seconds and refreshes the
+/// sources, so the Apps page does not run it on every load.
+pub const EXPORT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Top level of the `winget export` file (schema 2.0).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WingetExport {
+    #[serde(default)]
+    pub sources: Vec<WingetSource>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WingetSource {
+    #[serde(default)]
+    pub packages: Vec<WingetExportPackage>,
+    pub source_details: WingetSourceDetails,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WingetSourceDetails {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WingetExportPackage {
+    pub package_identifier: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WingetPackage {
+    pub id: String,
+    pub version: Option<String>,
+    pub source: String,
+}
+
+/// Flattens a `winget export` document into one entry per package.
+pub fn parse_export(json: &str) -> Result<Vec<WingetPackage>, String> {
+    // Get-Content may hand the file over with a UTF-8 byte order mark.
+    let json = json.trim().trim_start_matches('\u{feff}');
+    if json.is_empty() {
+        return Err("winget export printed nothing".to_string());
+    }
+
+    let export: WingetExport = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse winget export: {}", e))?;
+
+    Ok(export
+        .sources
+        .into_iter()
+        .flat_map(|source| {
+            let name = source.source_details.name;
+            source.packages.into_iter().map(move |package| WingetPackage {
+                id: package.package_identifier,
+                version: package.version,
+                source: name.clone(),
+            })
+        })
+        .collect())
+}
+
+/// The last successful export, kept in Tauri's managed state. Failures are
+/// not kept, so the next load asks winget again.
+#[derive(Default)]
+pub struct ExportCache(Mutex<Option<(Instant, Vec<WingetPackage>)>>);
+
+impl ExportCache {
+    pub fn get(&self) -> Option<Vec<WingetPackage>> {
+        self.get_at(Instant::now())
+    }
+
+    fn get_at(&self, now: Instant) -> Option<Vec<WingetPackage>> {
+        let cached = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        cached
+            .as_ref()
+            .filter(|(exported, _)| now.duration_since(*exported) < EXPORT_TTL)
+            .map(|(_, packages)| packages.clone())
+    }
+
+    pub fn store(&self, packages: Vec<WingetPackage>) {
+        self.store_at(packages, Instant::now());
+    }
+
+    fn store_at(&self, packages: Vec<WingetPackage>, exported: Instant) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some((exported, packages));
+    }
+
+    /// Forgets the export once packages were installed or removed.
+    pub fn invalidate(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+pub fn find_installed<'a>(id: &str, installed: &'a [WingetPackage]) -> Option<&'a WingetPackage> {
+    installed.iter().find(|package| package.id.eq_ignore_ascii_case(id))
+}
+
+/// A catalog entry from `data/winget.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WingetCatalogEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    #[serde(default)]
+    pub risk: Risk,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    pub winget_id: String,
+}
+
+pub fn uninstall_script(id: &str) -> String {
+    format!(
+        "winget uninstall --id {} --exact --silent --accept-source-agreements --disable-interactivity",
+        powershell::quote(id)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "\u{feff}{
+        \"$schema\": \"https://aka.ms/winget-packages.schema.2.0.json\",
+        \"CreationDate\": \"2026-10-19T10:00:00.000-00:00\",
+        \"Sources\": [
+            {
+                \"Packages\": [
+                    { \"PackageIdentifier\": \"Microsoft.OneDrive\", \"Version\": \"24.201.1006.0005\" },
+                    { \"PackageIdentifier\": \"Spotify.Spotify\" }
+                ],
+                \"SourceDetails\": {
+                    \"Argument\": \"https://cdn.winget.microsoft.com/cache\",
+                    \"Identifier\": \"Microsoft.Winget.Source_8wekyb3d8bbwe\",
+                    \"Name\": \"winget\",
+                    \"Type\": \"Microsoft.PreIndexed.Package\"
+                }
+            },
+            {
+                \"Packages\": [
+                    { \"PackageIdentifier\": \"9NBLGGH4NNS1\", \"Version\": \"1.0\" }
+                ],
+                \"SourceDetails\": {
+                    \"Argument\": \"https://storeedgefd.dsx.mp.microsoft.com/v9.0\",
+                    \"Identifier\": \"StoreEdgeFD\",
+                    \"Name\": \"msstore\",
+                    \"Type\": \"Microsoft.Rest\"
+                }
+            }
+        ],
+        \"WinGetVersion\": \"1.9.25200\"
+    }";
+
+    #[test]
+    fn flattens_export_sources() {
+        let packages = parse_export(EXPORT).unwrap();
+
+        assert_eq!(
+            packages,
+            [
+                WingetPackage {
+                    id: "Microsoft.OneDrive".to_string(),
+                    version: Some("24.201.1006.0005".to_string()),
+                    source: "winget".to_string(),
+                },
+                WingetPackage {
+                    id: "Spotify.Spotify".to_string(),
+                    version: None,
+                    source: "winget".to_string(),
+                },
+                WingetPackage {
+                    id: "9NBLGGH4NNS1".to_string(),
+                    version: Some("1.0".to_string()),
+                    source: "msstore".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_exports_are_errors_not_empty_lists() {
+        assert!(parse_export("").is_err());
+        assert!(parse_export(" \r\n").is_err());
+        assert!(parse_export("{}").unwrap().is_empty());
+        assert!(parse_export("winget is not recognized").is_err());
+    }
+
+    #[test]
+    fn exports_are_cached_until_they_expire_or_change() {
+        let cache = ExportCache::default();
+        let exported = Instant::now();
+        assert!(cache.get_at(exported).is_none());
+
+        cache.store_at(parse_export(EXPORT).unwrap(), exported);
+        assert_eq!(cache.get_at(exported + EXPORT_TTL / 2).map(|packages| packages.len()), Some(3));
+        assert!(cache.get_at(exported + EXPORT_TTL).is_none());
+
+        cache.store(Vec::new());
+        assert_eq!(cache.get(), Some(Vec::new()));
+        cache.invalidate();
+        assert!(cache.get().is_none());
+    }
+
+    #[test]
+    fn finds_installed_packages_ignoring_case() {
+        let packages = parse_export(EXPORT).unwrap();
+
+        assert_eq!(find_installed("spotify.spotify", &packages).map(|p| p.id.as_str()), Some("Spotify.Spotify"));
+        assert_eq!(find_installed("Microsoft.OneDrive", &packages).map(|p| p.source.as_str()), Some("winget"));
+        // Ids must match whole, not as a prefix.
+        assert!(find_installed("Microsoft.One", &packages).is_none());
+        assert!(find_installed("Spotify.Spotify", &[]).is_none());
+    }
+
+    #[test]
+    fn quotes_uninstall_ids() {
+        assert_eq!(
+            uninstall_script("Spotify.Spotify"),
+            "winget uninstall --id 'Spotify.Spotify' --exact --silent --accept-source-agreements --disable-interactivity"
+        );
+        assert!(uninstall_script("a'b").starts_with("winget uninstall --id 'a''b' "));
+    }
+}
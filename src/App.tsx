@@ -2,37 +2,77 @@ import { useState } from 'react';
 import {
   LayoutDashboard,
   Package,
+  AppWindow,
   Shield,
   Settings,
   Sliders,
   Download,
   Cpu,
+  Globe,
+  CalendarClock,
+  Network,
+  Puzzle,
+  Power,
+  MousePointerClick,
+  Sparkles,
+  Search,
+  ShieldAlert,
   Menu,
   X
 } from 'lucide-react';
 import Dashboard from './pages/dashboard/index';
 import Apps from './pages/apps/index';
+import Programs from './pages/programs/index';
 import Privacy from './pages/privacy/index';
 import Services from './pages/services/index';
 import Registry from './pages/registry/index';
 import Updates from './pages/updates/index';
 import System from './pages/system/index';
+import Edge from './pages/edge/index';
+import Tasks from './pages/tasks/index';
+import NetworkPage from './pages/network/index';
+import Features from './pages/features/index';
+import Startup from './pages/startup/index';
+import ContextMenu from './pages/contextmenu/index';
+import Ai from './pages/ai/index';
+import SearchPage from './pages/search/index';
+import { Button } from './components/ui/button';
+import { useElevation } from './hooks/useElevation';
+import { localeLabels, locales, useAppStore } from './store/useAppStore';
+import type { Locale } from './store/useAppStore';
+import type { Category } from './types';
 
-type Page = 'dashboard' | 'apps' | 'privacy' | 'services' | 'registry' | 'updates' | 'system';
+type Page = 'dashboard' | 'apps' | 'programs' | 'privacy' | 'services' | 'registry' | 'updates' | 'system' | 'edge' | 'tasks' | 'network' | 'features' | 'startup' | 'contextmenu' | 'ai' | 'search';
 
 const navItems = [
   { id: 'dashboard' as Page, label: 'Dashboard', icon: LayoutDashboard },
+  { id: 'search' as Page, label: 'Search', icon: Search },
   { id: 'apps' as Page, label: 'Apps', icon: Package },
+  { id: 'programs' as Page, label: 'Programs', icon: AppWindow },
   { id: 'privacy' as Page, label: 'Privacy', icon: Shield },
   { id: 'services' as Page, label: 'Services', icon: Settings },
   { id: 'registry' as Page, label: 'Registry', icon: Sliders },
   { id: 'updates' as Page, label: 'Updates', icon: Download },
   { id: 'system' as Page, label: 'System', icon: Cpu },
+  { id: 'edge' as Page, label: 'Edge', icon: Globe },
+  { id: 'tasks' as Page, label: 'Tasks', icon: CalendarClock },
+  { id: 'network' as Page, label: 'Network', icon: Network },
+  { id: 'features' as Page, label: 'Features', icon: Puzzle },
+  { id: 'startup' as Page, label: 'Startup', icon: Power },
+  { id: 'contextmenu' as Page, label: 'Context Menu', icon: MousePointerClick },
+  { id: 'ai' as Page, label: 'AI & Copilot', icon: Sparkles },
 ];
 
 function App() {
   const [currentPage, setCurrentPage] = useState<Page>('dashboard');
   const [sidebarOpen, setSidebarOpen] = useState(true);
+  const { locale, setLocale } = useAppStore();
+  const elevation = useElevation();
+
+  // Winget apps are listed on the Apps page, Win32 programs on their own page.
+  const openCategory = (category: Category) => {
+    setCurrentPage(category === 'win32' ? 'programs' : category === 'winget' ? 'apps' : category);
+  };
 
   const renderPage = () => {
     switch (currentPage) {
@@ -40,6 +80,8 @@ function App() {
         return <Dashboard />;
       case 'apps':
         return <Apps />;
+      case 'programs':
+        return <Programs />;
       case 'privacy':
         return <Privacy />;
       case 'services':
@@ -50,6 +92,22 @@ function App() {
         return <Updates />;
       case 'system':
         return <System />;
+      case 'edge':
+        return <Edge />;
+      case 'tasks':
+        return <Tasks />;
+      case 'network':
+        return <NetworkPage />;
+      case 'features':
+        return <Features />;
+      case 'startup':
+        return <Startup />;
+      case 'contextmenu':
+        return <ContextMenu />;
+      case 'ai':
+        return <Ai />;
+      case 'search':
+        return <SearchPage onOpenCategory={openCategory} />;
       default:
         return <Dashboard />;
     }
@@ -95,8 +153,34 @@ function App() {
               {sidebarOpen ? <X size={20} /> : <Menu size={20} />}
             </button>
           </div>
+          <select
+            value={locale}
+            onChange={(e) => setLocale(e.target.value as Locale)}
+            aria-label="Catalog language"
+            className="px-3 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+          >
+            {locales.map((code) => (
+              <option key={code} value={code}>
+                {localeLabels[code]}
+              </option>
+            ))}
+          </select>
         </header>
 
+        {elevation.status?.can_relaunch && (
+          <div className="flex items-center justify-between gap-4 px-4 py-2 border-b border-border bg-yellow-500/10">
+            <div className="flex items-center gap-2 text-sm">
+              <ShieldAlert size={16} className="text-yellow-500" />
+              <span>
+                {elevation.error ?? 'Debloater is not running as administrator. Changes to services, HKLM and all users will fail.'}
+              </span>
+            </div>
+            <Button size="sm" onClick={elevation.relaunch} disabled={elevation.relaunching}>
+              {elevation.relaunching ? 'Waiting for UAC...' : 'Restart as administrator'}
+            </Button>
+          </div>
+        )}
+
         <main className="flex-1 overflow-auto p-6">
           {renderPage()}
         </main>
@@ -1,6 +1,8 @@
 import { useState } from 'react';
 import { Search } from 'lucide-react';
 import { useDebloatItems } from '../../hooks/useDebloatItems';
+import { effectLabels } from '../../hooks/usePendingEffects';
+import { riskLevelLabels, riskReasonLabels } from '../../hooks/useRiskPolicy';
 import { useAppStore } from '../../store/useAppStore';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '../../components/ui/card';
 import { Switch } from '../../components/ui/switch';
@@ -114,10 +116,13 @@ export function CategoryPage({ title, description, category }: CategoryPageProps
                   <span
                     className={cn(
                       'text-xs px-2 py-1 rounded-full',
-                      item.safe ? 'bg-green-500/10 text-green-500' : 'bg-yellow-500/10 text-yellow-500'
+                      item.risk.level === 'low' && 'bg-green-500/10 text-green-500',
+                      item.risk.level === 'medium' && 'bg-yellow-500/10 text-yellow-500',
+                      item.risk.level === 'high' && 'bg-orange-500/10 text-orange-500',
+                      item.risk.level === 'critical' && 'bg-destructive/10 text-destructive'
                     )}
                   >
-                    {item.safe ? 'Safe' : 'Use with caution'}
+                    {riskLevelLabels[item.risk.level]}
                   </span>
                   {item.isInstalled !== undefined && (
                     <span
@@ -129,10 +134,44 @@ export function CategoryPage({ title, description, category }: CategoryPageProps
                       {item.isInstalled ? 'Installed' : 'Not installed'}
                     </span>
                   )}
+                  {item.statusError && (
+                    <span
+                      className="text-xs px-2 py-1 rounded-full bg-yellow-500/10 text-yellow-500"
+                      title={item.statusError}
+                    >
+                      Status unknown
+                    </span>
+                  )}
+                  {item.state !== undefined && (
+                    <span
+                      className={cn(
+                        'text-xs px-2 py-1 rounded-full',
+                        item.state === 'applied' && 'bg-blue-500/10 text-blue-500',
+                        item.state === 'partial' && 'bg-yellow-500/10 text-yellow-500',
+                        item.state === 'not_applied' && 'bg-gray-500/10 text-gray-500'
+                      )}
+                    >
+                      {item.state === 'applied'
+                        ? 'Applied'
+                        : item.state === 'partial'
+                          ? 'Partially applied'
+                          : 'Not applied'}
+                    </span>
+                  )}
+                  {item.effect && item.effect !== 'immediate' && (
+                    <span className="text-xs px-2 py-1 rounded-full bg-purple-500/10 text-purple-500">
+                      {effectLabels[item.effect]}
+                    </span>
+                  )}
                   <span className="text-xs text-muted-foreground">
                     {item.category}
                   </span>
                 </div>
+                {item.risk.reasons.length > 0 && (
+                  <p className="text-xs text-muted-foreground mt-2">
+                    {item.risk.reasons.map(reason => riskReasonLabels[reason]).join(' · ')}
+                  </p>
+                )}
               </CardContent>
             </Card>
           ))}
@@ -0,0 +1,63 @@
+import { AlertTriangle, CheckCircle, XCircle } from 'lucide-react';
+import { Card, CardContent, CardHeader, CardTitle } from '../ui/card';
+import { Button } from '../ui/button';
+import { cn } from '../../lib/utils';
+import type { PreflightReport } from '../../types';
+
+interface PreflightModalProps {
+  report: PreflightReport | null;
+  onCancel: () => void;
+  onContinue: (force: boolean) => void;
+}
+
+export function PreflightModal({ report, onCancel, onContinue }: PreflightModalProps) {
+  if (!report) return null;
+
+  return (
+    <div className="fixed inset-0 bg-background/80 backdrop-blur-sm flex items-center justify-center z-50">
+      <Card className="w-full max-w-2xl max-h-[80vh] overflow-hidden">
+        <CardHeader>
+          <CardTitle>{report.blocked ? 'Checks Failed' : 'Before You Continue'}</CardTitle>
+        </CardHeader>
+        <CardContent className="overflow-y-auto max-h-[60vh]">
+          <div className="space-y-3">
+            {report.checks.map((check) => (
+              <div
+                key={check.id}
+                className={cn(
+                  'flex items-start gap-3 p-3 rounded-lg',
+                  check.status === 'block' && 'bg-destructive/10',
+                  check.status === 'warn' && 'bg-yellow-500/10'
+                )}
+              >
+                {check.status === 'pass' && <CheckCircle className="text-primary mt-1" size={18} />}
+                {check.status === 'warn' && <AlertTriangle className="text-yellow-500 mt-1" size={18} />}
+                {check.status === 'block' && <XCircle className="text-destructive mt-1" size={18} />}
+
+                <div className="flex-1 min-w-0">
+                  <p className={cn('font-medium', check.status === 'block' && 'text-destructive')}>
+                    {check.name}
+                  </p>
+                  <p className="text-sm text-muted-foreground">{check.message}</p>
+                </div>
+              </div>
+            ))}
+          </div>
+
+          <div className="mt-6 pt-6 border-t border-border flex justify-end gap-2">
+            <Button variant="outline" onClick={onCancel}>
+              Cancel
+            </Button>
+            {report.blocked ? (
+              <Button variant="destructive" onClick={() => onContinue(true)}>
+                Apply Anyway
+              </Button>
+            ) : (
+              <Button onClick={() => onContinue(false)}>Continue</Button>
+            )}
+          </div>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
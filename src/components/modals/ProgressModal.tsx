@@ -68,7 +68,12 @@ export function ProgressModal({ isOpen, progress, onClose }: ProgressModalProps)
                       <p className="text-sm text-muted-foreground">Running...</p>
                     )}
                     {item.status === 'completed' && (
-                      <p className="text-sm text-muted-foreground">Completed successfully</p>
+                      <p className="text-sm text-muted-foreground">
+                        {item.restartRequired ? 'Completed, restart Windows to finish' : 'Completed successfully'}
+                      </p>
+                    )}
+                    {item.status === 'completed' && item.error && (
+                      <p className="text-sm text-yellow-500 break-words whitespace-pre-wrap">{item.error}</p>
                     )}
                     {item.status === 'failed' && (
                       <p className="text-sm text-destructive break-words whitespace-pre-wrap">{item.error || 'Failed to execute'}</p>
@@ -0,0 +1,52 @@
+import { ShieldAlert } from 'lucide-react';
+import { Card, CardContent, CardHeader, CardTitle } from '../ui/card';
+import { Button } from '../ui/button';
+import { riskReasonLabels } from '../../hooks/useRiskPolicy';
+import type { DebloatItem } from '../../types';
+
+interface RiskConfirmModalProps {
+  items: DebloatItem[];
+  onCancel: () => void;
+  onConfirm: () => void;
+}
+
+export function RiskConfirmModal({ items, onCancel, onConfirm }: RiskConfirmModalProps) {
+  if (items.length === 0) return null;
+
+  return (
+    <div className="fixed inset-0 bg-background/80 backdrop-blur-sm flex items-center justify-center z-50">
+      <Card className="w-full max-w-2xl max-h-[80vh] overflow-hidden">
+        <CardHeader>
+          <CardTitle>Confirm Critical Changes</CardTitle>
+        </CardHeader>
+        <CardContent className="overflow-y-auto max-h-[60vh]">
+          <p className="text-sm text-muted-foreground mb-4">
+            These changes can leave Windows unprotected or hard to repair. Only continue if you know how to undo them.
+          </p>
+          <div className="space-y-3">
+            {items.map((item) => (
+              <div key={item.id} className="flex items-start gap-3 p-3 rounded-lg bg-destructive/10">
+                <ShieldAlert className="text-destructive mt-1" size={18} />
+                <div className="flex-1 min-w-0">
+                  <p className="font-medium text-destructive">{item.name}</p>
+                  <p className="text-sm text-muted-foreground">
+                    {item.risk.reasons.map(reason => riskReasonLabels[reason]).join(' · ') || item.description}
+                  </p>
+                </div>
+              </div>
+            ))}
+          </div>
+
+          <div className="mt-6 pt-6 border-t border-border flex justify-end gap-2">
+            <Button variant="outline" onClick={onCancel}>
+              Cancel
+            </Button>
+            <Button variant="destructive" onClick={onConfirm}>
+              Apply Critical Changes
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
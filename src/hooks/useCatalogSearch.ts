@@ -0,0 +1,57 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { SearchFilters, SearchResult, Tag } from '../types';
+
+export const tags: Tag[] = ['gaming', 'privacy', 'performance', 'security', 'laptop', 'enterprise'];
+
+export const tagLabels: Record<Tag, string> = {
+  gaming: 'Gaming',
+  privacy: 'Privacy',
+  performance: 'Performance',
+  security: 'Security',
+  laptop: 'Laptop',
+  enterprise: 'Enterprise',
+};
+
+export function useCatalogSearch(query: string, filters: SearchFilters) {
+  const [results, setResults] = useState<SearchResult[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Filters are compared by value so callers can pass a fresh object each render.
+  const filterKey = JSON.stringify(filters);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    async function search() {
+      try {
+        setLoading(true);
+        const result = await invoke<SearchResult[]>('search_items', {
+          query,
+          filters: JSON.parse(filterKey) as SearchFilters,
+        });
+        if (!cancelled) {
+          setResults(result);
+          setError(null);
+        }
+      } catch (err) {
+        if (!cancelled) {
+          setError(err instanceof Error ? err.message : String(err));
+        }
+      } finally {
+        if (!cancelled) {
+          setLoading(false);
+        }
+      }
+    }
+
+    search();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [query, filterKey]);
+
+  return { results, loading, error };
+}
@@ -1,11 +1,13 @@
 import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { DebloatItem, BloatwareApp } from '../types';
+import { useAppStore } from '../store/useAppStore';
+import type { DebloatItem, BloatwareApp, ItemStatus } from '../types';
 
 export function useDebloatItems(category: string) {
   const [items, setItems] = useState<DebloatItem[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const locale = useAppStore(state => state.locale);
 
   useEffect(() => {
     async function loadItems() {
@@ -13,24 +15,43 @@ export function useDebloatItems(category: string) {
         setLoading(true);
         
         if (category === 'apps') {
-          const bloatwareApps = await invoke<BloatwareApp[]>('get_all_bloatware_with_status');
+          const bloatwareApps = await invoke<BloatwareApp[]>('get_all_bloatware_with_status', { locale });
           
           const debloatItems: DebloatItem[] = bloatwareApps.map(app => ({
             id: app.id,
             name: app.name,
             description: app.description,
             category: 'apps',
-            safe: app.safe,
-            command: `Get-AppxPackage *${app.package_pattern}* | Remove-AppxPackage`,
+            risk: app.risk,
+            tags: app.tags,
+            command: app.source === 'winget'
+              ? `winget uninstall --id ${app.package_pattern} --exact`
+              : `Get-AppxPackage ${app.package_pattern} | Remove-AppxPackage`,
             rollbackCommand: undefined,
-            isInstalled: app.is_installed,
+            requiresAdmin: true,
+            // Unknown when the app's source could not be queried.
+            isInstalled: app.status_error ? undefined : app.is_installed,
+            statusError: app.status_error ?? undefined,
             packagePattern: app.package_pattern,
           }));
           
           setItems(debloatItems);
         } else {
-          const result = await invoke<DebloatItem[]>('load_items', { category });
-          setItems(result);
+          const loaded = await invoke<DebloatItem[]>('load_items', { category, locale });
+          // Items for other Windows builds, editions or architectures are hidden.
+          const result = loaded.filter(item => item.applicability?.applicable !== false);
+
+          const detectable = result.some(item =>
+            item.policies?.length || item.tasks?.length || item.hosts?.length || item.firewallRules?.length || item.features?.length || item.contextMenu
+          );
+
+          if (detectable) {
+            const statuses = await invoke<ItemStatus[]>('get_item_status', { category });
+            const states = new Map(statuses.map(status => [status.id, status.state]));
+            setItems(result.map(item => ({ ...item, state: states.get(item.id) })));
+          } else {
+            setItems(result);
+          }
         }
         
         setError(null);
@@ -42,7 +63,7 @@ export function useDebloatItems(category: string) {
     }
 
     loadItems();
-  }, [category]);
+  }, [category, locale]);
 
   return { items, loading, error };
 }
@@ -0,0 +1,30 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { PrivilegeStatus } from '../types';
+
+export function useElevation() {
+  const [status, setStatus] = useState<PrivilegeStatus | null>(null);
+  const [relaunching, setRelaunching] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<PrivilegeStatus>('get_privilege_status')
+      .then(setStatus)
+      .catch((err) => setError(err instanceof Error ? err.message : String(err)));
+  }, []);
+
+  // On success the backend exits this instance once the elevated one starts.
+  const relaunch = useCallback(async () => {
+    try {
+      setRelaunching(true);
+      await invoke('relaunch_as_admin');
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setRelaunching(false);
+    }
+  }, []);
+
+  return { status, relaunching, error, relaunch };
+}
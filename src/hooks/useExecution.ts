@@ -1,19 +1,58 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { DebloatItem, ProgressItem } from '../types';
+import type { ApplyTicket, CompositeResult, DebloatItem, PendingEffects, ProgressItem } from '../types';
 
 interface CommandResult {
   success: boolean;
   output: string;
   error?: string | null;
+  restart_needed?: boolean;
+}
+
+async function executeComposite(item: DebloatItem, isRollback: boolean, ticket?: string): Promise<CommandResult> {
+  const result: CompositeResult = await invoke('execute_composite', {
+    category: item.category,
+    id: item.id,
+    isRollback,
+    ticket: ticket ?? null,
+  });
+
+  const failures = result.steps
+    .filter((step) => !step.success && !step.skipped)
+    .map((step) => `${step.name}: ${step.error || 'Unknown error'}`);
+
+  return {
+    success: result.success,
+    output: result.steps.map((step) => step.output).filter(Boolean).join('\n'),
+    error: failures.length > 0 ? failures.join('; ') : null,
+  };
 }
 
 export async function executeItems(
   items: DebloatItem[],
-  onProgress?: (progress: ProgressItem[]) => void
-): Promise<{ success: boolean; errors: string[] }> {
+  onProgress?: (progress: ProgressItem[]) => void,
+  options: { force?: boolean; confirmation?: string } = {}
+): Promise<{ success: boolean; errors: string[]; refused?: boolean; effects?: PendingEffects }> {
   const errors: string[] = [];
   const progress: ProgressItem[] = [];
 
+  // The backend refuses batches that fail a blocking pre-flight check unless
+  // forced, that exceed the risk policy, or with unconfirmed critical items.
+  // Each item of the batch then needs the ticket to run.
+  let ticket: string;
+  try {
+    ({ ticket } = await invoke<ApplyTicket>('begin_apply', {
+      ids: items.map(item => item.id),
+      force: options.force ?? false,
+      confirmation: options.confirmation ?? null,
+    }));
+  } catch (error) {
+    return {
+      success: false,
+      errors: [error instanceof Error ? error.message : String(error)],
+      refused: true,
+    };
+  }
+
   for (const item of items) {
     progress.push({
       id: item.id,
@@ -30,12 +69,52 @@ export async function executeItems(
       
       if (item.packagePattern) {
         result = await invoke('remove_app', {
-          packagePattern: item.packagePattern,
+          id: item.id,
+          ticket,
+        });
+      } else if (item.steps?.length) {
+        result = await executeComposite(item, false, ticket);
+      } else if (item.policies?.length) {
+        result = await invoke('apply_policy_item', {
+          category: item.category,
+          id: item.id,
+          isRollback: false,
+          ticket,
+        });
+      } else if (item.tasks?.length) {
+        result = await invoke('apply_task_item', {
+          category: item.category,
+          id: item.id,
+          isRollback: false,
+          ticket,
+        });
+      } else if (item.features?.length) {
+        result = await invoke('apply_feature_item', {
+          category: item.category,
+          id: item.id,
+          isRollback: false,
+          ticket,
+        });
+      } else if (item.hosts?.length) {
+        result = await invoke('apply_hosts_item', {
+          category: item.category,
+          id: item.id,
+          isRollback: false,
+          ticket,
+        });
+      } else if (item.contextMenu) {
+        result = await invoke('apply_context_menu_item', {
+          category: item.category,
+          id: item.id,
+          isRollback: false,
+          ticket,
         });
       } else {
-        result = await invoke('execute_command', {
-          command: item.command,
+        result = await invoke('execute_item', {
+          category: item.category,
+          id: item.id,
           isRollback: false,
+          ticket,
         });
       }
 
@@ -43,6 +122,9 @@ export async function executeItems(
       if (lastProgress) {
         if (result.success) {
           lastProgress.status = 'completed';
+          lastProgress.restartRequired = result.restart_needed;
+          // Applied, but something around it (e.g. saving the reinstall record) failed.
+          lastProgress.error = result.error || undefined;
         } else {
           lastProgress.status = 'failed';
           lastProgress.error = result.error || 'Unknown error';
@@ -67,13 +149,103 @@ export async function executeItems(
     }
   }
 
+  const effects = await finishApply(
+    progress.filter(item => item.status === 'completed').map(item => item.id)
+  );
+
   return {
     success: errors.length === 0,
     errors,
+    effects,
   };
 }
 
+// Records the items that still need an Explorer restart, sign-out or reboot.
+// Rolling back an item that was still waiting drops its record instead.
+async function finishApply(ids: string[], isRollback = false): Promise<PendingEffects | undefined> {
+  if (ids.length === 0) {
+    return undefined;
+  }
+
+  try {
+    return await invoke<PendingEffects>('finish_apply', { ids, isRollback });
+  } catch (error) {
+    console.error('Failed to record pending effects:', error);
+    return undefined;
+  }
+}
+
 export async function rollbackItem(item: DebloatItem): Promise<{ success: boolean; error?: string }> {
+  if (item.packagePattern) {
+    try {
+      const result: CommandResult = await invoke('reinstall_app', { id: item.id });
+
+      return {
+        success: result.success,
+        error: result.error || undefined,
+      };
+    } catch (error) {
+      return {
+        success: false,
+        error: error instanceof Error ? error.message : String(error),
+      };
+    }
+  }
+
+  if (item.steps?.length) {
+    try {
+      const result = await executeComposite(item, true);
+
+      if (result.success) {
+        await finishApply([item.id], true);
+      }
+
+      return {
+        success: result.success,
+        error: result.error || undefined,
+      };
+    } catch (error) {
+      return {
+        success: false,
+        error: error instanceof Error ? error.message : String(error),
+      };
+    }
+  }
+
+  if (item.policies?.length || item.tasks?.length || item.hosts?.length || item.features?.length || item.contextMenu) {
+    const command = item.policies?.length
+      ? 'apply_policy_item'
+      : item.tasks?.length
+        ? 'apply_task_item'
+        : item.hosts?.length
+          ? 'apply_hosts_item'
+          : item.features?.length
+            ? 'apply_feature_item'
+            : 'apply_context_menu_item';
+
+    try {
+      const result: CommandResult = await invoke(command, {
+        category: item.category,
+        id: item.id,
+        isRollback: true,
+      });
+
+      if (result.success) {
+        await finishApply([item.id], true);
+      }
+
+      return {
+        success: result.success,
+        error: result.error || undefined,
+      };
+    } catch (error) {
+      return {
+        success: false,
+        error: error instanceof Error ? error.message : String(error),
+      };
+    }
+  }
+
   if (!item.rollbackCommand) {
     return {
       success: false,
@@ -82,11 +254,16 @@ export async function rollbackItem(item: DebloatItem): Promise<{ success: boolea
   }
 
   try {
-    const result: CommandResult = await invoke('execute_command', {
-      command: item.rollbackCommand,
+    const result: CommandResult = await invoke('execute_item', {
+      category: item.category,
+      id: item.id,
       isRollback: true,
     });
 
+    if (result.success) {
+      await finishApply([item.id], true);
+    }
+
     return {
       success: result.success,
       error: result.error || undefined,
@@ -0,0 +1,52 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { Effect, PendingEffects } from '../types';
+
+interface CommandResult {
+  success: boolean;
+  output: string;
+  error?: string | null;
+}
+
+export const effectLabels: Record<Effect, string> = {
+  immediate: 'Takes effect immediately',
+  explorer_restart: 'Needs Explorer restart',
+  signout: 'Needs sign-out',
+  reboot: 'Needs restart',
+};
+
+export function usePendingEffects() {
+  const [pending, setPending] = useState<PendingEffects | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      const result = await invoke<PendingEffects>('get_pending_effects');
+      setPending(result);
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const restartExplorer = useCallback(async () => {
+    const result = await invoke<CommandResult>('restart_explorer');
+
+    if (!result.success) {
+      setError(result.error || 'Failed to restart Explorer');
+      return;
+    }
+
+    await reload();
+    // Restarted, but the pending list could not be updated.
+    if (result.error) {
+      setError(result.error);
+    }
+  }, [reload]);
+
+  return { pending, error, reload, restartExplorer };
+}
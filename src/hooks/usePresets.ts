@@ -1,17 +1,19 @@
 import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { useAppStore } from '../store/useAppStore';
 import type { Preset } from '../types';
 
 export function usePresets() {
   const [presets, setPresets] = useState<Preset[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const locale = useAppStore(state => state.locale);
 
   useEffect(() => {
     async function loadPresets() {
       try {
         setLoading(true);
-        const result = await invoke<Preset[]>('load_presets');
+        const result = await invoke<Preset[]>('load_presets', { locale });
         setPresets(result);
         setError(null);
       } catch (err) {
@@ -22,7 +24,7 @@ export function usePresets() {
     }
 
     loadPresets();
-  }, []);
+  }, [locale]);
 
   return { presets, loading, error };
 }
@@ -0,0 +1,50 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { RiskLevel, RiskPolicy, RiskReason } from '../types';
+
+export const riskLevels: RiskLevel[] = ['low', 'medium', 'high', 'critical'];
+
+export const riskLevelLabels: Record<RiskLevel, string> = {
+  low: 'Low risk',
+  medium: 'Medium risk',
+  high: 'High risk',
+  critical: 'Critical risk',
+};
+
+export const riskReasonLabels: Record<RiskReason, string> = {
+  breaks_printing: 'Breaks printing',
+  weakens_security: 'Weakens security',
+  breaks_store_updates: 'Breaks Store updates',
+  delays_security_updates: 'Delays security updates',
+  breaks_search: 'Breaks search',
+  breaks_gaming: 'Breaks games',
+  breaks_devices: 'Breaks devices',
+  breaks_sync: 'Breaks sync',
+  breaks_dependent_apps: 'Breaks apps that depend on it',
+  affects_performance: 'Can reduce performance',
+  reduces_troubleshooting: 'Makes troubleshooting harder',
+  hard_to_reinstall: 'Hard to reinstall',
+};
+
+export function useRiskPolicy() {
+  const [policy, setPolicy] = useState<RiskPolicy | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<RiskPolicy>('get_risk_policy')
+      .then(setPolicy)
+      .catch((err) => setError(err instanceof Error ? err.message : String(err)));
+  }, []);
+
+  const setMaxLevel = useCallback(async (maxLevel: RiskLevel) => {
+    try {
+      await invoke('set_risk_policy', { policy: { maxLevel } });
+      setPolicy({ maxLevel });
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
+
+  return { policy, error, setMaxLevel };
+}
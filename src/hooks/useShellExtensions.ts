@@ -0,0 +1,55 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { ShellExtension } from '../types';
+
+interface CommandResult {
+  success: boolean;
+  output: string;
+  error?: string | null;
+}
+
+export function useShellExtensions() {
+  const [extensions, setExtensions] = useState<ShellExtension[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      setLoading(true);
+      const result = await invoke<ShellExtension[]>('get_shell_extensions');
+      setExtensions(result);
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const setBlocked = useCallback(async (extension: ShellExtension, blocked: boolean) => {
+    const result = await invoke<CommandResult>('set_shell_extension_blocked', {
+      clsid: extension.clsid,
+      blocked,
+    });
+
+    if (!result.success) {
+      setError(`${extension.name}: ${result.error || 'Unknown error'}`);
+      return;
+    }
+
+    setError(null);
+    setExtensions(current =>
+      current.map(existing =>
+        existing.clsid === extension.clsid
+          ? { ...existing, blocked, blocked_for_all_users: blocked && existing.blocked_for_all_users }
+          : existing
+      )
+    );
+  }, []);
+
+  return { extensions, loading, error, reload, setBlocked };
+}
@@ -0,0 +1,51 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { StartupEntry } from '../types';
+
+interface CommandResult {
+  success: boolean;
+  output: string;
+  error?: string | null;
+}
+
+export function useStartupEntries() {
+  const [entries, setEntries] = useState<StartupEntry[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      setLoading(true);
+      const result = await invoke<StartupEntry[]>('get_startup_entries');
+      setEntries(result);
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const setEnabled = useCallback(async (entry: StartupEntry, enabled: boolean) => {
+    const result = await invoke<CommandResult>('set_startup_entry_enabled', {
+      id: entry.id,
+      enabled,
+    });
+
+    if (!result.success) {
+      setError(`${entry.name}: ${result.error || 'Unknown error'}`);
+      return;
+    }
+
+    setError(null);
+    setEntries(current =>
+      current.map(existing => (existing.id === entry.id ? { ...existing, enabled } : existing))
+    );
+  }, []);
+
+  return { entries, loading, error, reload, setEnabled };
+}
@@ -1,4 +1,4 @@
-import { useEffect, useState } from 'react';
+import { useCallback, useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import type { SystemInfo } from '../types';
 
@@ -6,21 +6,23 @@ export function useSystemInfo() {
   const [systemInfo, setSystemInfo] = useState<SystemInfo | null>(null);
   const [loading, setLoading] = useState(true);
 
-  useEffect(() => {
-    async function loadSystemInfo() {
-      try {
-        setLoading(true);
-        const result = await invoke<any>('get_system_info');
-        setSystemInfo(result as SystemInfo);
-      } catch (err) {
-        console.error('Failed to load system info:', err);
-      } finally {
-        setLoading(false);
-      }
+  const load = useCallback(async (command: 'get_system_info' | 'refresh_system_info') => {
+    try {
+      setLoading(true);
+      const result = await invoke<SystemInfo>(command);
+      setSystemInfo(result);
+    } catch (err) {
+      console.error('Failed to load system info:', err);
+    } finally {
+      setLoading(false);
     }
-
-    loadSystemInfo();
   }, []);
 
-  return { systemInfo, loading };
+  useEffect(() => {
+    load('get_system_info');
+  }, [load]);
+
+  const refresh = useCallback(() => load('refresh_system_info'), [load]);
+
+  return { systemInfo, loading, refresh };
 }
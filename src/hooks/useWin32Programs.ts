@@ -0,0 +1,62 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { useAppStore } from '../store/useAppStore';
+import type { ApplyTicket, Win32App } from '../types';
+
+interface CommandResult {
+  success: boolean;
+  output: string;
+  error?: string | null;
+}
+
+export function useWin32Programs() {
+  const [programs, setPrograms] = useState<Win32App[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [uninstalling, setUninstalling] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const locale = useAppStore(state => state.locale);
+
+  const reload = useCallback(async () => {
+    try {
+      setLoading(true);
+      const result = await invoke<Win32App[]>('get_win32_programs_with_status', { locale });
+      setPrograms(result);
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  }, [locale]);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const uninstall = useCallback(async (program: Win32App) => {
+    try {
+      setUninstalling(program.id);
+      // Runs the same risk and pre-flight checks as a batch of one.
+      const { ticket } = await invoke<ApplyTicket>('begin_apply', {
+        ids: [program.id],
+        force: false,
+        confirmation: null,
+      });
+      const result = await invoke<CommandResult>('uninstall_win32_program', { id: program.id, ticket });
+
+      if (!result.success) {
+        setError(`${program.name}: ${result.error || 'Unknown error'}`);
+        return;
+      }
+
+      setError(null);
+      await reload();
+    } catch (err) {
+      setError(`${program.name}: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setUninstalling(null);
+    }
+  }, [reload]);
+
+  return { programs, loading, uninstalling, error, reload, uninstall };
+}
@@ -0,0 +1,11 @@
+import { CategoryPage } from '../../components/cards/CategoryPage';
+
+export default function Ai() {
+  return (
+    <CategoryPage
+      title="AI & Copilot"
+      description="Turn off Copilot, Recall and the AI features built into Windows and its inbox apps"
+      category="ai"
+    />
+  );
+}
@@ -0,0 +1,85 @@
+import { useState } from 'react';
+import { Search } from 'lucide-react';
+import { CategoryPage } from '../../components/cards/CategoryPage';
+import { useShellExtensions } from '../../hooks/useShellExtensions';
+import { Card, CardDescription, CardHeader, CardTitle } from '../../components/ui/card';
+import { Switch } from '../../components/ui/switch';
+
+export default function ContextMenu() {
+  const [searchQuery, setSearchQuery] = useState('');
+  const { extensions, loading, error, setBlocked } = useShellExtensions();
+
+  const filteredExtensions = extensions.filter(extension =>
+    extension.name.toLowerCase().includes(searchQuery.toLowerCase()) ||
+    extension.clsid.toLowerCase().includes(searchQuery.toLowerCase())
+  );
+
+  return (
+    <div className="space-y-10">
+      <CategoryPage
+        title="Context Menu"
+        description="Clean up the Explorer context menu. Changes show after Explorer restarts or you sign in again."
+        category="contextmenu"
+      />
+
+      <div className="space-y-4">
+        <div>
+          <h2 className="text-2xl font-bold mb-2">Shell Extensions</h2>
+          <p className="text-muted-foreground">
+            Every context menu handler registered for files, folders and drives. Switch one off to block it for your account.
+          </p>
+        </div>
+
+        {error && (
+          <div className="bg-destructive/10 border border-destructive/20 rounded-lg p-4">
+            <p className="text-destructive text-sm">{error}</p>
+          </div>
+        )}
+
+        {loading ? (
+          <div className="bg-card border border-border rounded-lg p-6">
+            <p className="text-muted-foreground">Loading data...</p>
+          </div>
+        ) : (
+          <>
+            <div className="relative">
+              <Search className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground" size={18} />
+              <input
+                type="text"
+                placeholder="Search shell extensions..."
+                value={searchQuery}
+                onChange={(e) => setSearchQuery(e.target.value)}
+                className="w-full pl-10 pr-4 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+
+            <p className="text-sm text-muted-foreground">
+              {filteredExtensions.length} extension{filteredExtensions.length !== 1 ? 's' : ''} found
+            </p>
+
+            <div className="grid gap-4">
+              {filteredExtensions.map((extension) => (
+                <Card key={extension.clsid}>
+                  <CardHeader className="flex flex-row items-start justify-between space-y-0 pb-4">
+                    <div className="space-y-1 flex-1 min-w-0">
+                      <CardTitle className="text-lg">{extension.name}</CardTitle>
+                      <CardDescription className="break-all">{extension.clsid}</CardDescription>
+                      <p className="text-xs text-muted-foreground">
+                        {extension.locations.length ? extension.locations.join(', ') : 'No longer registered'}
+                        {extension.blocked_for_all_users && ' · blocked for all users, unblocking requires administrator'}
+                      </p>
+                    </div>
+                    <Switch
+                      checked={!extension.blocked}
+                      onCheckedChange={(enabled) => setBlocked(extension, !enabled)}
+                    />
+                  </CardHeader>
+                </Card>
+              ))}
+            </div>
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
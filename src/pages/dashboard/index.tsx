@@ -1,24 +1,70 @@
 import { useState } from 'react';
-import { Shield, Settings, Play } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { Shield, Settings, Play, RefreshCw, RotateCcw } from 'lucide-react';
 import { useAppStore } from '../../store/useAppStore';
 import { useSystemInfo } from '../../hooks/useSystemInfo';
 import { usePresets } from '../../hooks/usePresets';
 import { useDebloatItems } from '../../hooks/useDebloatItems';
+import { effectLabels, usePendingEffects } from '../../hooks/usePendingEffects';
+import { riskLevelLabels, riskLevels, useRiskPolicy } from '../../hooks/useRiskPolicy';
 import { executeItems } from '../../hooks/useExecution';
 import { Card, CardContent, CardHeader, CardTitle } from '../../components/ui/card';
 import { Button } from '../../components/ui/button';
 import { ProgressModal } from '../../components/modals/ProgressModal';
-import type { ProgressItem } from '../../types';
+import { PreflightModal } from '../../components/modals/PreflightModal';
+import { RiskConfirmModal } from '../../components/modals/RiskConfirmModal';
+import type { BatchPlan, DebloatItem, PreflightReport, ProgressItem, RiskLevel, SystemInfo } from '../../types';
+
+function formatBytes(bytes: number) {
+  return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+}
+
+function formatBuild(info: SystemInfo) {
+  if (info.buildNumber === null) {
+    return 'Unknown';
+  }
+  return info.ubr === null ? `${info.buildNumber}` : `${info.buildNumber}.${info.ubr}`;
+}
+
+function formatManagement(info: SystemInfo) {
+  const parts = [
+    info.domain && `Domain (${info.domain})`,
+    info.azureAdJoined && 'Microsoft Entra ID',
+    info.mdmEnrolled && 'MDM',
+  ].filter(Boolean);
+  return parts.length ? parts.join(', ') : 'Not managed';
+}
+
+function formatSecurity(info: SystemInfo) {
+  const secureBoot = info.secureBoot === null ? 'Legacy BIOS' : `Secure Boot ${info.secureBoot ? 'on' : 'off'}`;
+  const tpm = info.tpmPresent === null ? 'TPM unknown' : info.tpmPresent ? 'TPM present' : 'no TPM';
+  return `${secureBoot}, ${tpm}`;
+}
+
+function describePlanProblems(plan: BatchPlan) {
+  return [
+    ...plan.conflicts.map(conflict => `${conflict.id} conflicts with ${conflict.conflictsWith}`),
+    ...plan.missing.map(missing => `${missing.requiredBy} requires unknown item ${missing.id}`),
+    ...(plan.cycle.length ? [`Dependency cycle: ${plan.cycle.join(' -> ')}`] : []),
+  ];
+}
 
 export default function Dashboard() {
   const { selectedItems, selectedItemCount, applyPreset, addToHistory, clearProgress, updateProgress } = useAppStore();
-  const { systemInfo } = useSystemInfo();
+  const { systemInfo, loading: systemInfoLoading, refresh: refreshSystemInfo } = useSystemInfo();
   const { presets } = usePresets();
   const { items: allItems } = useDebloatItems('apps');
+  const { pending, error: pendingError, reload: reloadPending, restartExplorer } = usePendingEffects();
+  const { policy: riskPolicy, error: riskPolicyError, setMaxLevel } = useRiskPolicy();
 
   const [isProgressModalOpen, setIsProgressModalOpen] = useState(false);
   const [currentProgress, setCurrentProgress] = useState<ProgressItem[]>([]);
   const [isExecuting, setIsExecuting] = useState(false);
+  const [preflightReport, setPreflightReport] = useState<PreflightReport | null>(null);
+  const [batchError, setBatchError] = useState<string | null>(null);
+  const [plannedItems, setPlannedItems] = useState<DebloatItem[]>([]);
+  const [criticalItems, setCriticalItems] = useState<DebloatItem[]>([]);
+  const [pendingForce, setPendingForce] = useState(false);
 
   const handlePresetClick = (presetId: string) => {
     const preset = presets.find(p => p.id === presetId);
@@ -27,32 +73,101 @@ export default function Dashboard() {
     }
   };
 
+  // Expands the selection with required items and puts it in execution order.
+  const planSelection = async (): Promise<DebloatItem[] | null> => {
+    const plan = await invoke<BatchPlan>('plan_batch', { ids: Array.from(selectedItems) });
+    const problems = describePlanProblems(plan);
+
+    if (problems.length > 0) {
+      setBatchError(`Cannot apply this selection: ${problems.join('; ')}`);
+      return null;
+    }
+
+    const byId = new Map([...allItems, ...plan.items].map(item => [item.id, item]));
+    return plan.steps
+      .map(step => byId.get(step.id))
+      .filter((item): item is DebloatItem => item !== undefined);
+  };
+
   const handleApplySelected = async () => {
-    const selectedItemsList = allItems.filter(item => selectedItems.has(item.id));
+    if (selectedItems.size === 0) {
+      return;
+    }
+
+    setBatchError(null);
+    try {
+      const items = await planSelection();
+
+      if (!items || items.length === 0) {
+        return;
+      }
+
+      setPlannedItems(items);
+      const report = await invoke<PreflightReport>('preflight', { ids: items.map(item => item.id) });
+      if (report.checks.every(check => check.status === 'pass')) {
+        await proceed(items, false);
+      } else {
+        setPreflightReport(report);
+      }
+    } catch (err) {
+      setBatchError(err instanceof Error ? err.message : String(err));
+    }
+  };
 
-    if (selectedItemsList.length === 0) {
+  // Critical items need an explicit confirmation, which the backend turns
+  // into a one-time token for this batch.
+  const proceed = async (items: DebloatItem[], force: boolean) => {
+    const critical = items.filter(item => item.risk.level === 'critical');
+
+    if (critical.length > 0) {
+      setPendingForce(force);
+      setCriticalItems(critical);
       return;
     }
 
+    await runBatch(items, force);
+  };
+
+  const handleConfirmCritical = async () => {
+    const ids = criticalItems.map(item => item.id);
+    setCriticalItems([]);
+
+    try {
+      const confirmation = await invoke<string>('confirm_critical_items', { ids });
+      await runBatch(plannedItems, pendingForce, confirmation);
+    } catch (err) {
+      setBatchError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const runBatch = async (items: DebloatItem[], force: boolean, confirmation?: string) => {
     setIsExecuting(true);
     setIsProgressModalOpen(true);
     setCurrentProgress([]);
     updateProgress([]);
 
-    await executeItems(selectedItemsList, (progress) => {
+    const result = await executeItems(items, (progress) => {
       setCurrentProgress(progress);
       updateProgress(progress);
-    });
+    }, { force, confirmation });
+
+    if (result.refused) {
+      setIsProgressModalOpen(false);
+      setBatchError(result.errors.join('; '));
+      setIsExecuting(false);
+      return;
+    }
 
     addToHistory({
       id: Date.now().toString(),
       timestamp: Date.now(),
-      items: selectedItemsList.map(item => item.id),
+      items: items.map(item => item.id),
       type: 'apply',
     });
 
     setIsExecuting(false);
     clearProgress();
+    reloadPending();
   };
 
   return (
@@ -62,6 +177,40 @@ export default function Dashboard() {
         <p className="text-muted-foreground">Overview of your debloating status</p>
       </div>
 
+      {batchError && (
+        <div className="bg-destructive/10 border border-destructive/20 rounded-lg p-4">
+          <p className="text-destructive text-sm">{batchError}</p>
+        </div>
+      )}
+
+      {pending && pending.items.length > 0 && (
+        <Card>
+          <CardHeader className="flex flex-row items-center justify-between space-y-0">
+            <CardTitle>{effectLabels[pending.effect]}</CardTitle>
+            {pending.items.some(item => item.effect === 'explorer_restart') && (
+              <Button variant="outline" size="sm" onClick={restartExplorer}>
+                <RotateCcw size={16} className="mr-2" />
+                Restart Explorer
+              </Button>
+            )}
+          </CardHeader>
+          <CardContent>
+            <p className="text-sm text-muted-foreground mb-2">
+              {pending.items.length} applied change{pending.items.length !== 1 ? 's' : ''} will take effect later
+            </p>
+            <div className="space-y-1">
+              {pending.items.map((item) => (
+                <div key={item.id} className="flex justify-between text-sm">
+                  <span>{item.name}</span>
+                  <span className="text-muted-foreground">{effectLabels[item.effect]}</span>
+                </div>
+              ))}
+            </div>
+            {pendingError && <p className="text-destructive text-sm mt-2">{pendingError}</p>}
+          </CardContent>
+        </Card>
+      )}
+
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
         <Card>
           <CardHeader>
@@ -89,9 +238,11 @@ export default function Dashboard() {
           </CardHeader>
           <CardContent>
             <p className="text-2xl font-bold">
-              {systemInfo?.buildNumber || 'Unknown'}
+              {systemInfo ? formatBuild(systemInfo) : 'Unknown'}
+            </p>
+            <p className="text-sm text-muted-foreground mt-1">
+              Windows Build{systemInfo?.displayVersion && ` (${systemInfo.displayVersion})`}
             </p>
-            <p className="text-sm text-muted-foreground mt-1">Windows Build</p>
           </CardContent>
         </Card>
       </div>
@@ -145,32 +296,121 @@ export default function Dashboard() {
 
       <Card>
         <CardHeader>
+          <CardTitle>Risk Policy</CardTitle>
+        </CardHeader>
+        <CardContent>
+          <div className="flex items-center justify-between gap-4">
+            <div>
+              <p className="font-medium">Maximum risk level</p>
+              <p className="text-sm text-muted-foreground">
+                Items above this level are refused. Critical items always ask for confirmation.
+              </p>
+            </div>
+            <select
+              value={riskPolicy?.maxLevel ?? 'critical'}
+              disabled={!riskPolicy}
+              onChange={(e) => setMaxLevel(e.target.value as RiskLevel)}
+              className="px-3 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+            >
+              {riskLevels.map((level) => (
+                <option key={level} value={level}>
+                  {riskLevelLabels[level]}
+                </option>
+              ))}
+            </select>
+          </div>
+          {riskPolicyError && <p className="text-destructive text-sm mt-2">{riskPolicyError}</p>}
+        </CardContent>
+      </Card>
+
+      <Card>
+        <CardHeader className="flex flex-row items-center justify-between space-y-0">
           <CardTitle>System Information</CardTitle>
+          <Button variant="ghost" size="icon" aria-label="Refresh system information" onClick={refreshSystemInfo} disabled={systemInfoLoading}>
+            <RefreshCw size={16} className={systemInfoLoading ? 'animate-spin' : ''} />
+          </Button>
         </CardHeader>
         <CardContent>
           <div className="space-y-2">
             <div className="flex justify-between py-2 border-b border-border">
               <span className="text-muted-foreground">Operating System</span>
               <span className="font-medium">
-                {systemInfo?.windowsVersion || 'Checking...'}
+                {systemInfo ? systemInfo.windowsVersion : 'Checking...'}
               </span>
             </div>
             <div className="flex justify-between py-2 border-b border-border">
               <span className="text-muted-foreground">Build</span>
               <span className="font-medium">
-                {systemInfo?.buildNumber || 'Checking...'}
+                {systemInfo ? formatBuild(systemInfo) : 'Checking...'}
               </span>
             </div>
-            <div className="flex justify-between py-2">
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Version</span>
+              <span className="font-medium">
+                {systemInfo ? systemInfo.displayVersion || 'Unknown' : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Architecture</span>
+              <span className="font-medium">
+                {systemInfo ? systemInfo.architecture ?? 'Unknown' : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
               <span className="text-muted-foreground">User</span>
               <span className="font-medium">
-                {systemInfo?.username || 'Checking...'}
+                {systemInfo ? `${systemInfo.username}${systemInfo.isElevated ? ' (administrator)' : ''}` : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Management</span>
+              <span className="font-medium">
+                {systemInfo ? formatManagement(systemInfo) : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Security</span>
+              <span className="font-medium">
+                {systemInfo ? formatSecurity(systemInfo) : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Memory</span>
+              <span className="font-medium">
+                {systemInfo ? formatBytes(systemInfo.totalMemoryBytes) : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2 border-b border-border">
+              <span className="text-muted-foreground">Free Space</span>
+              <span className="font-medium">
+                {systemInfo ? `${formatBytes(systemInfo.diskFreeBytes)} of ${formatBytes(systemInfo.diskTotalBytes)} on ${systemInfo.systemDrive}` : 'Checking...'}
+              </span>
+            </div>
+            <div className="flex justify-between py-2">
+              <span className="text-muted-foreground">Pending Restart</span>
+              <span className="font-medium">
+                {systemInfo ? systemInfo.pendingReboot ? 'Yes' : 'No' : 'Checking...'}
               </span>
             </div>
           </div>
         </CardContent>
       </Card>
 
+      <PreflightModal
+        report={preflightReport}
+        onCancel={() => setPreflightReport(null)}
+        onContinue={(force) => {
+          setPreflightReport(null);
+          proceed(plannedItems, force);
+        }}
+      />
+
+      <RiskConfirmModal
+        items={criticalItems}
+        onCancel={() => setCriticalItems([])}
+        onConfirm={handleConfirmCritical}
+      />
+
       <ProgressModal
         isOpen={isProgressModalOpen}
         progress={currentProgress}
@@ -0,0 +1,11 @@
+import { CategoryPage } from '../../components/cards/CategoryPage';
+
+export default function Edge() {
+  return (
+    <CategoryPage
+      title="Microsoft Edge"
+      description="Tame Edge with group policies instead of uninstalling it"
+      category="edge"
+    />
+  );
+}
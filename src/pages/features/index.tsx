@@ -0,0 +1,11 @@
+import { CategoryPage } from '../../components/cards/CategoryPage';
+
+export default function Features() {
+  return (
+    <CategoryPage
+      title="Windows Features"
+      description="Disable optional features and remove legacy Windows capabilities"
+      category="features"
+    />
+  );
+}
@@ -0,0 +1,11 @@
+import { CategoryPage } from '../../components/cards/CategoryPage';
+
+export default function Network() {
+  return (
+    <CategoryPage
+      title="Network Blocking"
+      description="Block telemetry endpoints in the hosts file and telemetry programs in Windows Firewall"
+      category="network"
+    />
+  );
+}
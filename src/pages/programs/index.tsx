@@ -0,0 +1,138 @@
+import { useState } from 'react';
+import { Search } from 'lucide-react';
+import { useWin32Programs } from '../../hooks/useWin32Programs';
+import { riskLevelLabels, riskReasonLabels } from '../../hooks/useRiskPolicy';
+import { Button } from '../../components/ui/button';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '../../components/ui/card';
+import { cn } from '../../lib/utils';
+import type { UninstallEntry } from '../../types';
+
+function isSilent(entry: UninstallEntry) {
+  return entry.product_code !== null || entry.quiet_uninstall_string !== null;
+}
+
+export default function Programs() {
+  const [searchQuery, setSearchQuery] = useState('');
+  const [installedOnly, setInstalledOnly] = useState(true);
+  const { programs, loading, uninstalling, error, uninstall } = useWin32Programs();
+
+  const filteredPrograms = programs.filter(program =>
+    (!installedOnly || program.is_installed) &&
+    (program.name.toLowerCase().includes(searchQuery.toLowerCase()) ||
+      program.description.toLowerCase().includes(searchQuery.toLowerCase()))
+  );
+
+  return (
+    <div className="space-y-6">
+      <div>
+        <h1 className="text-3xl font-bold mb-2">Programs</h1>
+        <p className="text-muted-foreground">
+          Uninstall trialware and OEM utilities installed as desktop programs. Each program is uninstalled on its own.
+        </p>
+      </div>
+
+      {error && (
+        <div className="bg-destructive/10 border border-destructive/20 rounded-lg p-4">
+          <p className="text-destructive text-sm whitespace-pre-line">{error}</p>
+        </div>
+      )}
+
+      {loading ? (
+        <div className="bg-card border border-border rounded-lg p-6">
+          <p className="text-muted-foreground">Loading data...</p>
+        </div>
+      ) : (
+        <div className="space-y-4">
+          <div className="relative">
+            <Search className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground" size={18} />
+            <input
+              type="text"
+              placeholder="Search programs..."
+              value={searchQuery}
+              onChange={(e) => setSearchQuery(e.target.value)}
+              className="w-full pl-10 pr-4 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+          </div>
+
+          <div className="flex items-center justify-between">
+            <p className="text-sm text-muted-foreground">
+              {filteredPrograms.length} program{filteredPrograms.length !== 1 ? 's' : ''} found
+            </p>
+            <button
+              onClick={() => setInstalledOnly(!installedOnly)}
+              className="text-sm text-primary hover:underline"
+            >
+              {installedOnly ? 'Show all' : 'Show installed only'}
+            </button>
+          </div>
+
+          <div className="grid gap-4">
+            {filteredPrograms.map((program) => (
+              <Card key={program.id}>
+                <CardHeader className="flex flex-row items-start justify-between space-y-0 pb-2">
+                  <div className="space-y-1 flex-1">
+                    <CardTitle className="text-lg">{program.name}</CardTitle>
+                    <CardDescription>{program.description}</CardDescription>
+                  </div>
+                  {program.is_installed && (
+                    <Button
+                      variant="destructive"
+                      size="sm"
+                      disabled={uninstalling !== null}
+                      onClick={() => uninstall(program)}
+                    >
+                      {uninstalling === program.id ? 'Uninstalling...' : 'Uninstall'}
+                    </Button>
+                  )}
+                </CardHeader>
+                <CardContent className="pt-0 space-y-2">
+                  <div className="flex items-center gap-2">
+                    <span
+                      className={cn(
+                        'text-xs px-2 py-1 rounded-full',
+                        program.risk.level === 'low' && 'bg-green-500/10 text-green-500',
+                        program.risk.level === 'medium' && 'bg-yellow-500/10 text-yellow-500',
+                        program.risk.level === 'high' && 'bg-orange-500/10 text-orange-500',
+                        program.risk.level === 'critical' && 'bg-destructive/10 text-destructive'
+                      )}
+                    >
+                      {riskLevelLabels[program.risk.level]}
+                    </span>
+                    <span
+                      className={cn(
+                        'text-xs px-2 py-1 rounded-full',
+                        program.is_installed ? 'bg-blue-500/10 text-blue-500' : 'bg-gray-500/10 text-gray-500'
+                      )}
+                    >
+                      {program.is_installed ? 'Installed' : 'Not installed'}
+                    </span>
+                  </div>
+                  {program.risk.reasons.length > 0 && (
+                    <p className="text-xs text-muted-foreground">
+                      {program.risk.reasons.map(reason => riskReasonLabels[reason]).join(' · ')}
+                    </p>
+                  )}
+                  {program.matched.map((entry) => (
+                    <p key={`${entry.hive}-${entry.key_name}`} className="text-xs text-muted-foreground">
+                      {entry.display_name}
+                      {entry.display_version && ` ${entry.display_version}`}
+                      {entry.publisher && ` · ${entry.publisher}`}
+                      {entry.hive === 'HKLM' && ' · all users, requires administrator'}
+                      {!isSilent(entry) && ' · opens the vendor uninstaller'}
+                    </p>
+                  ))}
+                </CardContent>
+              </Card>
+            ))}
+          </div>
+
+          {filteredPrograms.length === 0 && (
+            <div className="text-center py-12">
+              <p className="text-muted-foreground">No programs found matching your search.</p>
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,134 @@
+import { useState } from 'react';
+import { Search as SearchIcon } from 'lucide-react';
+import { tagLabels, tags, useCatalogSearch } from '../../hooks/useCatalogSearch';
+import { riskLevelLabels } from '../../hooks/useRiskPolicy';
+import { useAppStore } from '../../store/useAppStore';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '../../components/ui/card';
+import { Switch } from '../../components/ui/switch';
+import { cn } from '../../lib/utils';
+import type { Category, Tag } from '../../types';
+
+interface SearchProps {
+  onOpenCategory: (category: Category) => void;
+}
+
+export default function Search({ onOpenCategory }: SearchProps) {
+  const [query, setQuery] = useState('');
+  const [selectedTags, setSelectedTags] = useState<Tag[]>([]);
+  const { results, loading, error } = useCatalogSearch(query, { tags: selectedTags, limit: 100 });
+  const { selectedItems, toggleItem } = useAppStore();
+
+  const toggleTag = (tag: Tag) => {
+    setSelectedTags(current =>
+      current.includes(tag) ? current.filter(t => t !== tag) : [...current, tag]
+    );
+  };
+
+  return (
+    <div className="space-y-6">
+      <div>
+        <h1 className="text-3xl font-bold mb-2">Search</h1>
+        <p className="text-muted-foreground">Find tweaks, apps and programs across every category</p>
+      </div>
+
+      <div className="space-y-4">
+        <div className="relative">
+          <SearchIcon className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground" size={18} />
+          <input
+            type="text"
+            placeholder="Search by name, description, tag or id..."
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            className="w-full pl-10 pr-4 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+          />
+        </div>
+
+        <div className="flex flex-wrap gap-2">
+          {tags.map((tag) => (
+            <button
+              key={tag}
+              onClick={() => toggleTag(tag)}
+              className={cn(
+                'text-sm px-3 py-1 rounded-full border transition-colors',
+                selectedTags.includes(tag)
+                  ? 'bg-primary text-primary-foreground border-primary'
+                  : 'border-border text-muted-foreground hover:bg-secondary'
+              )}
+            >
+              {tagLabels[tag]}
+            </button>
+          ))}
+        </div>
+
+        {error && (
+          <div className="bg-destructive/10 border border-destructive/20 rounded-lg p-4">
+            <p className="text-destructive font-medium">Search failed</p>
+            <p className="text-muted-foreground mt-1">{error}</p>
+          </div>
+        )}
+
+        <p className="text-sm text-muted-foreground">
+          {loading ? 'Searching...' : `${results.length} result${results.length !== 1 ? 's' : ''}`}
+        </p>
+
+        <div className="grid gap-4">
+          {results.map((result) => (
+            <Card
+              key={`${result.kind}-${result.id}`}
+              className={cn(
+                'transition-colors hover:bg-accent',
+                selectedItems.has(result.id) && 'border-primary'
+              )}
+            >
+              <CardHeader className="flex flex-row items-start justify-between space-y-0 pb-2">
+                <div className="space-y-1 flex-1">
+                  <CardTitle className="text-lg">{result.name}</CardTitle>
+                  <CardDescription>{result.description}</CardDescription>
+                </div>
+                {/* Win32 programs are uninstalled one at a time from the Programs page. */}
+                {result.kind !== 'program' && (
+                  <Switch
+                    checked={selectedItems.has(result.id)}
+                    onCheckedChange={() => toggleItem(result.id)}
+                  />
+                )}
+              </CardHeader>
+              <CardContent className="pt-0">
+                <div className="flex flex-wrap items-center gap-2">
+                  <span
+                    className={cn(
+                      'text-xs px-2 py-1 rounded-full',
+                      result.risk.level === 'low' && 'bg-green-500/10 text-green-500',
+                      result.risk.level === 'medium' && 'bg-yellow-500/10 text-yellow-500',
+                      result.risk.level === 'high' && 'bg-orange-500/10 text-orange-500',
+                      result.risk.level === 'critical' && 'bg-destructive/10 text-destructive'
+                    )}
+                  >
+                    {riskLevelLabels[result.risk.level]}
+                  </span>
+                  {result.tags.map((tag) => (
+                    <span key={tag} className="text-xs px-2 py-1 rounded-full bg-secondary text-muted-foreground">
+                      {tagLabels[tag]}
+                    </span>
+                  ))}
+                  <button
+                    onClick={() => onOpenCategory(result.category)}
+                    className="text-xs text-muted-foreground hover:text-foreground underline"
+                  >
+                    {result.category}
+                  </button>
+                </div>
+              </CardContent>
+            </Card>
+          ))}
+        </div>
+
+        {!loading && results.length === 0 && (
+          <div className="text-center py-12">
+            <p className="text-muted-foreground">No items found matching your search.</p>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
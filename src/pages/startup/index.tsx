@@ -0,0 +1,89 @@
+import { useState } from 'react';
+import { Search } from 'lucide-react';
+import { useStartupEntries } from '../../hooks/useStartupEntries';
+import { Card, CardDescription, CardHeader, CardTitle } from '../../components/ui/card';
+import { Switch } from '../../components/ui/switch';
+import type { StartupSource } from '../../types';
+
+const sourceLabels: Record<StartupSource, string> = {
+  machine_run: 'Registry (all users)',
+  machine_run32: 'Registry (all users, 32-bit)',
+  machine_run_once: 'Run once (all users)',
+  user_run: 'Registry (current user)',
+  user_run_once: 'Run once (current user)',
+  user_startup_folder: 'Startup folder',
+  common_startup_folder: 'Startup folder (all users)',
+  logon_task: 'Scheduled task at sign-in',
+};
+
+export default function Startup() {
+  const [searchQuery, setSearchQuery] = useState('');
+  const { entries, loading, error, setEnabled } = useStartupEntries();
+
+  const filteredEntries = entries.filter(entry =>
+    entry.name.toLowerCase().includes(searchQuery.toLowerCase()) ||
+    entry.command.toLowerCase().includes(searchQuery.toLowerCase())
+  );
+
+  return (
+    <div className="space-y-6">
+      <div>
+        <h1 className="text-3xl font-bold mb-2">Startup Programs</h1>
+        <p className="text-muted-foreground">
+          Choose which programs start when you sign in. Entries are disabled the same way Task Manager does it.
+        </p>
+      </div>
+
+      {error && (
+        <div className="bg-destructive/10 border border-destructive/20 rounded-lg p-4">
+          <p className="text-destructive text-sm">{error}</p>
+        </div>
+      )}
+
+      {loading ? (
+        <div className="bg-card border border-border rounded-lg p-6">
+          <p className="text-muted-foreground">Loading data...</p>
+        </div>
+      ) : (
+        <div className="space-y-4">
+          <div className="relative">
+            <Search className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground" size={18} />
+            <input
+              type="text"
+              placeholder="Search startup entries..."
+              value={searchQuery}
+              onChange={(e) => setSearchQuery(e.target.value)}
+              className="w-full pl-10 pr-4 py-2 bg-card border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+          </div>
+
+          <p className="text-sm text-muted-foreground">
+            {filteredEntries.length} entr{filteredEntries.length !== 1 ? 'ies' : 'y'} found
+          </p>
+
+          <div className="grid gap-4">
+            {filteredEntries.map((entry) => (
+              <Card key={entry.id}>
+                <CardHeader className="flex flex-row items-start justify-between space-y-0 pb-4">
+                  <div className="space-y-1 flex-1 min-w-0">
+                    <CardTitle className="text-lg">{entry.name}</CardTitle>
+                    <CardDescription className="break-all">{entry.command}</CardDescription>
+                    <p className="text-xs text-muted-foreground">
+                      {sourceLabels[entry.source]}
+                      {entry.requires_admin && ' · requires administrator'}
+                    </p>
+                  </div>
+                  <Switch
+                    checked={entry.enabled}
+                    disabled={!entry.can_toggle}
+                    onCheckedChange={(enabled) => setEnabled(entry, enabled)}
+                  />
+                </CardHeader>
+              </Card>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
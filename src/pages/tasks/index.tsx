@@ -0,0 +1,11 @@
+import { CategoryPage } from '../../components/cards/CategoryPage';
+
+export default function Tasks() {
+  return (
+    <CategoryPage
+      title="Scheduled Tasks"
+      description="Disable telemetry and data collection scheduled tasks"
+      category="tasks"
+    />
+  );
+}
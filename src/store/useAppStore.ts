@@ -2,12 +2,28 @@ import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import type { DebloatItem, Preset, HistoryEntry, ProgressItem } from '../types';
 
+export const locales = ['en', 'de', 'ja'] as const;
+
+export type Locale = typeof locales[number];
+
+export const localeLabels: Record<Locale, string> = {
+  en: 'English',
+  de: 'Deutsch',
+  ja: '日本語',
+};
+
+function systemLocale(): Locale {
+  const language = navigator.language.split('-')[0];
+  return locales.find(locale => locale === language) ?? 'en';
+}
+
 interface AppState {
   selectedItems: Set<string>;
   currentPreset: string | null;
   history: HistoryEntry[];
   currentProgress: ProgressItem[];
   selectedItemCount: number;
+  locale: Locale;
   
   toggleItem: (id: string) => void;
   selectPreset: (presetId: string) => void;
@@ -18,6 +34,7 @@ interface AppState {
   clearSelection: () => void;
   selectAll: (items: DebloatItem[]) => void;
   deselectAll: () => void;
+  setLocale: (locale: Locale) => void;
 }
 
 export const useAppStore = create<AppState>()(
@@ -28,6 +45,7 @@ export const useAppStore = create<AppState>()(
       history: [],
       currentProgress: [],
       selectedItemCount: 0,
+      locale: systemLocale(),
       
       toggleItem: (id) => set((state) => {
         const newSet = new Set(state.selectedItems);
@@ -63,6 +81,8 @@ export const useAppStore = create<AppState>()(
       }),
       
       deselectAll: () => set(() => ({ selectedItems: new Set(), selectedItemCount: 0 })),
+      
+      setLocale: (locale) => set({ locale }),
     }),
     {
       name: 'debloater-storage',
@@ -70,12 +90,14 @@ export const useAppStore = create<AppState>()(
         selectedItems: Array.from(state.selectedItems),
         currentPreset: state.currentPreset,
         history: state.history,
+        locale: state.locale,
       }),
       merge: (persistedState: any, currentState) => ({
         ...currentState,
         selectedItems: new Set(persistedState.selectedItems || []),
         currentPreset: persistedState.currentPreset || null,
         history: persistedState.history || [],
+        locale: persistedState.locale || currentState.locale,
       }),
     }
   )
@@ -3,30 +3,279 @@ export interface DebloatItem {
   name: string;
   description: string;
   category: Category;
-  safe: boolean;
+  risk: Risk;
+  tags: Tag[];
   command: string;
   rollbackCommand?: string;
+  requiresAdmin?: boolean;
+  effect?: Effect;
+  minBuild?: number;
+  maxBuild?: number;
+  editions?: Edition[];
+  architectures?: Architecture[];
+  applicability?: Applicability;
+  requires?: string[];
+  conflictsWith?: string[];
+  runAfter?: string[];
   enabled?: boolean;
   isInstalled?: boolean;
+  statusError?: string;
   packagePattern?: string;
+  steps?: CompositeStep[];
+  policies?: Policy[];
+  tasks?: string[];
+  hosts?: string[];
+  firewallRules?: FirewallRule[];
+  features?: FeatureChange[];
+  contextMenu?: ContextMenuChange;
+  state?: ItemState;
+}
+
+export type ContextMenuChange =
+  | { kind: 'block_handlers'; handlers: { clsid: string; name: string }[] }
+  | { kind: 'classic_menu' };
+
+export interface ShellExtension {
+  clsid: string;
+  name: string;
+  locations: string[];
+  blocked: boolean;
+  blocked_for_all_users: boolean;
+}
+
+export type RiskLevel = 'low' | 'medium' | 'high' | 'critical';
+
+export type RiskReason =
+  | 'breaks_printing'
+  | 'weakens_security'
+  | 'breaks_store_updates'
+  | 'delays_security_updates'
+  | 'breaks_search'
+  | 'breaks_gaming'
+  | 'breaks_devices'
+  | 'breaks_sync'
+  | 'breaks_dependent_apps'
+  | 'affects_performance'
+  | 'reduces_troubleshooting'
+  | 'hard_to_reinstall';
+
+export interface Risk {
+  level: RiskLevel;
+  reasons: RiskReason[];
+}
+
+export interface RiskPolicy {
+  maxLevel: RiskLevel;
+}
+
+export type Tag = 'gaming' | 'privacy' | 'performance' | 'security' | 'laptop' | 'enterprise';
+
+export type SearchKind = 'item' | 'app' | 'program';
+
+export interface SearchFilters {
+  kinds?: SearchKind[];
+  categories?: Category[];
+  tags?: Tag[];
+  maxRisk?: RiskLevel;
+  limit?: number;
+}
+
+export interface SearchResult {
+  kind: SearchKind;
+  id: string;
+  name: string;
+  description: string;
+  category: Category;
+  tags: Tag[];
+  risk: Risk;
+  score: number;
+}
+
+export type Effect = 'immediate' | 'explorer_restart' | 'signout' | 'reboot';
+
+export interface PendingItem {
+  id: string;
+  name: string;
+  effect: Effect;
+}
+
+export interface PendingEffects {
+  effect: Effect;
+  items: PendingItem[];
+}
+
+export type Edition = 'home' | 'pro' | 'enterprise' | 'education';
+
+export type Architecture = 'x64' | 'arm64' | 'x86';
+
+export interface Applicability {
+  applicable: boolean;
+  reasons: string[];
+}
+
+export interface FeatureChange {
+  kind: 'optional_feature' | 'capability';
+  name: string;
+  action: 'enable' | 'disable' | 'remove';
+}
+
+export interface FirewallRule {
+  name: string;
+  program: string;
+}
+
+export type Policy = { key: string; name: string } & (
+  | { type: 'dword'; value: number }
+  | { type: 'string'; value: string }
+);
+
+export type ItemState = 'applied' | 'partial' | 'not_applied';
+
+export interface ItemStatus {
+  id: string;
+  state: ItemState;
+}
+
+export interface CompositeStep {
+  id: string;
+  name: string;
+  command: string;
+  rollbackCommand?: string;
+  continueOnError?: boolean;
+}
+
+export interface StepResult {
+  id: string;
+  name: string;
+  success: boolean;
+  skipped: boolean;
+  output: string;
+  error?: string | null;
+}
+
+export interface CompositeResult {
+  success: boolean;
+  steps: StepResult[];
 }
 
 export interface BloatwareApp {
   id: string;
   name: string;
   description: string;
-  safe: boolean;
+  risk: Risk;
+  tags: Tag[];
+  source: 'appx' | 'winget';
   package_pattern: string;
   is_installed: boolean;
+  installed_for_other_users: boolean | null;
+  provisioned: boolean | null;
+  matched_packages: string[];
+  reinstall: ReinstallStrategy[];
+  status_error: string | null;
+}
+
+export type ReinstallStrategy =
+  | { kind: 'register_manifest'; manifest_path: string }
+  | { kind: 'winget'; id: string }
+  | { kind: 'store'; product_id: string };
+
+export interface PackageInfo {
+  name: string;
+  family_name: string;
+  full_name: string;
+  version: string;
+  publisher: string;
+  architecture: string;
+  install_location: string | null;
+  install_size: number | null;
+  is_framework: boolean;
+  non_removable: boolean;
+  signature_kind: string;
+  dependencies: string[];
+  dependents: string[];
+  removable: boolean;
+}
+
+export interface WingetPackage {
+  id: string;
+  version: string | null;
+  source: string;
+}
+
+export interface DiscoveredPackage {
+  package: PackageInfo;
+  classification: {
+    verdict: 'likely' | 'possible' | 'unlikely';
+    vendor: string | null;
+    reasons: string[];
+  };
+}
+
+export interface UninstallEntry {
+  key_name: string;
+  hive: string;
+  wow64: boolean;
+  display_name: string;
+  display_version: string | null;
+  publisher: string | null;
+  uninstall_string: string | null;
+  quiet_uninstall_string: string | null;
+  product_code: string | null;
+  estimated_size: number | null;
+}
+
+export interface Win32App {
+  id: string;
+  name: string;
+  description: string;
+  category: Category;
+  risk: Risk;
+  tags: Tag[];
+  displayName: string;
+  publisher: string | null;
+  is_installed: boolean;
+  matched: UninstallEntry[];
 }
 
+export type StartupSource =
+  | 'machine_run'
+  | 'machine_run32'
+  | 'machine_run_once'
+  | 'user_run'
+  | 'user_run_once'
+  | 'user_startup_folder'
+  | 'common_startup_folder'
+  | 'logon_task';
+
+export interface StartupEntry {
+  id: string;
+  source: StartupSource;
+  name: string;
+  command: string;
+  location: string;
+  enabled: boolean;
+  disabled_at: number | null;
+  can_toggle: boolean;
+  requires_admin: boolean;
+}
+
+export type RemovalScope = 'current_user' | 'all_users' | 'provisioned';
+
 export type Category = 
   | 'apps'
   | 'privacy'
   | 'services'
   | 'registry'
   | 'updates'
-  | 'system';
+  | 'system'
+  | 'edge'
+  | 'tasks'
+  | 'network'
+  | 'features'
+  | 'contextmenu'
+  | 'ai'
+  | 'win32'
+  | 'winget';
 
 export interface Preset {
   id: string;
@@ -44,8 +293,65 @@ export interface HistoryEntry {
 
 export interface SystemInfo {
   windowsVersion: string;
-  buildNumber: string;
+  displayVersion: string | null;
+  buildNumber: number | null;
+  ubr: number | null;
+  editionId: string;
+  edition: Edition | null;
+  architecture: Architecture | null;
   username: string;
+  domain: string | null;
+  azureAdJoined: boolean;
+  mdmEnrolled: boolean;
+  isElevated: boolean;
+  secureBoot: boolean | null;
+  tpmPresent: boolean | null;
+  totalMemoryBytes: number;
+  systemDrive: string;
+  diskFreeBytes: number;
+  diskTotalBytes: number;
+  onBattery: boolean | null;
+  batteryPercent: number | null;
+  tamperProtected: boolean | null;
+  pendingReboot: boolean;
+}
+
+export interface PlanStep {
+  id: string;
+  requiredBy: string | null;
+}
+
+export interface BatchPlan {
+  steps: PlanStep[];
+  conflicts: { id: string; conflictsWith: string }[];
+  missing: { id: string; requiredBy: string }[];
+  cycle: string[];
+  items: DebloatItem[];
+}
+
+export type CheckStatus = 'pass' | 'warn' | 'block';
+
+export interface CheckResult {
+  id: string;
+  name: string;
+  status: CheckStatus;
+  message: string;
+}
+
+export interface PreflightReport {
+  checks: CheckResult[];
+  blocked: boolean;
+}
+
+// Returned by begin_apply; every apply command of the batch passes the ticket.
+export interface ApplyTicket {
+  ticket: string;
+  preflight: PreflightReport;
+}
+
+export interface PrivilegeStatus {
+  is_elevated: boolean;
+  can_relaunch: boolean;
 }
 
 export interface ProgressItem {
@@ -53,4 +359,5 @@ export interface ProgressItem {
   name: string;
   status: 'pending' | 'running' | 'completed' | 'failed';
   error?: string;
+  restartRequired?: boolean;
 }